@@ -0,0 +1,107 @@
+// Try/catch support for script mode.
+//
+// The parser lowers `try { body } catch err { handler } finally { cleanup }` to a
+// match on `__try_catch(|| Ok({ body }))`, so `err` is a ScriptError built from
+// either a panic payload or an `Err` propagated out of the body with `?`.
+
+#[allow(dead_code)]
+pub struct ScriptError {
+	message: String,
+	payload: Option<Box<dyn std::any::Any + Send>>,
+}
+
+#[allow(dead_code)]
+impl ScriptError {
+	pub fn new(message: impl Into<String>) -> Self {
+		ScriptError { message: message.into(), payload: None }
+	}
+
+	pub fn message(&self) -> &str {
+		&self.message
+	}
+
+	// True if the error was caught from a panic rather than an `Err` value.
+	pub fn is_panic(&self) -> bool {
+		self.payload.is_some()
+	}
+}
+
+impl std::fmt::Display for ScriptError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.message)
+	}
+}
+
+// Debug prints the bare message so `put!(err)` reads like the panic text.
+impl std::fmt::Debug for ScriptError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.message)
+	}
+}
+
+// Any std error, `Box<dyn Error>`, `String` or `&str` can be propagated out of a
+// try body with `?`: whatever `Box<dyn Error>` accepts.
+impl<E: Into<Box<dyn std::error::Error>>> From<E> for ScriptError {
+	fn from(e: E) -> Self {
+		ScriptError::new(e.into().to_string())
+	}
+}
+
+#[allow(dead_code)]
+fn __panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+	if let Some(s) = payload.downcast_ref::<&str>() {
+		s.to_string()
+	} else if let Some(s) = payload.downcast_ref::<String>() {
+		s.clone()
+	} else {
+		"panic".to_string()
+	}
+}
+
+// Threads currently running a try body, once per nesting level.
+#[allow(dead_code)]
+static __TRY_THREADS: std::sync::Mutex<Vec<std::thread::ThreadId>> = std::sync::Mutex::new(Vec::new());
+#[allow(dead_code)]
+static __TRY_HOOK: std::sync::Once = std::sync::Once::new();
+
+#[allow(dead_code)]
+fn __try_threads() -> std::sync::MutexGuard<'static, Vec<std::thread::ThreadId>> {
+	__TRY_THREADS.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// Run a try body, turning a panic into Err(ScriptError).
+// Panics inside a try body don't reach the panic hook: the catch block reports the error.
+// The hook is wrapped once rather than swapped per call, so concurrent try bodies on
+// other threads can't restore the wrong one.
+#[allow(dead_code)]
+pub fn __try_catch<T>(body: impl FnOnce() -> Result<T, ScriptError>) -> Result<T, ScriptError> {
+	__TRY_HOOK.call_once(|| {
+		let previous_hook = std::panic::take_hook();
+		std::panic::set_hook(Box::new(move |info| {
+			if !__try_threads().contains(&std::thread::current().id()) {
+				previous_hook(info);
+			}
+		}));
+	});
+	let thread = std::thread::current().id();
+	__try_threads().push(thread);
+	let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(body));
+	let mut threads = __try_threads();
+	if let Some(i) = threads.iter().rposition(|t| *t == thread) {
+		threads.swap_remove(i);
+	}
+	drop(threads);
+	match outcome {
+		Ok(result) => result,
+		Err(payload) => Err(ScriptError { message: __panic_message(&*payload), payload: Some(payload) }),
+	}
+}
+
+// Re-raise an error after a `finally` block when there is no `catch`.
+#[allow(dead_code)]
+pub fn __try_rethrow(err: ScriptError) -> ! {
+	match err.payload {
+		Some(payload) => std::panic::resume_unwind(payload),
+		None => panic!("{}", err.message),
+	}
+}
@@ -0,0 +1,235 @@
+// Runtime support for `import lib "libfoo.so" { fn add(a: i32, b: i32) -> i32 }`.
+//
+// The parser turns the import into `mod foo` holding a `__DynLib` static, two wrappers
+// per declared function and a `load()` that resolves them all up front. The library is
+// opened on first use: `foo.try_add(1, 2)` returns the `DynloadError` if `add` can't be
+// used, `foo.add(1, 2)` panics with it. Every symbol is resolved together with its `dynexport_meta_<name>`
+// record, whose type and compiler hashes must equal the ones the parser computed from
+// the declared signature; otherwise the call fails with a `DynloadError`. A declaration
+// with `#[dynexport(version = N)]` also needs the item's version, kept in the upper half of
+// `flags`, to be at least N.
+
+// Mirror of the record `#[dynexport]` emits next to each exported item.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct __DynexportMeta {
+	pub type_hash: u64,
+	pub compiler_hash: u32,
+	// Static, nounwind and C-shim bits, and the item version in the upper 16 bits.
+	pub flags: u32,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DynloadError {
+	LibraryNotFound { library: String, message: String },
+	SymbolNotFound { library: String, symbol: String },
+	// The symbol exists but was not exported with `#[dynexport]`.
+	MissingMetadata { library: String, symbol: String },
+	TypeMismatch { library: String, symbol: String, expected: u64, found: u64 },
+	CompilerMismatch { library: String, symbol: String, expected: u32, found: u32 },
+	// The item's `#[dynexport(version = N)]` is lower than the import requires.
+	VersionTooOld { library: String, symbol: String, required: u16, found: u16 },
+	// The library file couldn't be read for its interface descriptions.
+	InterfaceUnavailable { library: String, message: String },
+}
+
+impl std::fmt::Display for DynloadError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			DynloadError::LibraryNotFound { library, message } => {
+				write!(f, "cannot load `{}`: {}", library, message)
+			}
+			DynloadError::SymbolNotFound { library, symbol } => {
+				write!(f, "`{}` has no symbol `{}`", library, symbol)
+			}
+			DynloadError::MissingMetadata { library, symbol } => {
+				write!(f, "`{}` in `{}` is not a #[dynexport] item", symbol, library)
+			}
+			DynloadError::TypeMismatch { library, symbol, expected, found } => write!(
+				f,
+				"`{}` in `{}` has a different signature than imported (type hash {:#018x}, expected {:#018x})",
+				symbol, library, found, expected
+			),
+			DynloadError::CompilerMismatch { library, symbol, expected, found } => write!(
+				f,
+				"`{}` in `{}` was built by a different compiler (compiler hash {:#010x}, expected {:#010x})",
+				symbol, library, found, expected
+			),
+			DynloadError::VersionTooOld { library, symbol, required, found } => write!(
+				f,
+				"`{}` in `{}` is version {}, but at least version {} is required",
+				symbol, library, found, required
+			),
+			DynloadError::InterfaceUnavailable { library, message } => {
+				write!(f, "cannot read the interface of `{}`: {}", library, message)
+			}
+		}
+	}
+}
+
+impl std::error::Error for DynloadError {}
+
+#[cfg(unix)]
+mod __dynload_sys {
+	pub const RTLD_NOW: std::ffi::c_int = 2;
+
+	unsafe extern "C" {
+		pub fn dlopen(filename: *const std::ffi::c_char, flags: std::ffi::c_int) -> *mut std::ffi::c_void;
+		pub fn dlsym(handle: *mut std::ffi::c_void, symbol: *const std::ffi::c_char) -> *mut std::ffi::c_void;
+		pub fn dlerror() -> *mut std::ffi::c_char;
+		#[cfg(target_os = "linux")]
+		pub fn dlinfo(handle: *mut std::ffi::c_void, request: std::ffi::c_int, info: *mut std::ffi::c_void) -> std::ffi::c_int;
+	}
+
+	// Start of glibc's and musl's `struct link_map`, which `dlinfo` hands out.
+	#[cfg(target_os = "linux")]
+	#[repr(C)]
+	pub struct LinkMap {
+		pub l_addr: usize,
+		pub l_name: *const std::ffi::c_char,
+	}
+
+	#[cfg(target_os = "linux")]
+	pub const RTLD_DI_LINKMAP: std::ffi::c_int = 2;
+}
+
+// A library opened on first use. The handle is kept as an address so the static is `Sync`.
+#[allow(dead_code)]
+pub struct __DynLib {
+	path: &'static str,
+	handle: std::sync::OnceLock<Result<usize, DynloadError>>,
+}
+
+#[allow(dead_code)]
+impl __DynLib {
+	pub const fn new(path: &'static str) -> Self {
+		__DynLib { path, handle: std::sync::OnceLock::new() }
+	}
+
+	#[cfg(unix)]
+	fn open(&self) -> Result<usize, DynloadError> {
+		self.handle
+			.get_or_init(|| {
+				let path = std::ffi::CString::new(self.path).map_err(|_| DynloadError::LibraryNotFound {
+					library: self.path.to_string(),
+					message: "path contains a NUL byte".to_string(),
+				})?;
+				let handle = unsafe { __dynload_sys::dlopen(path.as_ptr(), __dynload_sys::RTLD_NOW) };
+				if handle.is_null() {
+					let err = unsafe { __dynload_sys::dlerror() };
+					let message = if err.is_null() {
+						"unknown error".to_string()
+					} else {
+						unsafe { std::ffi::CStr::from_ptr(err) }.to_string_lossy().into_owned()
+					};
+					return Err(DynloadError::LibraryNotFound { library: self.path.to_string(), message });
+				}
+				Ok(handle as usize)
+			})
+			.clone()
+	}
+
+	#[cfg(not(unix))]
+	fn open(&self) -> Result<usize, DynloadError> {
+		Err(DynloadError::LibraryNotFound {
+			library: self.path.to_string(),
+			message: "dynamic loading is not supported on this platform".to_string(),
+		})
+	}
+
+	#[cfg(unix)]
+	fn symbol(&self, name: &str) -> Result<*mut std::ffi::c_void, DynloadError> {
+		let handle = self.open()? as *mut std::ffi::c_void;
+		let Ok(c_name) = std::ffi::CString::new(name) else {
+			return Err(DynloadError::SymbolNotFound { library: self.path.to_string(), symbol: name.to_string() });
+		};
+		Ok(unsafe { __dynload_sys::dlsym(handle, c_name.as_ptr()) })
+	}
+
+	#[cfg(not(unix))]
+	fn symbol(&self, _name: &str) -> Result<*mut std::ffi::c_void, DynloadError> {
+		self.open().map(|_| std::ptr::null_mut())
+	}
+
+	// The file the dynamic loader actually opened, which may differ from `path` when that
+	// was found through the library search path.
+	#[cfg(target_os = "linux")]
+	fn loaded_path(&self) -> Result<String, DynloadError> {
+		let handle = self.open()? as *mut std::ffi::c_void;
+		let mut map: *const __dynload_sys::LinkMap = std::ptr::null();
+		let found = unsafe {
+			__dynload_sys::dlinfo(handle, __dynload_sys::RTLD_DI_LINKMAP, &mut map as *mut _ as *mut std::ffi::c_void)
+		};
+		if found != 0 || map.is_null() || unsafe { (*map).l_name }.is_null() {
+			return Ok(self.path.to_string());
+		}
+		let name = unsafe { std::ffi::CStr::from_ptr((*map).l_name) }.to_string_lossy().into_owned();
+		Ok(if name.is_empty() { self.path.to_string() } else { name })
+	}
+
+	#[cfg(not(target_os = "linux"))]
+	fn loaded_path(&self) -> Result<String, DynloadError> {
+		self.open().map(|_| self.path.to_string())
+	}
+
+	// The interface descriptions of every `#[dynexport]` item in the library.
+	pub fn interface(&self) -> Result<DynexportInterface, DynloadError> {
+		DynexportInterface::read(&self.loaded_path()?)
+	}
+
+	// Look up `name` and check its metadata record against the imported signature.
+	pub fn __resolve(&self, name: &str, type_hash: u64, compiler_hash: u32, version: u16) -> Result<*const (), DynloadError> {
+		let library = self.path.to_string();
+		let symbol = self.symbol(name)?;
+		if symbol.is_null() {
+			return Err(DynloadError::SymbolNotFound { library, symbol: name.to_string() });
+		}
+		let meta = self.symbol(&format!("dynexport_meta_{}", name))?;
+		if meta.is_null() {
+			return Err(DynloadError::MissingMetadata { library, symbol: name.to_string() });
+		}
+		let meta = unsafe { *(meta as *const __DynexportMeta) };
+		if meta.compiler_hash != compiler_hash {
+			return Err(DynloadError::CompilerMismatch {
+				library,
+				symbol: name.to_string(),
+				expected: compiler_hash,
+				found: meta.compiler_hash,
+			});
+		}
+		if meta.type_hash != type_hash {
+			return Err(DynloadError::TypeMismatch {
+				library,
+				symbol: name.to_string(),
+				expected: type_hash,
+				found: meta.type_hash,
+			});
+		}
+		let found = (meta.flags >> 16) as u16;
+		if found < version {
+			return Err(DynloadError::VersionTooOld { library, symbol: name.to_string(), required: version, found });
+		}
+		Ok(symbol as *const ())
+	}
+}
+
+// Resolved address of one imported function, cached after the first successful call.
+#[allow(dead_code)]
+pub struct __DynSym(std::sync::OnceLock<usize>);
+
+#[allow(dead_code)]
+impl __DynSym {
+	pub const fn new() -> Self {
+		__DynSym(std::sync::OnceLock::new())
+	}
+
+	// A failed lookup isn't cached, so a later call tries again.
+	pub fn get(&self, lib: &__DynLib, name: &str, type_hash: u64, compiler_hash: u32, version: u16) -> Result<*const (), DynloadError> {
+		if let Some(addr) = self.0.get() {
+			return Ok(*addr as *const ());
+		}
+		let symbol = lib.__resolve(name, type_hash, compiler_hash, version)?;
+		Ok(*self.0.get_or_init(|| symbol as usize) as *const ())
+	}
+}
@@ -0,0 +1,155 @@
+// A minimal reader for ELF files of either class and byte order.
+//
+// `DynexportInterface::read` finds the `.rustc_dynexport_wit` section with it, and
+// `rustc --inspect-dynexport` includes this file as a module to find the
+// `dynexport_meta_<name>` records of an ELF library through its symbol tables. Every offset
+// and size comes from the file, so each one is checked: a truncated or corrupt file yields
+// `None` (or skips the broken symbol), never a panic.
+
+#[allow(dead_code)]
+pub struct __Elf<'a> {
+	data: &'a [u8],
+	is_64: bool,
+	big_endian: bool,
+	sections: Vec<__ElfSection>,
+	// Index of the section holding the section names.
+	names: u64,
+}
+
+// The fields of a section header the reader needs.
+#[allow(dead_code)]
+#[derive(Clone, Copy)]
+struct __ElfSection {
+	name: u64,
+	kind: u64,
+	address: u64,
+	offset: u64,
+	size: u64,
+	link: u64,
+}
+
+#[allow(dead_code)]
+pub struct __ElfSymbol<'a> {
+	pub name: &'a [u8],
+	section: u64,
+	value: u64,
+}
+
+#[allow(dead_code)]
+impl<'a> __Elf<'a> {
+	const SYMTAB: u64 = 2;
+	const NOBITS: u64 = 8;
+	const DYNSYM: u64 = 11;
+
+	pub fn parse(data: &'a [u8]) -> Option<__Elf<'a>> {
+		if !data.starts_with(b"\x7fELF") {
+			return None;
+		}
+		let is_64 = *data.get(4)? == 2;
+		let big_endian = *data.get(5)? == 2;
+		let mut elf = __Elf { data, is_64, big_endian, sections: Vec::new(), names: 0 };
+		let (shoff, shentsize, shnum, shstrndx) = if is_64 {
+			(elf.read(0x28, 8)?, elf.read(0x3a, 2)?, elf.read(0x3c, 2)?, elf.read(0x3e, 2)?)
+		} else {
+			(elf.read(0x20, 4)?, elf.read(0x2e, 2)?, elf.read(0x30, 2)?, elf.read(0x32, 2)?)
+		};
+		for index in 0..shnum {
+			let header = shoff.checked_add(index.checked_mul(shentsize)?)?;
+			let section = elf.section_header(header)?;
+			elf.sections.push(section);
+		}
+		elf.names = shstrndx;
+		Some(elf)
+	}
+
+	pub fn is_big_endian(&self) -> bool {
+		self.big_endian
+	}
+
+	// The contents of the section called `name`.
+	pub fn section(&self, name: &str) -> Option<&'a [u8]> {
+		let section = self
+			.sections
+			.iter()
+			.find(|section| self.string(self.names, section.name) == Some(name.as_bytes()))?;
+		self.data(section)
+	}
+
+	// The entries of the static and dynamic symbol tables that are defined in a section.
+	pub fn symbols(&self) -> Vec<__ElfSymbol<'a>> {
+		let entry_size: u64 = if self.is_64 { 24 } else { 16 };
+		// Offsets of st_value and st_shndx within an entry.
+		let (value_at, section_at, word) = if self.is_64 { (8, 6, 8) } else { (4, 14, 4) };
+		let mut symbols = Vec::new();
+		for table in &self.sections {
+			if table.kind != Self::SYMTAB && table.kind != Self::DYNSYM {
+				continue;
+			}
+			let Some(entries) = self.data(table) else { continue };
+			for index in 0..entries.len() as u64 / entry_size {
+				// Within `entries`, which lies inside the file, so none of this overflows.
+				let entry = table.offset + index * entry_size;
+				let (Some(name), Some(value), Some(section)) = (
+					self.read(entry, 4),
+					self.read(entry + value_at, word),
+					self.read(entry + section_at, 2),
+				) else {
+					continue;
+				};
+				// Undefined, absolute and common symbols have no bytes in the file.
+				if section == 0 || section >= 0xff00 {
+					continue;
+				}
+				let Some(name) = self.string(table.link, name) else { continue };
+				symbols.push(__ElfSymbol { name, section, value });
+			}
+		}
+		symbols
+	}
+
+	// The bytes from `symbol`'s address to the end of its section.
+	pub fn symbol_data(&self, symbol: &__ElfSymbol<'a>) -> Option<&'a [u8]> {
+		let section = self.sections.get(usize::try_from(symbol.section).ok()?)?;
+		let offset = symbol.value.checked_sub(section.address)?;
+		self.data(section)?.get(usize::try_from(offset).ok()?..)
+	}
+
+	// The unsigned `size`-byte integer at `offset`, in the file's byte order.
+	fn read(&self, offset: u64, size: usize) -> Option<u64> {
+		let start = usize::try_from(offset).ok()?;
+		let bytes = self.data.get(start..start.checked_add(size)?)?;
+		let fold = |value: u64, &byte: &u8| (value << 8) | byte as u64;
+		Some(if self.big_endian { bytes.iter().fold(0, fold) } else { bytes.iter().rev().fold(0, fold) })
+	}
+
+	fn section_header(&self, header: u64) -> Option<__ElfSection> {
+		let word = if self.is_64 { 8 } else { 4 };
+		// sh_addr, sh_offset and sh_size are consecutive words; sh_link follows sh_flags.
+		let (address_at, link_at) = if self.is_64 { (0x10, 0x28) } else { (0x0c, 0x18) };
+		let field = |at: u64, size: usize| self.read(header.checked_add(at)?, size);
+		Some(__ElfSection {
+			name: field(0, 4)?,
+			kind: field(4, 4)?,
+			address: field(address_at, word)?,
+			offset: field(address_at + word as u64, word)?,
+			size: field(address_at + 2 * word as u64, word)?,
+			link: field(link_at, 4)?,
+		})
+	}
+
+	// The file contents of `section`; empty for one that occupies no space, like `.bss`.
+	fn data(&self, section: &__ElfSection) -> Option<&'a [u8]> {
+		if section.kind == Self::NOBITS {
+			return Some(&[]);
+		}
+		let start = usize::try_from(section.offset).ok()?;
+		let end = start.checked_add(usize::try_from(section.size).ok()?)?;
+		self.data.get(start..end)
+	}
+
+	// The NUL-terminated string at `offset` in the string table with index `table`.
+	fn string(&self, table: u64, offset: u64) -> Option<&'a [u8]> {
+		let strings = self.data(self.sections.get(usize::try_from(table).ok()?)?)?;
+		strings.get(usize::try_from(offset).ok()?..)?.split(|&byte| byte == 0).next()
+	}
+}
@@ -0,0 +1,18 @@
+// Go-style errors for script mode.
+//
+// A script function declared `def f() error { .. }` returns `Result<(), Box<dyn Error>>`:
+// the parser lowers `return nil` to `return Ok(())` and the statement `try g()` to `g()?`,
+// `try x := g()` to `let mut x = g()?`. Any error type converts on the way out, so `try`
+// works on `"42".parse::<int>()` as well as on another `error` function.
+//
+// The script harness injects this file as `mod errors`, reached as `errors.New("...")`.
+// Callers test the result Go-style with `err != nil`, which the parser turns into
+// `!(&err).is_nil()` (see `IsNil` in truthy.rs).
+
+// ⚠️ 🚨 DO NOT USE ANY CRATES when compiling rustc where this is a module, not a crate  ⚠️ 🚨
+
+// The `error` holding the message `text`.
+#[allow(non_snake_case)]
+pub fn New(text: impl Into<String>) -> Result<(), Box<dyn std::error::Error>> {
+	Err(text.into().into())
+}
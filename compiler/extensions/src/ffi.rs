@@ -0,0 +1,268 @@
+// The C representation of `#[dynexport]` functions.
+//
+// Every `#[dynexport]` fn with the Rust ABI is also exported as `dynexport_c_<name>`, an
+// `extern "C"` shim whose parameters and return value are the `FfiRepr::Repr` of the Rust
+// types. The shim converts on the way in and out, so the Rust-ABI symbol keeps its
+// signature and its `dynexport_meta_<name>` record. The representation is:
+//
+//   bool, integers, floats      themselves
+//   char                        u32 holding the scalar value
+//   ()                          nothing (a `void` return)
+//   &str                        FfiStr { const uint8_t *ptr; size_t len; }, borrowed for the call
+//   String                      FfiString { uint8_t *ptr; size_t len; }, owned
+//   Vec<T>                      FfiVec { Repr(T) *ptr; size_t len; }, owned
+//   Option<T>                   FfiOption { bool is_some; Repr(T) value; }, `value` unset if None
+//   #[dynexport] struct         a #[repr(C)] struct of its fields' representations
+//   #[dynexport] enum           a #[repr(C, u32)] enum: a uint32_t tag holding the discriminant,
+//                               then a union with one #[repr(C)] struct per variant
+//
+// Owned buffers are allocated with the C `malloc` and belong to whoever receives them, who
+// releases them with `free` once done (nested buffers first). `from_repr` does this for Rust
+// callers: it copies the data out and frees the buffer. Strings are UTF-8 and have a NUL
+// byte after `len` so C can use `ptr` as a C string. Nothing in a representation points
+// into either side's Rust heap, so the two sides need not share an allocator, a compiler
+// or a language.
+//
+// Nothing may unwind out of a shim, so invalid UTF-8 from C is not an error: a `&str`
+// parameter gets it replaced with U+FFFD, and a `&str` nested in another type (a field, an
+// `Option<&str>`) ends before the first invalid byte.
+
+#[allow(dead_code)]
+mod __ffi_sys {
+	unsafe extern "C" {
+		pub fn malloc(size: usize) -> *mut std::ffi::c_void;
+		pub fn free(ptr: *mut std::ffi::c_void);
+	}
+}
+
+#[allow(dead_code)]
+fn __ffi_alloc(size: usize) -> *mut std::ffi::c_void {
+	let ptr = unsafe { __ffi_sys::malloc(size.max(1)) };
+	if ptr.is_null() {
+		std::alloc::handle_alloc_error(std::alloc::Layout::from_size_align(size.max(1), 1).unwrap());
+	}
+	ptr
+}
+
+// A Rust type that crosses the C boundary as `Repr`.
+pub trait FfiRepr: Sized {
+	type Repr;
+
+	fn into_repr(self) -> Self::Repr;
+
+	// `repr` must follow the layout above, e.g. come from `into_repr` on the other side of
+	// the boundary. Owned buffers in it are freed.
+	unsafe fn from_repr(repr: Self::Repr) -> Self;
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct FfiStr {
+	pub ptr: *const u8,
+	pub len: usize,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct FfiString {
+	pub ptr: *mut u8,
+	pub len: usize,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct FfiVec<T> {
+	pub ptr: *mut T,
+	pub len: usize,
+}
+
+#[repr(C)]
+pub struct FfiOption<T> {
+	pub is_some: bool,
+	pub value: std::mem::MaybeUninit<T>,
+}
+
+impl FfiRepr for () {
+	type Repr = ();
+	fn into_repr(self) {}
+	unsafe fn from_repr(_repr: ()) {}
+}
+
+impl FfiRepr for bool {
+	type Repr = bool;
+	fn into_repr(self) -> bool { self }
+	unsafe fn from_repr(repr: bool) -> bool { repr }
+}
+
+impl FfiRepr for char {
+	type Repr = u32;
+	fn into_repr(self) -> u32 { self as u32 }
+	unsafe fn from_repr(repr: u32) -> char { char::from_u32(repr).unwrap_or(char::REPLACEMENT_CHARACTER) }
+}
+
+impl FfiRepr for i8 {
+	type Repr = i8;
+	fn into_repr(self) -> i8 { self }
+	unsafe fn from_repr(repr: i8) -> i8 { repr }
+}
+
+impl FfiRepr for i16 {
+	type Repr = i16;
+	fn into_repr(self) -> i16 { self }
+	unsafe fn from_repr(repr: i16) -> i16 { repr }
+}
+
+impl FfiRepr for i32 {
+	type Repr = i32;
+	fn into_repr(self) -> i32 { self }
+	unsafe fn from_repr(repr: i32) -> i32 { repr }
+}
+
+impl FfiRepr for i64 {
+	type Repr = i64;
+	fn into_repr(self) -> i64 { self }
+	unsafe fn from_repr(repr: i64) -> i64 { repr }
+}
+
+impl FfiRepr for isize {
+	type Repr = isize;
+	fn into_repr(self) -> isize { self }
+	unsafe fn from_repr(repr: isize) -> isize { repr }
+}
+
+impl FfiRepr for u8 {
+	type Repr = u8;
+	fn into_repr(self) -> u8 { self }
+	unsafe fn from_repr(repr: u8) -> u8 { repr }
+}
+
+impl FfiRepr for u16 {
+	type Repr = u16;
+	fn into_repr(self) -> u16 { self }
+	unsafe fn from_repr(repr: u16) -> u16 { repr }
+}
+
+impl FfiRepr for u32 {
+	type Repr = u32;
+	fn into_repr(self) -> u32 { self }
+	unsafe fn from_repr(repr: u32) -> u32 { repr }
+}
+
+impl FfiRepr for u64 {
+	type Repr = u64;
+	fn into_repr(self) -> u64 { self }
+	unsafe fn from_repr(repr: u64) -> u64 { repr }
+}
+
+impl FfiRepr for usize {
+	type Repr = usize;
+	fn into_repr(self) -> usize { self }
+	unsafe fn from_repr(repr: usize) -> usize { repr }
+}
+
+impl FfiRepr for f32 {
+	type Repr = f32;
+	fn into_repr(self) -> f32 { self }
+	unsafe fn from_repr(repr: f32) -> f32 { repr }
+}
+
+impl FfiRepr for f64 {
+	type Repr = f64;
+	fn into_repr(self) -> f64 { self }
+	unsafe fn from_repr(repr: f64) -> f64 { repr }
+}
+
+impl<'a> FfiRepr for &'a str {
+	type Repr = FfiStr;
+
+	fn into_repr(self) -> FfiStr {
+		FfiStr { ptr: self.as_ptr(), len: self.len() }
+	}
+
+	unsafe fn from_repr(repr: FfiStr) -> &'a str {
+		if repr.ptr.is_null() || repr.len == 0 {
+			return "";
+		}
+		let bytes = unsafe { std::slice::from_raw_parts(repr.ptr, repr.len) };
+		match std::str::from_utf8(bytes) {
+			Ok(string) => string,
+			Err(err) => unsafe { std::str::from_utf8_unchecked(&bytes[..err.valid_up_to()]) },
+		}
+	}
+}
+
+#[allow(dead_code)]
+impl FfiStr {
+	// A shim's `&str` parameter, with invalid UTF-8 replaced by U+FFFD.
+	pub unsafe fn to_str_lossy<'a>(self) -> std::borrow::Cow<'a, str> {
+		if self.ptr.is_null() || self.len == 0 {
+			return std::borrow::Cow::Borrowed("");
+		}
+		String::from_utf8_lossy(unsafe { std::slice::from_raw_parts(self.ptr, self.len) })
+	}
+}
+
+impl FfiRepr for String {
+	type Repr = FfiString;
+
+	fn into_repr(self) -> FfiString {
+		let len = self.len();
+		let ptr = __ffi_alloc(len + 1) as *mut u8;
+		unsafe {
+			std::ptr::copy_nonoverlapping(self.as_ptr(), ptr, len);
+			*ptr.add(len) = 0;
+		}
+		FfiString { ptr, len }
+	}
+
+	unsafe fn from_repr(repr: FfiString) -> String {
+		if repr.ptr.is_null() {
+			return String::new();
+		}
+		let bytes = unsafe { std::slice::from_raw_parts(repr.ptr, repr.len) };
+		let string = String::from_utf8_lossy(bytes).into_owned();
+		unsafe { __ffi_sys::free(repr.ptr as *mut std::ffi::c_void) };
+		string
+	}
+}
+
+impl<T: FfiRepr> FfiRepr for Vec<T> {
+	type Repr = FfiVec<T::Repr>;
+
+	fn into_repr(self) -> FfiVec<T::Repr> {
+		let len = self.len();
+		let size = len.checked_mul(std::mem::size_of::<T::Repr>()).expect("dynexport: Vec too large");
+		let ptr = __ffi_alloc(size) as *mut T::Repr;
+		for (i, item) in self.into_iter().enumerate() {
+			unsafe { ptr.add(i).write(item.into_repr()) };
+		}
+		FfiVec { ptr, len }
+	}
+
+	unsafe fn from_repr(repr: FfiVec<T::Repr>) -> Vec<T> {
+		if repr.ptr.is_null() {
+			return Vec::new();
+		}
+		let mut items = Vec::with_capacity(repr.len);
+		for i in 0..repr.len {
+			items.push(unsafe { T::from_repr(repr.ptr.add(i).read()) });
+		}
+		unsafe { __ffi_sys::free(repr.ptr as *mut std::ffi::c_void) };
+		items
+	}
+}
+
+impl<T: FfiRepr> FfiRepr for Option<T> {
+	type Repr = FfiOption<T::Repr>;
+
+	fn into_repr(self) -> FfiOption<T::Repr> {
+		match self {
+			Some(value) => FfiOption { is_some: true, value: std::mem::MaybeUninit::new(value.into_repr()) },
+			None => FfiOption { is_some: false, value: std::mem::MaybeUninit::uninit() },
+		}
+	}
+
+	unsafe fn from_repr(repr: FfiOption<T::Repr>) -> Option<T> {
+		if repr.is_some { Some(unsafe { T::from_repr(repr.value.assume_init()) }) } else { None }
+	}
+}
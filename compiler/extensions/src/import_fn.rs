@@ -0,0 +1,128 @@
+// Argument conversions for `import fn` declarations with strings, variadics or callbacks.
+//
+// Such a declaration becomes `mod __import_<name>` and every call `name(..)` a call of the
+// module's `call!`, which converts each argument where the call is written (see
+// compiler/rustc_parse/src/transformer/import_fn.rs). A `string` parameter takes anything
+// implementing `__CString`: script strings are copied into a NUL-terminated buffer that lives
+// until the C function returns, a `CStr` is passed as is. Arguments in the `...` of a variadic
+// function go through `__CVarArg`:
+//
+//   int (i64)                   long long, printed with %lld
+//   float (f64)                 double
+//   bool                        int
+//   char                        unsigned int holding the scalar value
+//   &str, String, CString       const char *, copied like a `string` parameter
+//   CStr                        const char *, borrowed
+//   raw pointers                themselves
+//
+// Other numbers need a cast, `n as int`: with a second integer type the variadic arguments
+// couldn't infer unsuffixed literals.
+
+// ⚠️ 🚨 DO NOT USE ANY CRATES when compiling rustc where this is a module, not a crate  ⚠️ 🚨
+
+#[allow(dead_code)]
+pub trait __CString {
+	fn __c_string(&self) -> std::borrow::Cow<'_, std::ffi::CStr>;
+}
+
+impl __CString for str {
+	fn __c_string(&self) -> std::borrow::Cow<'_, std::ffi::CStr> {
+		std::borrow::Cow::Owned(__c_string_copy(self))
+	}
+}
+impl __CString for String {
+	fn __c_string(&self) -> std::borrow::Cow<'_, std::ffi::CStr> { self.as_str().__c_string() }
+}
+impl __CString for std::ffi::CStr {
+	fn __c_string(&self) -> std::borrow::Cow<'_, std::ffi::CStr> { std::borrow::Cow::Borrowed(self) }
+}
+impl __CString for std::ffi::CString {
+	fn __c_string(&self) -> std::borrow::Cow<'_, std::ffi::CStr> {
+		std::borrow::Cow::Borrowed(self.as_c_str())
+	}
+}
+impl<T: __CString + ?Sized> __CString for &T {
+	fn __c_string(&self) -> std::borrow::Cow<'_, std::ffi::CStr> { (**self).__c_string() }
+}
+
+#[allow(dead_code)]
+fn __c_string_copy(s: &str) -> std::ffi::CString {
+	match std::ffi::CString::new(s) {
+		Ok(s) => s,
+		Err(err) => panic!("string passed to C has a NUL byte at {}: {:?}", err.nul_position(), s),
+	}
+}
+
+// The copy of a `char *` a C function returned, NULL giving "". Invalid UTF-8 is replaced.
+#[allow(dead_code)]
+pub unsafe fn __c_string_owned(ptr: *const std::ffi::c_char) -> String {
+	if ptr.is_null() {
+		return String::new();
+	}
+	unsafe { std::ffi::CStr::from_ptr(ptr) }.to_string_lossy().into_owned()
+}
+
+// A variadic argument, held until the call returns, then passed as `Held::C`.
+#[allow(dead_code)]
+pub trait __CVarArg {
+	type Held: __CPassed;
+	fn __c_hold(&self) -> Self::Held;
+}
+
+#[allow(dead_code)]
+pub trait __CPassed {
+	type C;
+	fn __c_passed(&self) -> Self::C;
+}
+
+// Only one integer and one float type, so unsuffixed literals are inferred as script numbers.
+impl __CVarArg for i64 { type Held = i64; fn __c_hold(&self) -> i64 { *self } }
+impl __CVarArg for f64 { type Held = f64; fn __c_hold(&self) -> f64 { *self } }
+impl __CVarArg for bool { type Held = i32; fn __c_hold(&self) -> i32 { *self as i32 } }
+impl __CVarArg for char { type Held = u32; fn __c_hold(&self) -> u32 { *self as u32 } }
+
+impl __CVarArg for str {
+	type Held = std::ffi::CString;
+	fn __c_hold(&self) -> std::ffi::CString { __c_string_copy(self) }
+}
+impl __CVarArg for String {
+	type Held = std::ffi::CString;
+	fn __c_hold(&self) -> std::ffi::CString { __c_string_copy(self) }
+}
+impl __CVarArg for std::ffi::CString {
+	type Held = std::ffi::CString;
+	fn __c_hold(&self) -> std::ffi::CString { self.clone() }
+}
+impl __CVarArg for std::ffi::CStr {
+	type Held = *const std::ffi::c_char;
+	fn __c_hold(&self) -> *const std::ffi::c_char { self.as_ptr() }
+}
+impl<T> __CVarArg for *const T {
+	type Held = *const T;
+	fn __c_hold(&self) -> *const T { *self }
+}
+impl<T> __CVarArg for *mut T {
+	type Held = *mut T;
+	fn __c_hold(&self) -> *mut T { *self }
+}
+impl<T: __CVarArg + ?Sized> __CVarArg for &T {
+	type Held = T::Held;
+	fn __c_hold(&self) -> T::Held { (**self).__c_hold() }
+}
+
+impl __CPassed for i32 { type C = i32; fn __c_passed(&self) -> i32 { *self } }
+impl __CPassed for u32 { type C = u32; fn __c_passed(&self) -> u32 { *self } }
+impl __CPassed for i64 { type C = i64; fn __c_passed(&self) -> i64 { *self } }
+impl __CPassed for f64 { type C = f64; fn __c_passed(&self) -> f64 { *self } }
+impl __CPassed for std::ffi::CString {
+	type C = *const std::ffi::c_char;
+	fn __c_passed(&self) -> *const std::ffi::c_char { self.as_ptr() }
+}
+impl<T> __CPassed for *const T {
+	type C = *const T;
+	fn __c_passed(&self) -> *const T { *self }
+}
+impl<T> __CPassed for *mut T {
+	type C = *mut T;
+	fn __c_passed(&self) -> *mut T { *self }
+}
@@ -0,0 +1,146 @@
+// Reading back the interface descriptions `#[dynexport]` embeds in a library.
+//
+// Every `#[dynexport]` item has a NUL-terminated, WIT-style description in the
+// `.rustc_dynexport_wit` section (the format is documented in rustc_abi's dynexport module):
+//
+//   record point { x: f64, y: f64 }
+//   midpoint: func(a: point, b: point) -> point;
+//
+// `DynexportInterface::read("libplugin.so")` collects them from an ELF file (read with
+// elf.rs), and the module generated for `import lib` offers `interface()` for the library it
+// loaded, so a host can discover a plugin's functions and types without a shared header.
+
+#[cfg(feature = "standalone_extension")]
+use crate::elf::__Elf;
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DynexportInterface {
+	// Record, variant and enum definitions, each once.
+	pub types: Vec<String>,
+	pub items: Vec<DynexportItem>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DynexportItem {
+	pub name: String,
+	// What follows the name: `func(a: s32, b: s32) -> s32` or `static s32`.
+	pub signature: String,
+	// Parameter names and WIT types; empty for statics.
+	pub params: Vec<(String, String)>,
+	// The result type of a function, or the type of a static.
+	pub result: Option<String>,
+	pub is_static: bool,
+}
+
+#[allow(dead_code)]
+impl DynexportInterface {
+	// Read the descriptions embedded in the ELF file at `path`.
+	pub fn read(path: &str) -> Result<DynexportInterface, DynloadError> {
+		let unavailable = |message: String| DynloadError::InterfaceUnavailable { library: path.to_string(), message };
+		let bytes = std::fs::read(path).map_err(|err| unavailable(err.to_string()))?;
+		let Some(elf) = __Elf::parse(&bytes) else {
+			return Err(unavailable("not an ELF file".to_string()));
+		};
+		match elf.section(".rustc_dynexport_wit") {
+			Some(section) => Ok(DynexportInterface::parse(&String::from_utf8_lossy(section))),
+			None => Ok(DynexportInterface::default()),
+		}
+	}
+
+	// Parse the contents of the section: descriptions separated by NUL bytes.
+	pub fn parse(section: &str) -> DynexportInterface {
+		let mut interface = DynexportInterface::default();
+		for description in section.split('\0') {
+			for line in description.lines().map(str::trim).filter(|line| !line.is_empty()) {
+				let is_type = ["record ", "variant ", "enum ", "flags "].iter().any(|kw| line.starts_with(kw));
+				if is_type {
+					if !interface.types.iter().any(|ty| ty == line) {
+						interface.types.push(line.to_string());
+					}
+				} else if let Some(item) = __parse_interface_item(line) {
+					interface.items.push(item);
+				}
+			}
+		}
+		interface
+	}
+
+	pub fn item(&self, name: &str) -> Option<&DynexportItem> {
+		self.items.iter().find(|item| item.name == name)
+	}
+
+	pub fn functions(&self) -> Vec<&DynexportItem> {
+		self.items.iter().filter(|item| !item.is_static).collect()
+	}
+
+	// The whole library as one WIT interface block.
+	pub fn to_wit(&self, name: &str) -> String {
+		let mut out = format!("interface {} {{\n", name);
+		for ty in &self.types {
+			out.push_str(&format!("    {}\n", ty));
+		}
+		for item in &self.items {
+			out.push_str(&format!("    {}: {};\n", item.name, item.signature));
+		}
+		out.push_str("}\n");
+		out
+	}
+}
+
+#[allow(dead_code)]
+fn __parse_interface_item(line: &str) -> Option<DynexportItem> {
+	let (name, signature) = line.trim_end_matches(';').split_once(": ")?;
+	let signature = signature.trim().to_string();
+	if let Some(ty) = signature.strip_prefix("static ") {
+		return Some(DynexportItem {
+			name: name.to_string(),
+			params: Vec::new(),
+			result: Some(ty.to_string()),
+			is_static: true,
+			signature,
+		});
+	}
+	let rest = signature.strip_prefix("func(")?;
+	// The parameter list ends at the `)` matching `func(`.
+	let mut depth = 0;
+	let close = rest.char_indices().find(|&(_, c)| {
+		match c {
+			'(' | '<' => depth += 1,
+			')' | '>' if depth > 0 => depth -= 1,
+			')' => return true,
+			_ => {}
+		}
+		false
+	})?.0;
+	let params = __split_top_level(&rest[..close])
+		.into_iter()
+		.filter_map(|param| param.split_once(": ").map(|(n, t)| (n.trim().to_string(), t.trim().to_string())))
+		.collect();
+	let result = rest[close + 1..].trim().strip_prefix("->").map(|ty| ty.trim().to_string());
+	Some(DynexportItem { name: name.to_string(), params, result, is_static: false, signature })
+}
+
+// Split at commas that are not inside `<>` or `()`.
+#[allow(dead_code)]
+fn __split_top_level(list: &str) -> Vec<&str> {
+	let mut parts = Vec::new();
+	let mut depth = 0;
+	let mut start = 0;
+	for (i, c) in list.char_indices() {
+		match c {
+			'(' | '<' => depth += 1,
+			')' | '>' => depth -= 1,
+			',' if depth == 0 => {
+				parts.push(&list[start..i]);
+				start = i + 1;
+			}
+			_ => {}
+		}
+	}
+	if !list[start..].trim().is_empty() {
+		parts.push(&list[start..]);
+	}
+	parts
+}
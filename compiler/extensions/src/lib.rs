@@ -13,3 +13,14 @@
 pub mod val;
 pub mod numbers;
 pub mod macros;
+pub mod catch;
+pub mod maps;
+pub mod dynload;
+pub mod ffi;
+pub mod elf;
+pub mod interface;
+pub mod import_fn;
+#[allow(dead_code)]
+pub mod errors;
+#[allow(dead_code, non_upper_case_globals)]
+pub mod units;
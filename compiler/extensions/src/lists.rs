@@ -39,123 +39,145 @@ fn tail(&self) -> Option<T> { self.as_slice().tail() }
 	fn end(&self) -> Option<T> { self.as_slice().end() }
 }
 
+// Pipelines (mapped, select, without, except, pairs, reversed, sorted) don't copy the list.
+// They return a LazyList that borrows the source, and nothing runs until the result is
+// needed: `.list()`, `==`, `put!`, indexing or a `for` loop.
+// Predicates get elements by reference. The map synonyms hand each element to the closure by
+// value, one clone at a time when it comes from the source list, so `users.apply(u => u.name)`
+// can move fields out; `mapped_ref` / `apply_ref` borrow instead, for non-Clone elements.
 #[allow(dead_code)]
-pub trait ListExtensions<T: Clone> {
+pub trait ListExtensions<T> {
+	// Lazy view of the whole list, the start of every pipeline below
+	fn lazy(&self) -> LazyList<std::slice::Iter<'_, T>, ByRef>;
+
 	// Map synonyms - transform each element
-	fn mapped<U, F: Fn(T) -> U>(&self, f: F) -> Vec<U>;
-	fn apply<U, F: Fn(T) -> U>(&self, f: F) -> Vec<U>;
-	fn transform<U, F: Fn(T) -> U>(&self, f: F) -> Vec<U>;
-	fn convert<U, F: Fn(T) -> U>(&self, f: F) -> Vec<U>;
+	fn mapped<U, F: Fn(T) -> U>(&self, f: F) -> LazyList<LazyMap<std::slice::Iter<'_, T>, F, ByRef>, ByVal> where T: Clone;
+	fn apply<U, F: Fn(T) -> U>(&self, f: F) -> LazyList<LazyMap<std::slice::Iter<'_, T>, F, ByRef>, ByVal> where T: Clone;
+	fn transform<U, F: Fn(T) -> U>(&self, f: F) -> LazyList<LazyMap<std::slice::Iter<'_, T>, F, ByRef>, ByVal> where T: Clone;
+	fn convert<U, F: Fn(T) -> U>(&self, f: F) -> LazyList<LazyMap<std::slice::Iter<'_, T>, F, ByRef>, ByVal> where T: Clone;
+
+	// Borrowing map - the closure gets `&T`, nothing is cloned
+	fn mapped_ref<U, F: Fn(&T) -> U>(&self, f: F) -> LazyList<LazyMapRef<std::slice::Iter<'_, T>, F, ByRef>, ByVal>;
+	fn apply_ref<U, F: Fn(&T) -> U>(&self, f: F) -> LazyList<LazyMapRef<std::slice::Iter<'_, T>, F, ByRef>, ByVal>;
 
 	// Filter synonyms - select elements matching predicate
-	fn filtered<F: Fn(&T) -> bool>(&self, f: F) -> Vec<T>;
-	fn select<F: Fn(&T) -> bool>(&self, f: F) -> Vec<T>;
-	fn chose<F: Fn(&T) -> bool>(&self, f: F) -> Vec<T>;
-	fn that<F: Fn(&T) -> bool>(&self, f: F) -> Vec<T>;
-	fn which<F: Fn(&T) -> bool>(&self, f: F) -> Vec<T>;
+	fn filtered<F: Fn(&T) -> bool>(&self, f: F) -> LazyList<LazySelect<std::slice::Iter<'_, T>, F, ByRef>, ByRef>;
+	fn select<F: Fn(&T) -> bool>(&self, f: F) -> LazyList<LazySelect<std::slice::Iter<'_, T>, F, ByRef>, ByRef>;
+	fn chose<F: Fn(&T) -> bool>(&self, f: F) -> LazyList<LazySelect<std::slice::Iter<'_, T>, F, ByRef>, ByRef>;
+	fn that<F: Fn(&T) -> bool>(&self, f: F) -> LazyList<LazySelect<std::slice::Iter<'_, T>, F, ByRef>, ByRef>;
+	fn which<F: Fn(&T) -> bool>(&self, f: F) -> LazyList<LazySelect<std::slice::Iter<'_, T>, F, ByRef>, ByRef>;
 
 	// Inverse filter utilities
-	// `without` returns a new Vec with all elements equal to `item` removed
-	fn without(&self, item: &T) -> Vec<T> where T: PartialEq;
+	// `without` leaves out all elements equal to `item`
+	fn without<'a>(&'a self, item: &'a T) -> LazyList<LazyWithout<'a, std::slice::Iter<'a, T>, T, ByRef>, ByRef> where T: PartialEq;
 
-	// `except` returns a new Vec with elements matching predicate removed
-	fn except<F: Fn(&T) -> bool>(&self, f: F) -> Vec<T>;
+	// `except` leaves out elements matching predicate
+	fn except<F: Fn(&T) -> bool>(&self, f: F) -> LazyList<LazySelect<std::slice::Iter<'_, T>, F, ByRef>, ByRef>;
 
 	// Element access (non-mutating)
-	fn first_cloned(&self) -> Option<T>;
+	fn first_cloned(&self) -> Option<T> where T: Clone;
 
-	// Enumeration
-	fn pairs(&self) -> Vec<(usize, T)>;
+	// Enumeration, yields (index, element)
+	fn pairs(&self) -> LazyList<LazyPairs<std::slice::Iter<'_, T>, ByRef>, ByVal> where T: Clone;
 
 	// Slicing and copying
-	fn slice(&self, start: usize, end: usize) -> Vec<T>;
-	fn copy(&self) -> Vec<T>;
+	fn slice(&self, start: usize, end: usize) -> Vec<T> where T: Clone;
+	fn copy(&self) -> Vec<T> where T: Clone;
 
 	// Adding elements (non-mutating, returns new vec)
-	fn append(&self, item: T) -> Vec<T>;
-	fn prepend(&self, item: T) -> Vec<T>;
-	fn insert(&self, index: usize, item: T) -> Vec<T>;
+	fn append(&self, item: T) -> Vec<T> where T: Clone;
+	fn prepend(&self, item: T) -> Vec<T> where T: Clone;
+	fn insert(&self, index: usize, item: T) -> Vec<T> where T: Clone;
 
 	// Non-mutating reverse
-	fn reversed(&self) -> Vec<T>;
+	fn reversed(&self) -> LazyList<std::iter::Rev<std::slice::Iter<'_, T>>, ByRef>;
 
 	// Index finding
 	#[allow(nonstandard_style)]
 	fn indexOf(&self, item: &T) -> i64 where T: PartialEq;
 	fn index_of(&self, item: &T) -> i64 where T: PartialEq;
 
-	// Sorting (returns new vec)
-	fn sorted(&self) -> Vec<T> where T: Ord;
+	// Sorting (sorts references, the list itself is left alone)
+	fn sorted(&self) -> LazyList<std::vec::IntoIter<&T>, ByRef> where T: Ord;
 	#[allow(nonstandard_style)]
-	fn sortDesc(&self) -> Vec<T> where T: Ord;
-	fn sort_desc(&self) -> Vec<T> where T: Ord;
+	fn sortDesc(&self) -> LazyList<std::vec::IntoIter<&T>, ByRef> where T: Ord;
+	fn sort_desc(&self) -> LazyList<std::vec::IntoIter<&T>, ByRef> where T: Ord;
 
 	// Random utilities
 	// `random` returns a single random element (cloned) or `None` for empty slices
-	fn random(&self) -> Option<T>;
+	fn random(&self) -> Option<T> where T: Clone;
 
-	// `shuffle` returns a new Vec with the elements shuffled (non-mutating)
-	fn shuffle(&self) -> Vec<T>;
+	// `shuffle` returns the elements in random order (non-mutating)
+	fn shuffle(&self) -> LazyList<std::vec::IntoIter<&T>, ByRef>;
 }
 
-impl<T: Clone, S: AsRef<[T]>> ListExtensions<T> for S {
-	fn mapped<U, F: Fn(T) -> U>(&self, f: F) -> Vec<U> {
-		self.as_ref().iter().cloned().map(f).collect()
+impl<T, S: AsRef<[T]>> ListExtensions<T> for S {
+	fn lazy(&self) -> LazyList<std::slice::Iter<'_, T>, ByRef> {
+		LazyList::new(self.as_ref().iter())
 	}
-	fn apply<U, F: Fn(T) -> U>(&self, f: F) -> Vec<U> { self.mapped(f) }
-	fn transform<U, F: Fn(T) -> U>(&self, f: F) -> Vec<U> { self.mapped(f) }
-	fn convert<U, F: Fn(T) -> U>(&self, f: F) -> Vec<U> { self.mapped(f) }
 
-	fn filtered<F: Fn(&T) -> bool>(&self, f: F) -> Vec<T> {
-		self.as_ref().iter().filter(|x| f(x)).cloned().collect()
+	fn mapped<U, F: Fn(T) -> U>(&self, f: F) -> LazyList<LazyMap<std::slice::Iter<'_, T>, F, ByRef>, ByVal> where T: Clone {
+		self.lazy().mapped(f)
 	}
-	fn select<F: Fn(&T) -> bool>(&self, f: F) -> Vec<T> { self.filtered(f) }
-	fn chose<F: Fn(&T) -> bool>(&self, f: F) -> Vec<T> { self.filtered(f) }
-	fn that<F: Fn(&T) -> bool>(&self, f: F) -> Vec<T> { self.filtered(f) }
-	fn which<F: Fn(&T) -> bool>(&self, f: F) -> Vec<T> { self.filtered(f) }
+	fn apply<U, F: Fn(T) -> U>(&self, f: F) -> LazyList<LazyMap<std::slice::Iter<'_, T>, F, ByRef>, ByVal> where T: Clone { self.mapped(f) }
+	fn transform<U, F: Fn(T) -> U>(&self, f: F) -> LazyList<LazyMap<std::slice::Iter<'_, T>, F, ByRef>, ByVal> where T: Clone { self.mapped(f) }
+	fn convert<U, F: Fn(T) -> U>(&self, f: F) -> LazyList<LazyMap<std::slice::Iter<'_, T>, F, ByRef>, ByVal> where T: Clone { self.mapped(f) }
 
-	fn without(&self, item: &T) -> Vec<T> where T: PartialEq {
-		self.as_ref().iter().filter(|x| x != &item).cloned().collect()
+	fn mapped_ref<U, F: Fn(&T) -> U>(&self, f: F) -> LazyList<LazyMapRef<std::slice::Iter<'_, T>, F, ByRef>, ByVal> {
+		self.lazy().mapped_ref(f)
 	}
+	fn apply_ref<U, F: Fn(&T) -> U>(&self, f: F) -> LazyList<LazyMapRef<std::slice::Iter<'_, T>, F, ByRef>, ByVal> { self.mapped_ref(f) }
 
-	fn except<F: Fn(&T) -> bool>(&self, f: F) -> Vec<T> {
-		self.as_ref().iter().filter(|x| !f(x)).cloned().collect()
+	fn filtered<F: Fn(&T) -> bool>(&self, f: F) -> LazyList<LazySelect<std::slice::Iter<'_, T>, F, ByRef>, ByRef> {
+		self.lazy().filtered(f)
 	}
+	fn select<F: Fn(&T) -> bool>(&self, f: F) -> LazyList<LazySelect<std::slice::Iter<'_, T>, F, ByRef>, ByRef> { self.filtered(f) }
+	fn chose<F: Fn(&T) -> bool>(&self, f: F) -> LazyList<LazySelect<std::slice::Iter<'_, T>, F, ByRef>, ByRef> { self.filtered(f) }
+	fn that<F: Fn(&T) -> bool>(&self, f: F) -> LazyList<LazySelect<std::slice::Iter<'_, T>, F, ByRef>, ByRef> { self.filtered(f) }
+	fn which<F: Fn(&T) -> bool>(&self, f: F) -> LazyList<LazySelect<std::slice::Iter<'_, T>, F, ByRef>, ByRef> { self.filtered(f) }
 
-	fn first_cloned(&self) -> Option<T> {
+	fn without<'a>(&'a self, item: &'a T) -> LazyList<LazyWithout<'a, std::slice::Iter<'a, T>, T, ByRef>, ByRef> where T: PartialEq {
+		self.lazy().without(item)
+	}
+
+	fn except<F: Fn(&T) -> bool>(&self, f: F) -> LazyList<LazySelect<std::slice::Iter<'_, T>, F, ByRef>, ByRef> {
+		self.lazy().except(f)
+	}
+
+	fn first_cloned(&self) -> Option<T> where T: Clone {
 		self.as_ref().first().cloned()
 	}
 
-	fn pairs(&self) -> Vec<(usize, T)> {
-		self.as_ref().iter().cloned().enumerate().collect()
+	fn pairs(&self) -> LazyList<LazyPairs<std::slice::Iter<'_, T>, ByRef>, ByVal> where T: Clone {
+		self.lazy().pairs()
 	}
 
 	// Slicing and copying
-	fn slice(&self, start: usize, end: usize) -> Vec<T> {
+	fn slice(&self, start: usize, end: usize) -> Vec<T> where T: Clone {
 		self.as_ref()[start..end].to_vec()
 	}
-	fn copy(&self) -> Vec<T> { self.as_ref().to_vec() }
+	fn copy(&self) -> Vec<T> where T: Clone { self.as_ref().to_vec() }
 
 	// Adding elements (non-mutating)
-	fn append(&self, item: T) -> Vec<T> {
+	fn append(&self, item: T) -> Vec<T> where T: Clone {
 		let mut v = self.as_ref().to_vec();
 		v.push(item);
 		v
 	}
-	fn prepend(&self, item: T) -> Vec<T> {
+	fn prepend(&self, item: T) -> Vec<T> where T: Clone {
 		let mut v = vec![item];
 		v.extend(self.as_ref().iter().cloned());
 		v
 	}
-	fn insert(&self, index: usize, item: T) -> Vec<T> {
+	fn insert(&self, index: usize, item: T) -> Vec<T> where T: Clone {
 		let mut v = self.as_ref().to_vec();
 		Vec::insert(&mut v, index, item);
 		v
 	}
 
 	// Non-mutating reverse
-	fn reversed(&self) -> Vec<T> {
-		self.as_ref().iter().rev().cloned().collect()
+	fn reversed(&self) -> LazyList<std::iter::Rev<std::slice::Iter<'_, T>>, ByRef> {
+		self.lazy().reversed()
 	}
 
 	// Index finding - returns -1 if not found (like JS/Python convention)
@@ -165,39 +187,381 @@ fn indexOf(&self, item: &T) -> i64 where T: PartialEq {
 	}
 	fn index_of(&self, item: &T) -> i64 where T: PartialEq { self.indexOf(item) }
 
-	// Sorting (returns new sorted vec)
-	fn sorted(&self) -> Vec<T> where T: Ord {
-		let mut v = self.as_ref().to_vec();
-		v.sort();
-		v
+	fn sorted(&self) -> LazyList<std::vec::IntoIter<&T>, ByRef> where T: Ord {
+		self.lazy().sorted()
 	}
 	#[allow(nonstandard_style)]
-	fn sortDesc(&self) -> Vec<T> where T: Ord {
-		let mut v = self.as_ref().to_vec();
-		v.sort();
-		v.reverse();
-		v
+	fn sortDesc(&self) -> LazyList<std::vec::IntoIter<&T>, ByRef> where T: Ord {
+		self.lazy().sortDesc()
 	}
-	fn sort_desc(&self) -> Vec<T> where T: Ord { self.sortDesc() }
+	fn sort_desc(&self) -> LazyList<std::vec::IntoIter<&T>, ByRef> where T: Ord { self.sortDesc() }
 
 	// Random utilities implementation
-	fn random(&self) -> Option<T> {
+	fn random(&self) -> Option<T> where T: Clone {
 		let slice = self.as_ref();
 		if slice.is_empty() { return None }
 		let idx = rand::rng().random_range(0..slice.len());
 		slice.get(idx).cloned()
 	}
 
-	fn shuffle(&self) -> Vec<T> {
-		let mut v = self.as_ref().to_vec();
+	fn shuffle(&self) -> LazyList<std::vec::IntoIter<&T>, ByRef> {
+		self.lazy().shuffle()
+	}
+}
+
+// How a pipeline hands its items to closures: `ByRef` items are `&T` borrowed from the
+// source list, `ByVal` items are values made along the way (by `mapped` or `pairs`).
+#[allow(dead_code)]
+pub trait ListMode<X> {
+	type Elem;
+	fn elem(x: &X) -> &Self::Elem;
+}
+
+// Modes that can turn an item into an owned element, for `.list()`, `head()` and friends
+#[allow(dead_code)]
+pub trait ListOwn<X>: ListMode<X> {
+	fn own(x: X) -> Self::Elem;
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ByRef;
+
+#[derive(Clone, Copy, Debug)]
+pub struct ByVal;
+
+impl<'a, T> ListMode<&'a T> for ByRef {
+	type Elem = T;
+	fn elem<'b>(x: &'b &'a T) -> &'b T { x }
+}
+
+impl<'a, T: Clone> ListOwn<&'a T> for ByRef {
+	fn own(x: &'a T) -> T { x.clone() }
+}
+
+impl<U> ListMode<U> for ByVal {
+	type Elem = U;
+	fn elem(x: &U) -> &U { x }
+}
+
+impl<U> ListOwn<U> for ByVal {
+	fn own(x: U) -> U { x }
+}
+
+// A list pipeline that hasn't run yet.
+// Comparing, printing or indexing runs it once and keeps the items (references for `ByRef`).
+#[allow(dead_code)]
+pub struct LazyList<I: Iterator, M> {
+	iter: I,
+	items: std::cell::OnceCell<Vec<I::Item>>,
+	mode: std::marker::PhantomData<M>,
+}
+
+#[allow(dead_code)]
+impl<I: Iterator, M: ListMode<I::Item>> LazyList<I, M> {
+	pub fn new(iter: I) -> Self {
+		LazyList { iter, items: std::cell::OnceCell::new(), mode: std::marker::PhantomData }
+	}
+
+	// Map synonyms - closures get each element by value (cloned when borrowed from the source)
+	pub fn mapped<U, F: Fn(M::Elem) -> U>(self, f: F) -> LazyList<LazyMap<I, F, M>, ByVal> where M: ListOwn<I::Item> {
+		LazyList::new(LazyMap { iter: self.iter, f, mode: std::marker::PhantomData })
+	}
+	pub fn apply<U, F: Fn(M::Elem) -> U>(self, f: F) -> LazyList<LazyMap<I, F, M>, ByVal> where M: ListOwn<I::Item> { self.mapped(f) }
+	pub fn transform<U, F: Fn(M::Elem) -> U>(self, f: F) -> LazyList<LazyMap<I, F, M>, ByVal> where M: ListOwn<I::Item> { self.mapped(f) }
+	pub fn convert<U, F: Fn(M::Elem) -> U>(self, f: F) -> LazyList<LazyMap<I, F, M>, ByVal> where M: ListOwn<I::Item> { self.mapped(f) }
+
+	// Borrowing map - closures get each element by reference
+	pub fn mapped_ref<U, F: Fn(&M::Elem) -> U>(self, f: F) -> LazyList<LazyMapRef<I, F, M>, ByVal> {
+		LazyList::new(LazyMapRef { iter: self.iter, f, mode: std::marker::PhantomData })
+	}
+	pub fn apply_ref<U, F: Fn(&M::Elem) -> U>(self, f: F) -> LazyList<LazyMapRef<I, F, M>, ByVal> { self.mapped_ref(f) }
+
+	// Filter synonyms
+	pub fn filtered<F: Fn(&M::Elem) -> bool>(self, f: F) -> LazyList<LazySelect<I, F, M>, M> {
+		LazyList::new(LazySelect { iter: self.iter, f, keep: true, mode: std::marker::PhantomData })
+	}
+	pub fn select<F: Fn(&M::Elem) -> bool>(self, f: F) -> LazyList<LazySelect<I, F, M>, M> { self.filtered(f) }
+	pub fn chose<F: Fn(&M::Elem) -> bool>(self, f: F) -> LazyList<LazySelect<I, F, M>, M> { self.filtered(f) }
+	pub fn that<F: Fn(&M::Elem) -> bool>(self, f: F) -> LazyList<LazySelect<I, F, M>, M> { self.filtered(f) }
+	pub fn which<F: Fn(&M::Elem) -> bool>(self, f: F) -> LazyList<LazySelect<I, F, M>, M> { self.filtered(f) }
+
+	pub fn except<F: Fn(&M::Elem) -> bool>(self, f: F) -> LazyList<LazySelect<I, F, M>, M> {
+		LazyList::new(LazySelect { iter: self.iter, f, keep: false, mode: std::marker::PhantomData })
+	}
+
+	pub fn without(self, item: &M::Elem) -> LazyList<LazyWithout<'_, I, M::Elem, M>, M> where M::Elem: PartialEq {
+		LazyList::new(LazyWithout { iter: self.iter, item, mode: std::marker::PhantomData })
+	}
+
+	pub fn pairs(self) -> LazyList<LazyPairs<I, M>, ByVal> where M: ListOwn<I::Item> {
+		LazyList::new(LazyPairs { iter: self.iter.enumerate(), mode: std::marker::PhantomData })
+	}
+
+	pub fn reversed(self) -> LazyList<std::iter::Rev<I>, M> where I: DoubleEndedIterator {
+		LazyList::new(self.iter.rev())
+	}
+
+	// Sorting has to see every item, but only the items are buffered, not copies of the elements
+	pub fn sorted(self) -> LazyList<std::vec::IntoIter<I::Item>, M> where M::Elem: Ord {
+		let mut items: Vec<I::Item> = self.iter.collect();
+		items.sort_by(|a, b| M::elem(a).cmp(M::elem(b)));
+		LazyList::new(items.into_iter())
+	}
+	#[allow(nonstandard_style)]
+	pub fn sortDesc(self) -> LazyList<std::vec::IntoIter<I::Item>, M> where M::Elem: Ord {
+		let mut items: Vec<I::Item> = self.iter.collect();
+		items.sort_by(|a, b| M::elem(b).cmp(M::elem(a)));
+		LazyList::new(items.into_iter())
+	}
+	pub fn sort_desc(self) -> LazyList<std::vec::IntoIter<I::Item>, M> where M::Elem: Ord { self.sortDesc() }
+
+	pub fn shuffle(self) -> LazyList<std::vec::IntoIter<I::Item>, M> {
+		let mut items: Vec<I::Item> = self.iter.collect();
 		// Fisher-Yates shuffle
-		let mut n = v.len();
+		let mut n = items.len();
 		while n > 1 {
-			let j = rand::rng().random_range(0..len(self));
+			let j = rand::rng().random_range(0..n);
 			n -= 1;
-			v.swap(n, j);
+			items.swap(n, j);
 		}
-		v
+		LazyList::new(items.into_iter())
+	}
+
+	// Materialize into a Vec of owned elements (cloned when borrowed from the source)
+	pub fn list(self) -> Vec<M::Elem> where M: ListOwn<I::Item> {
+		self.iter.map(M::own).collect()
+	}
+
+	// First / last element (owned)
+	pub fn head(mut self) -> Option<M::Elem> where M: ListOwn<I::Item> { self.iter.next().map(M::own) }
+	pub fn start(self) -> Option<M::Elem> where M: ListOwn<I::Item> { self.head() }
+	pub fn begin(self) -> Option<M::Elem> where M: ListOwn<I::Item> { self.head() }
+
+	pub fn tail(self) -> Option<M::Elem> where M: ListOwn<I::Item> { self.iter.last().map(M::own) }
+	pub fn end(self) -> Option<M::Elem> where M: ListOwn<I::Item> { self.tail() }
+
+	pub fn random(self) -> Option<M::Elem> where M: ListOwn<I::Item> {
+		let mut items: Vec<I::Item> = self.iter.collect();
+		if items.is_empty() { return None }
+		let idx = rand::rng().random_range(0..items.len());
+		Some(M::own(items.swap_remove(idx)))
+	}
+
+	#[allow(nonstandard_style)]
+	pub fn indexOf(self, item: &M::Elem) -> i64 where M::Elem: PartialEq {
+		let mut iter = self.iter;
+		iter.position(|x| M::elem(&x) == item).map(|i| i as i64).unwrap_or(-1)
+	}
+	pub fn index_of(self, item: &M::Elem) -> i64 where M::Elem: PartialEq { self.indexOf(item) }
+
+	// Take elements off either end, like the Vec methods of the same names
+	pub fn shift(&mut self) -> Option<M::Elem> where M: ListOwn<I::Item> {
+		self.items.take();
+		self.iter.next().map(M::own)
+	}
+	pub fn pop_first(&mut self) -> Option<M::Elem> where M: ListOwn<I::Item> { self.shift() }
+
+	pub fn pop(&mut self) -> Option<M::Elem> where M: ListOwn<I::Item>, I: DoubleEndedIterator {
+		self.items.take();
+		self.iter.next_back().map(M::own)
+	}
+	pub fn pop_last(&mut self) -> Option<M::Elem> where M: ListOwn<I::Item>, I: DoubleEndedIterator { self.pop() }
+}
+
+#[allow(dead_code)]
+impl<I: Iterator + Clone, M: ListMode<I::Item>> LazyList<I, M> {
+	// Run the pipeline once and keep the items
+	fn items(&self) -> &[I::Item] {
+		self.items.get_or_init(|| self.iter.clone().collect())
+	}
+
+	pub fn len(&self) -> usize { self.items().len() }
+	pub fn size(&self) -> usize { self.len() }
+	pub fn length(&self) -> usize { self.len() }
+	pub fn is_empty(&self) -> bool { self.items().is_empty() }
+	pub fn iter(&self) -> impl Iterator<Item = &M::Elem> + '_ { self.items().iter().map(M::elem) }
+
+	fn eq_items<R>(&self, other: &[R]) -> bool where M::Elem: PartialEq<R> {
+		let items = self.items();
+		items.len() == other.len() && items.iter().zip(other).all(|(a, b)| M::elem(a) == b)
+	}
+}
+
+impl<I: Iterator + Clone, M> Clone for LazyList<I, M> {
+	fn clone(&self) -> Self {
+		LazyList { iter: self.iter.clone(), items: std::cell::OnceCell::new(), mode: std::marker::PhantomData }
+	}
+}
+
+impl<I: Iterator, M> IntoIterator for LazyList<I, M> {
+	type Item = I::Item;
+	type IntoIter = I;
+	fn into_iter(self) -> I { self.iter }
+}
+
+impl<I: Iterator + Clone, M: ListMode<I::Item>> std::fmt::Debug for LazyList<I, M> where M::Elem: std::fmt::Debug {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_list().entries(self.items().iter().map(M::elem)).finish()
+	}
+}
+
+impl<I: Iterator + Clone, M: ListMode<I::Item>> std::ops::Index<usize> for LazyList<I, M> {
+	type Output = M::Elem;
+	fn index(&self, index: usize) -> &M::Elem { M::elem(&self.items()[index]) }
+}
+
+// `x in xs.select(..)`, like `x in vec`
+impl<I: Iterator + Clone, M: ListMode<I::Item>, N: ?Sized> std::ops::Contains<N> for LazyList<I, M> where M::Elem: PartialEq<N> {
+	fn contains(&self, needle: &N) -> bool { self.items().iter().any(|x| M::elem(x) == needle) }
+}
+
+impl<I: Iterator + Clone, M: ListMode<I::Item>, R> PartialEq<Vec<R>> for LazyList<I, M> where M::Elem: PartialEq<R> {
+	fn eq(&self, other: &Vec<R>) -> bool { self.eq_items(other) }
+}
+
+impl<I: Iterator + Clone, M: ListMode<I::Item>, R, const N: usize> PartialEq<[R; N]> for LazyList<I, M> where M::Elem: PartialEq<R> {
+	fn eq(&self, other: &[R; N]) -> bool { self.eq_items(other) }
+}
+
+impl<I: Iterator + Clone, M: ListMode<I::Item>, R> PartialEq<[R]> for LazyList<I, M> where M::Elem: PartialEq<R> {
+	fn eq(&self, other: &[R]) -> bool { self.eq_items(other) }
+}
+
+impl<I, M, J, N> PartialEq<LazyList<J, N>> for LazyList<I, M>
+where
+	I: Iterator + Clone,
+	M: ListMode<I::Item>,
+	J: Iterator + Clone,
+	N: ListMode<J::Item>,
+	M::Elem: PartialEq<N::Elem>,
+{
+	fn eq(&self, other: &LazyList<J, N>) -> bool {
+		let (a, b) = (self.items(), other.items());
+		a.len() == b.len() && a.iter().zip(b).all(|(x, y)| M::elem(x) == N::elem(y))
+	}
+}
+
+impl<I: Iterator + Clone, M: ListMode<I::Item>, R> PartialEq<LazyList<I, M>> for Vec<R> where R: PartialEq<M::Elem> {
+	fn eq(&self, other: &LazyList<I, M>) -> bool {
+		let items = other.items();
+		self.len() == items.len() && self.iter().zip(items).all(|(a, b)| a == M::elem(b))
+	}
+}
+
+// Pipeline steps
+
+#[derive(Clone)]
+pub struct LazyMap<I, F, M> {
+	iter: I,
+	f: F,
+	mode: std::marker::PhantomData<M>,
+}
+
+impl<I: Iterator, U, F: Fn(M::Elem) -> U, M: ListOwn<I::Item>> Iterator for LazyMap<I, F, M> {
+	type Item = U;
+	fn next(&mut self) -> Option<U> {
+		let x = self.iter.next()?;
+		Some((self.f)(M::own(x)))
+	}
+	fn size_hint(&self) -> (usize, Option<usize>) { self.iter.size_hint() }
+}
+
+impl<I: DoubleEndedIterator, U, F: Fn(M::Elem) -> U, M: ListOwn<I::Item>> DoubleEndedIterator for LazyMap<I, F, M> {
+	fn next_back(&mut self) -> Option<U> {
+		let x = self.iter.next_back()?;
+		Some((self.f)(M::own(x)))
+	}
+}
+
+#[derive(Clone)]
+pub struct LazyMapRef<I, F, M> {
+	iter: I,
+	f: F,
+	mode: std::marker::PhantomData<M>,
+}
+
+impl<I: Iterator, U, F: Fn(&M::Elem) -> U, M: ListMode<I::Item>> Iterator for LazyMapRef<I, F, M> {
+	type Item = U;
+	fn next(&mut self) -> Option<U> {
+		let x = self.iter.next()?;
+		Some((self.f)(M::elem(&x)))
+	}
+	fn size_hint(&self) -> (usize, Option<usize>) { self.iter.size_hint() }
+}
+
+impl<I: DoubleEndedIterator, U, F: Fn(&M::Elem) -> U, M: ListMode<I::Item>> DoubleEndedIterator for LazyMapRef<I, F, M> {
+	fn next_back(&mut self) -> Option<U> {
+		let x = self.iter.next_back()?;
+		Some((self.f)(M::elem(&x)))
+	}
+}
+
+// Keeps the items the predicate accepts, or with `keep: false` the ones it rejects
+#[derive(Clone)]
+pub struct LazySelect<I, F, M> {
+	iter: I,
+	f: F,
+	keep: bool,
+	mode: std::marker::PhantomData<M>,
+}
+
+impl<I: Iterator, F: Fn(&M::Elem) -> bool, M: ListMode<I::Item>> Iterator for LazySelect<I, F, M> {
+	type Item = I::Item;
+	fn next(&mut self) -> Option<I::Item> {
+		let (f, keep) = (&self.f, self.keep);
+		self.iter.find(|x| f(M::elem(x)) == keep)
+	}
+	fn size_hint(&self) -> (usize, Option<usize>) { (0, self.iter.size_hint().1) }
+}
+
+impl<I: DoubleEndedIterator, F: Fn(&M::Elem) -> bool, M: ListMode<I::Item>> DoubleEndedIterator for LazySelect<I, F, M> {
+	fn next_back(&mut self) -> Option<I::Item> {
+		let (f, keep) = (&self.f, self.keep);
+		self.iter.rfind(|x| f(M::elem(x)) == keep)
+	}
+}
+
+// Numbers the items and hands out (index, element) pairs with owned elements
+#[derive(Clone)]
+pub struct LazyPairs<I, M> {
+	iter: std::iter::Enumerate<I>,
+	mode: std::marker::PhantomData<M>,
+}
+
+impl<I: Iterator, M: ListOwn<I::Item>> Iterator for LazyPairs<I, M> {
+	type Item = (usize, M::Elem);
+	fn next(&mut self) -> Option<(usize, M::Elem)> {
+		let (i, x) = self.iter.next()?;
+		Some((i, M::own(x)))
+	}
+	fn size_hint(&self) -> (usize, Option<usize>) { self.iter.size_hint() }
+}
+
+pub struct LazyWithout<'a, I, E, M> {
+	iter: I,
+	item: &'a E,
+	mode: std::marker::PhantomData<M>,
+}
+
+impl<I: Clone, E, M> Clone for LazyWithout<'_, I, E, M> {
+	fn clone(&self) -> Self {
+		LazyWithout { iter: self.iter.clone(), item: self.item, mode: std::marker::PhantomData }
+	}
+}
+
+impl<I: Iterator, E: PartialEq, M: ListMode<I::Item, Elem = E>> Iterator for LazyWithout<'_, I, E, M> {
+	type Item = I::Item;
+	fn next(&mut self) -> Option<I::Item> {
+		let item = self.item;
+		self.iter.find(|x| M::elem(x) != item)
+	}
+	fn size_hint(&self) -> (usize, Option<usize>) { (0, self.iter.size_hint().1) }
+}
+
+impl<I: DoubleEndedIterator, E: PartialEq, M: ListMode<I::Item, Elem = E>> DoubleEndedIterator for LazyWithout<'_, I, E, M> {
+	fn next_back(&mut self) -> Option<I::Item> {
+		let item = self.item;
+		self.iter.rfind(|x| M::elem(x) != item)
 	}
 }
 
@@ -0,0 +1,107 @@
+// Dot access on string-keyed maps for script mode.
+//
+// When field lookup fails in script mode, typeck falls back to the `MapField`
+// trait: `scores.math` reads through `*scores.__map_field("math")`, and
+// `scores.math = 1` writes through `__map_field_mut`. Typeck finds the trait
+// through its `script_map_field` lang item, whatever the script names its own
+// items.
+
+#[cfg(feature = "standalone_extension")]
+use crate::val::Val;
+
+#[allow(dead_code)]
+#[cfg_attr(not(feature = "standalone_extension"), lang = "script_map_field")]
+pub trait MapField {
+	type Value;
+	fn __map_field(&self, key: &str) -> &Self::Value;
+	// Assignment only updates existing keys; use `insert` to add new ones.
+	fn __map_field_mut(&mut self, key: &str) -> &mut Self::Value;
+}
+
+// Panic naming the key and what is there, instead of std's bare "key not found".
+#[allow(dead_code)]
+fn __missing_map_field<'a>(key: &str, keys: impl Iterator<Item = &'a str>) -> ! {
+	let mut keys: Vec<&str> = keys.collect();
+	keys.sort();
+	panic!("map has no key `{}` (available keys: {})", key, keys.join(", "))
+}
+
+impl<V, S: std::hash::BuildHasher> MapField for std::collections::HashMap<String, V, S> {
+	type Value = V;
+	fn __map_field(&self, key: &str) -> &V {
+		match self.get(key) {
+			Some(value) => value,
+			None => __missing_map_field(key, self.keys().map(|k| k.as_str())),
+		}
+	}
+	fn __map_field_mut(&mut self, key: &str) -> &mut V {
+		if !self.contains_key(key) {
+			__missing_map_field(key, self.keys().map(|k| k.as_str()));
+		}
+		self.get_mut(key).unwrap()
+	}
+}
+
+impl<V, S: std::hash::BuildHasher> MapField for std::collections::HashMap<&str, V, S> {
+	type Value = V;
+	fn __map_field(&self, key: &str) -> &V {
+		match self.get(key) {
+			Some(value) => value,
+			None => __missing_map_field(key, self.keys().copied()),
+		}
+	}
+	fn __map_field_mut(&mut self, key: &str) -> &mut V {
+		if !self.contains_key(key) {
+			__missing_map_field(key, self.keys().copied());
+		}
+		self.get_mut(key).unwrap()
+	}
+}
+
+impl<V> MapField for std::collections::BTreeMap<String, V> {
+	type Value = V;
+	fn __map_field(&self, key: &str) -> &V {
+		match self.get(key) {
+			Some(value) => value,
+			None => __missing_map_field(key, self.keys().map(|k| k.as_str())),
+		}
+	}
+	fn __map_field_mut(&mut self, key: &str) -> &mut V {
+		if !self.contains_key(key) {
+			__missing_map_field(key, self.keys().map(|k| k.as_str()));
+		}
+		self.get_mut(key).unwrap()
+	}
+}
+
+impl<V> MapField for std::collections::BTreeMap<&str, V> {
+	type Value = V;
+	fn __map_field(&self, key: &str) -> &V {
+		match self.get(key) {
+			Some(value) => value,
+			None => __missing_map_field(key, self.keys().copied()),
+		}
+	}
+	fn __map_field_mut(&mut self, key: &str) -> &mut V {
+		if !self.contains_key(key) {
+			__missing_map_field(key, self.keys().copied());
+		}
+		self.get_mut(key).unwrap()
+	}
+}
+
+// `cfg.db.host` on a `Val::Map`, same as `cfg["db"]["host"]`.
+impl MapField for Val {
+	type Value = Val;
+	fn __map_field(&self, key: &str) -> &Val {
+		&self[key]
+	}
+	fn __map_field_mut(&mut self, key: &str) -> &mut Val {
+		if let Val::Map(m) = self {
+			if !m.contains_key(key) {
+				__missing_map_field(key, m.keys().map(|k| k.as_str()));
+			}
+		}
+		&mut self[key]
+	}
+}
@@ -4,6 +4,8 @@
 // ⚠️ 🚨 DO NOT USE ANY CRATES when compiling rustc where this is a module, not a crate  ⚠️ 🚨
 #[cfg(feature = "standalone_extension")]
 use crate::val::Val;
+#[cfg(feature = "standalone_extension")]
+use crate::lists::{LazyList, ListMode};
 
 #[allow(dead_code)]
 pub trait Truthy {
@@ -39,6 +41,17 @@ impl Truthy for String { fn is_truthy(&self) -> bool { !self.is_empty() } }
 // Collections
 impl<T> Truthy for Vec<T> { fn is_truthy(&self) -> bool { !self.is_empty() } }
 impl<T> Truthy for Option<T> { fn is_truthy(&self) -> bool { self.is_some() } }
+impl<I: Iterator + Clone, M: ListMode<I::Item>> Truthy for LazyList<I, M> { fn is_truthy(&self) -> bool { !self.is_empty() } }
+
+// `x == nil` and `x != nil` in script mode: an `Option` is nil when it's `None`, and the
+// result of a function returning `error` is nil when it succeeded.
+#[allow(dead_code)]
+pub trait IsNil {
+	fn is_nil(&self) -> bool;
+}
+
+impl<T> IsNil for Option<T> { fn is_nil(&self) -> bool { self.is_none() } }
+impl<E> IsNil for Result<(), E> { fn is_nil(&self) -> bool { self.is_ok() } }
 
 
 // Truthy implementation
@@ -50,6 +63,7 @@ fn is_truthy(&self) -> bool {
 			Val::Float(n) => *n != 0.0,
 			Val::Bool(b) => *b,
 			Val::List(v) => !v.is_empty(),
+			Val::Map(m) => !m.is_empty(),
 			Val::Nil => false,
 		}
 	}
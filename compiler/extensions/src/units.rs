@@ -0,0 +1,234 @@
+// Physical units for script mode.
+//
+// A Quantity<D> stores its value in SI base units, tagged with a dimension
+// marker D. Quantities of the same dimension add, subtract and compare;
+// mixing dimensions (`1m + 1s`) has no Add impl and fails to compile.
+// Multiplication and division go through MulDim/DivDim, which name the
+// resulting dimension: Length * Length = Area, Length / Time = Velocity.
+//
+// The script harness injects this file as `mod units` in its helpers, followed by one
+// constant per unit (`pub const KiloMeter: Quantity<Length> = Quantity::new(1e3);`)
+// generated from the suffix table in rustc_parse/src/transformer/units.rs. In script mode
+// the parser lowers unit-suffixed literals onto those constants:
+// `500ms` -> `500f64 * crate::__script_helpers::units::MilliSecond`,
+// `5m/s` -> `5f64 * Meter / Second`, `10m²` -> `10f64 * Meter * Meter`.
+
+pub trait Dimension {
+	// Symbol of the SI unit the value is stored in.
+	const SYMBOL: &'static str;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ratio;
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Length;
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Area;
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Volume;
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Time;
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Mass;
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Velocity;
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Acceleration;
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Force;
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Energy;
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Power;
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Pressure;
+
+impl Dimension for Ratio { const SYMBOL: &'static str = ""; }
+impl Dimension for Length { const SYMBOL: &'static str = "m"; }
+impl Dimension for Area { const SYMBOL: &'static str = "m²"; }
+impl Dimension for Volume { const SYMBOL: &'static str = "m³"; }
+impl Dimension for Time { const SYMBOL: &'static str = "s"; }
+impl Dimension for Mass { const SYMBOL: &'static str = "kg"; }
+impl Dimension for Velocity { const SYMBOL: &'static str = "m/s"; }
+impl Dimension for Acceleration { const SYMBOL: &'static str = "m/s²"; }
+impl Dimension for Force { const SYMBOL: &'static str = "N"; }
+impl Dimension for Energy { const SYMBOL: &'static str = "J"; }
+impl Dimension for Power { const SYMBOL: &'static str = "W"; }
+impl Dimension for Pressure { const SYMBOL: &'static str = "Pa"; }
+
+// Dimension of `Self * Rhs`.
+pub trait MulDim<Rhs> {
+	type Output: Dimension;
+}
+
+// Dimension of `Self / Rhs`.
+pub trait DivDim<Rhs> {
+	type Output: Dimension;
+}
+
+impl MulDim<Length> for Length { type Output = Area; }
+impl MulDim<Area> for Length { type Output = Volume; }
+impl MulDim<Length> for Area { type Output = Volume; }
+impl MulDim<Time> for Velocity { type Output = Length; }
+impl MulDim<Velocity> for Time { type Output = Length; }
+impl MulDim<Time> for Acceleration { type Output = Velocity; }
+impl MulDim<Acceleration> for Time { type Output = Velocity; }
+impl MulDim<Acceleration> for Mass { type Output = Force; }
+impl MulDim<Mass> for Acceleration { type Output = Force; }
+impl MulDim<Length> for Force { type Output = Energy; }
+impl MulDim<Force> for Length { type Output = Energy; }
+impl MulDim<Velocity> for Force { type Output = Power; }
+impl MulDim<Time> for Power { type Output = Energy; }
+impl MulDim<Power> for Time { type Output = Energy; }
+impl MulDim<Area> for Pressure { type Output = Force; }
+impl MulDim<Pressure> for Area { type Output = Force; }
+
+// Same dimension divides to a plain ratio: `1km / 1m` is 1000.
+impl<D: Dimension> DivDim<D> for D { type Output = Ratio; }
+impl DivDim<Length> for Area { type Output = Length; }
+impl DivDim<Length> for Volume { type Output = Area; }
+impl DivDim<Area> for Volume { type Output = Length; }
+impl DivDim<Time> for Length { type Output = Velocity; }
+impl DivDim<Velocity> for Length { type Output = Time; }
+impl DivDim<Time> for Velocity { type Output = Acceleration; }
+impl DivDim<Acceleration> for Velocity { type Output = Time; }
+impl DivDim<Mass> for Force { type Output = Acceleration; }
+impl DivDim<Acceleration> for Force { type Output = Mass; }
+impl DivDim<Area> for Force { type Output = Pressure; }
+impl DivDim<Length> for Energy { type Output = Force; }
+impl DivDim<Force> for Energy { type Output = Length; }
+impl DivDim<Time> for Energy { type Output = Power; }
+impl DivDim<Power> for Energy { type Output = Time; }
+impl DivDim<Velocity> for Power { type Output = Force; }
+
+#[derive(Clone, Copy)]
+pub struct Quantity<D> {
+	si: f64,
+	dim: std::marker::PhantomData<D>,
+}
+
+impl<D: Dimension> Quantity<D> {
+	pub const fn new(si: f64) -> Self {
+		Quantity { si, dim: std::marker::PhantomData }
+	}
+
+	// Value in SI base units (metres, seconds, kilograms, ...).
+	pub fn value(self) -> f64 {
+		self.si
+	}
+
+	// Value expressed in another unit of the same dimension: `(90min).in_unit(Hour)` is 1.5.
+	pub fn in_unit(self, unit: Quantity<D>) -> f64 {
+		self.si / unit.si
+	}
+
+	pub fn symbol(self) -> &'static str {
+		D::SYMBOL
+	}
+}
+
+impl<D: Dimension> std::fmt::Display for Quantity<D> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		if D::SYMBOL.is_empty() {
+			write!(f, "{}", self.si)
+		} else {
+			write!(f, "{} {}", self.si, D::SYMBOL)
+		}
+	}
+}
+
+// Debug matches Display so `put!(5s)` prints `5 s`.
+impl<D: Dimension> std::fmt::Debug for Quantity<D> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		std::fmt::Display::fmt(self, f)
+	}
+}
+
+// Conversion factors are rarely exact in binary, so `1ft == 12inch` compares
+// with a relative tolerance.
+impl<D: Dimension> PartialEq for Quantity<D> {
+	fn eq(&self, other: &Self) -> bool {
+		let scale = self.si.abs().max(other.si.abs());
+		(self.si - other.si).abs() <= scale * 1e-9
+	}
+}
+
+impl<D: Dimension> PartialOrd for Quantity<D> {
+	fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+		if self == other { Some(std::cmp::Ordering::Equal) } else { self.si.partial_cmp(&other.si) }
+	}
+}
+
+impl<D: Dimension> std::ops::Add for Quantity<D> {
+	type Output = Quantity<D>;
+	fn add(self, rhs: Self) -> Self::Output { Quantity::new(self.si + rhs.si) }
+}
+
+impl<D: Dimension> std::ops::Sub for Quantity<D> {
+	type Output = Quantity<D>;
+	fn sub(self, rhs: Self) -> Self::Output { Quantity::new(self.si - rhs.si) }
+}
+
+impl<D: Dimension> std::ops::Neg for Quantity<D> {
+	type Output = Quantity<D>;
+	fn neg(self) -> Self::Output { Quantity::new(-self.si) }
+}
+
+impl<D: Dimension> std::ops::AddAssign for Quantity<D> {
+	fn add_assign(&mut self, rhs: Self) { self.si += rhs.si }
+}
+
+impl<D: Dimension> std::ops::SubAssign for Quantity<D> {
+	fn sub_assign(&mut self, rhs: Self) { self.si -= rhs.si }
+}
+
+impl<A: MulDim<B>, B> std::ops::Mul<Quantity<B>> for Quantity<A> {
+	type Output = Quantity<A::Output>;
+	fn mul(self, rhs: Quantity<B>) -> Self::Output { Quantity::new(self.si * rhs.si) }
+}
+
+impl<A: DivDim<B>, B> std::ops::Div<Quantity<B>> for Quantity<A> {
+	type Output = Quantity<A::Output>;
+	fn div(self, rhs: Quantity<B>) -> Self::Output { Quantity::new(self.si / rhs.si) }
+}
+
+// Scaling by plain numbers: `3 * KiloMeter`, `KiloMeter * 2.5`, `10m / 4`.
+impl<D: Dimension> std::ops::Mul<f64> for Quantity<D> {
+	type Output = Quantity<D>;
+	fn mul(self, rhs: f64) -> Self::Output { Quantity::new(self.si * rhs) }
+}
+
+impl<D: Dimension> std::ops::Mul<i32> for Quantity<D> {
+	type Output = Quantity<D>;
+	fn mul(self, rhs: i32) -> Self::Output { Quantity::new(self.si * rhs as f64) }
+}
+
+impl<D: Dimension> std::ops::Div<f64> for Quantity<D> {
+	type Output = Quantity<D>;
+	fn div(self, rhs: f64) -> Self::Output { Quantity::new(self.si / rhs) }
+}
+
+impl<D: Dimension> std::ops::Div<i32> for Quantity<D> {
+	type Output = Quantity<D>;
+	fn div(self, rhs: i32) -> Self::Output { Quantity::new(self.si / rhs as f64) }
+}
+
+impl<D: Dimension> std::ops::Mul<Quantity<D>> for f64 {
+	type Output = Quantity<D>;
+	fn mul(self, rhs: Quantity<D>) -> Self::Output { Quantity::new(self * rhs.si) }
+}
+
+impl<D: Dimension> std::ops::Mul<Quantity<D>> for i32 {
+	type Output = Quantity<D>;
+	fn mul(self, rhs: Quantity<D>) -> Self::Output { Quantity::new(self as f64 * rhs.si) }
+}
+
+impl PartialEq<f64> for Quantity<Ratio> {
+	fn eq(&self, other: &f64) -> bool {
+		*self == Quantity::<Ratio>::new(*other)
+	}
+}
+
+impl From<Quantity<Ratio>> for f64 {
+	fn from(q: Quantity<Ratio>) -> f64 { q.si }
+}
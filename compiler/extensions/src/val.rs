@@ -2,8 +2,14 @@
 //
 // Enables heterogeneous collections and dynamic value handling.
 // Note: Truthy trait must be defined before this file (loaded from truthy.rs).
+//
+// Numeric promotion: Int op Int stays Int, anything involving a Float is a Float.
+// `/` on two Ints stays an Int only when it divides evenly: 6 / 3 == 2, 7 / 2 == 3.5.
+// Int results that don't fit in i64 (overflow, i64::MIN / -1) and Int division or
+// remainder by zero are computed as Floats instead: 1 / 0 == inf, 1 % 0 is NaN.
+// Equality and ordering compare Int and Float by value, so Val::Int(1) == Val::Float(1.0).
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug)]
 #[allow(dead_code)]
 pub enum Val {
 	Str(String),
@@ -11,6 +17,8 @@ pub enum Val {
 	Float(f64),
 	Bool(bool),
 	List(Vec<Val>),
+	// Sorted keys keep Display, Hash and JSON output deterministic.
+	Map(std::collections::BTreeMap<String, Val>),
 	Nil,
 }
 
@@ -22,11 +30,97 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 			Val::Float(n) => write!(f, "{}", n),
 			Val::Bool(b) => write!(f, "{}", b),
 			Val::List(v) => write!(f, "{:?}", v),
+			Val::Map(m) => write!(f, "{:?}", m),
 			Val::Nil => write!(f, "nil"),
 		}
 	}
 }
 
+#[allow(dead_code)]
+impl Val {
+	pub fn type_name(&self) -> &'static str {
+		match self {
+			Val::Str(_) => "str",
+			Val::Int(_) => "int",
+			Val::Float(_) => "float",
+			Val::Bool(_) => "bool",
+			Val::List(_) => "list",
+			Val::Map(_) => "map",
+			Val::Nil => "nil",
+		}
+	}
+
+	pub fn is_nil(&self) -> bool {
+		matches!(self, Val::Nil)
+	}
+
+	// Number of chars, items or entries; 0 for scalars.
+	pub fn len(&self) -> usize {
+		match self {
+			Val::Str(s) => s.chars().count(),
+			Val::List(v) => v.len(),
+			Val::Map(m) => m.len(),
+			_ => 0,
+		}
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	pub fn as_f64(&self) -> Option<f64> {
+		match self {
+			Val::Int(n) => Some(*n as f64),
+			Val::Float(n) => Some(*n),
+			_ => None,
+		}
+	}
+
+	// Non-panicking lookups: `val["k"]` and `val[0]` panic when missing.
+	pub fn get(&self, key: &str) -> Option<&Val> {
+		match self {
+			Val::Map(m) => m.get(key),
+			_ => None,
+		}
+	}
+
+	pub fn at(&self, index: usize) -> Option<&Val> {
+		match self {
+			Val::List(v) => v.get(index),
+			_ => None,
+		}
+	}
+
+	pub fn keys(&self) -> Vec<Val> {
+		match self {
+			Val::Map(m) => m.keys().map(|k| Val::Str(k.clone())).collect(),
+			_ => Vec::new(),
+		}
+	}
+
+	pub fn values(&self) -> Vec<Val> {
+		match self {
+			Val::Map(m) => m.values().cloned().collect(),
+			Val::List(v) => v.clone(),
+			_ => Vec::new(),
+		}
+	}
+
+	pub fn push(&mut self, item: impl Into<Val>) {
+		match self {
+			Val::List(v) => v.push(item.into()),
+			other => panic!("cannot push onto {}", other.type_name()),
+		}
+	}
+
+	pub fn insert(&mut self, key: impl Into<String>, value: impl Into<Val>) -> Option<Val> {
+		match self {
+			Val::Map(m) => m.insert(key.into(), value.into()),
+			other => panic!("cannot insert a key into {}", other.type_name()),
+		}
+	}
+}
+
 // From implementations for various types
 impl From<&str> for Val {
 	fn from(s: &str) -> Self { Val::Str(s.to_string()) }
@@ -34,12 +128,21 @@ fn from(s: &str) -> Self { Val::Str(s.to_string()) }
 impl From<String> for Val {
 	fn from(s: String) -> Self { Val::Str(s) }
 }
+impl From<&String> for Val {
+	fn from(s: &String) -> Self { Val::Str(s.clone()) }
+}
 impl From<i64> for Val {
 	fn from(n: i64) -> Self { Val::Int(n) }
 }
 impl From<i32> for Val {
 	fn from(n: i32) -> Self { Val::Int(n as i64) }
 }
+impl From<u32> for Val {
+	fn from(n: u32) -> Self { Val::Int(n as i64) }
+}
+impl From<usize> for Val {
+	fn from(n: usize) -> Self { Val::Int(n as i64) }
+}
 impl From<f64> for Val {
 	fn from(n: f64) -> Self { Val::Float(n) }
 }
@@ -52,6 +155,156 @@ fn from(b: bool) -> Self { Val::Bool(b) }
 impl From<char> for Val {
 	fn from(c: char) -> Self { Val::Str(c.to_string()) }
 }
+impl<T: Into<Val>> From<Vec<T>> for Val {
+	fn from(v: Vec<T>) -> Self { Val::List(v.into_iter().map(Into::into).collect()) }
+}
+impl<T: Into<Val>> From<Option<T>> for Val {
+	fn from(o: Option<T>) -> Self { o.map_or(Val::Nil, Into::into) }
+}
+impl<K: Into<String>, V: Into<Val>> From<std::collections::BTreeMap<K, V>> for Val {
+	fn from(m: std::collections::BTreeMap<K, V>) -> Self {
+		Val::Map(m.into_iter().map(|(k, v)| (k.into(), v.into())).collect())
+	}
+}
+impl<K: Into<String>, V: Into<Val>, S> From<std::collections::HashMap<K, V, S>> for Val {
+	fn from(m: std::collections::HashMap<K, V, S>) -> Self {
+		Val::Map(m.into_iter().map(|(k, v)| (k.into(), v.into())).collect())
+	}
+}
+
+// TryFrom back to concrete types, so `let n: i64 = val.try_into()?` works.
+#[derive(Debug, Clone, PartialEq)]
+#[allow(dead_code)]
+pub struct ValTypeError {
+	pub expected: &'static str,
+	pub found: &'static str,
+}
+
+impl std::fmt::Display for ValTypeError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "expected {}, found {}", self.expected, self.found)
+	}
+}
+
+impl std::error::Error for ValTypeError {}
+
+impl TryFrom<Val> for i64 {
+	type Error = ValTypeError;
+	fn try_from(v: Val) -> Result<Self, Self::Error> {
+		match v {
+			Val::Int(n) => Ok(n),
+			other => Err(ValTypeError { expected: "int", found: other.type_name() }),
+		}
+	}
+}
+impl TryFrom<Val> for i32 {
+	type Error = ValTypeError;
+	fn try_from(v: Val) -> Result<Self, Self::Error> {
+		match v {
+			Val::Int(n) if i32::try_from(n).is_ok() => Ok(n as i32),
+			other => Err(ValTypeError { expected: "i32", found: other.type_name() }),
+		}
+	}
+}
+// Ints widen to float, as in arithmetic.
+impl TryFrom<Val> for f64 {
+	type Error = ValTypeError;
+	fn try_from(v: Val) -> Result<Self, Self::Error> {
+		v.as_f64().ok_or(ValTypeError { expected: "float", found: v.type_name() })
+	}
+}
+impl TryFrom<Val> for bool {
+	type Error = ValTypeError;
+	fn try_from(v: Val) -> Result<Self, Self::Error> {
+		match v {
+			Val::Bool(b) => Ok(b),
+			other => Err(ValTypeError { expected: "bool", found: other.type_name() }),
+		}
+	}
+}
+impl TryFrom<Val> for String {
+	type Error = ValTypeError;
+	fn try_from(v: Val) -> Result<Self, Self::Error> {
+		match v {
+			Val::Str(s) => Ok(s),
+			other => Err(ValTypeError { expected: "str", found: other.type_name() }),
+		}
+	}
+}
+impl TryFrom<Val> for Vec<Val> {
+	type Error = ValTypeError;
+	fn try_from(v: Val) -> Result<Self, Self::Error> {
+		match v {
+			Val::List(items) => Ok(items),
+			other => Err(ValTypeError { expected: "list", found: other.type_name() }),
+		}
+	}
+}
+impl TryFrom<Val> for std::collections::BTreeMap<String, Val> {
+	type Error = ValTypeError;
+	fn try_from(v: Val) -> Result<Self, Self::Error> {
+		match v {
+			Val::Map(m) => Ok(m),
+			other => Err(ValTypeError { expected: "map", found: other.type_name() }),
+		}
+	}
+}
+
+// Equality: numbers compare by value across Int and Float.
+impl PartialEq for Val {
+	fn eq(&self, other: &Val) -> bool {
+		match (self, other) {
+			(Val::Int(a), Val::Int(b)) => a == b,
+			(Val::Str(a), Val::Str(b)) => a == b,
+			(Val::Bool(a), Val::Bool(b)) => a == b,
+			(Val::List(a), Val::List(b)) => a == b,
+			(Val::Map(a), Val::Map(b)) => a == b,
+			(Val::Nil, Val::Nil) => true,
+			(a, b) => match (a.as_f64(), b.as_f64()) {
+				(Some(a), Some(b)) => a == b,
+				_ => false,
+			},
+		}
+	}
+}
+
+// Numbers hash by the f64 they compare as, so Int(2^53 + 1) and Float(2^53), which
+// compare equal, hash alike too.
+impl std::hash::Hash for Val {
+	fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+		match self {
+			Val::Str(s) => s.hash(state),
+			Val::Int(n) => __val_hash_f64(*n as f64, state),
+			Val::Float(n) => __val_hash_f64(*n, state),
+			Val::Bool(b) => b.hash(state),
+			Val::List(v) => v.hash(state),
+			Val::Map(m) => m.hash(state),
+			Val::Nil => 0u8.hash(state),
+		}
+	}
+}
+
+#[allow(dead_code)]
+fn __val_hash_f64<H: std::hash::Hasher>(n: f64, state: &mut H) {
+	// -0.0 == 0.0
+	let n = if n == 0.0 { 0.0 } else { n };
+	std::hash::Hash::hash(&n.to_bits(), state)
+}
+
+// Values of different kinds (other than Int vs Float) are unordered.
+impl PartialOrd for Val {
+	fn partial_cmp(&self, other: &Val) -> Option<std::cmp::Ordering> {
+		match (self, other) {
+			(Val::Int(a), Val::Int(b)) => a.partial_cmp(b),
+			(Val::Str(a), Val::Str(b)) => a.partial_cmp(b),
+			(Val::Bool(a), Val::Bool(b)) => a.partial_cmp(b),
+			(Val::List(a), Val::List(b)) => a.partial_cmp(b),
+			(Val::Map(a), Val::Map(b)) => a.partial_cmp(b),
+			(Val::Nil, Val::Nil) => Some(std::cmp::Ordering::Equal),
+			(a, b) => a.as_f64()?.partial_cmp(&b.as_f64()?),
+		}
+	}
+}
 
 // PartialEq with char for comparison
 impl PartialEq<char> for Val {
@@ -63,3 +316,579 @@ fn eq(&self, other: &char) -> bool {
 	}
 }
 
+// Comparisons with plain values: `val == 3`, `val == "a"`, `val < 2.5`
+impl PartialEq<i64> for Val {
+	fn eq(&self, other: &i64) -> bool { *self == Val::Int(*other) }
+}
+impl PartialEq<i32> for Val {
+	fn eq(&self, other: &i32) -> bool { *self == Val::Int(*other as i64) }
+}
+impl PartialEq<f64> for Val {
+	fn eq(&self, other: &f64) -> bool { *self == Val::Float(*other) }
+}
+impl PartialEq<bool> for Val {
+	fn eq(&self, other: &bool) -> bool { matches!(self, Val::Bool(b) if b == other) }
+}
+impl PartialEq<&str> for Val {
+	fn eq(&self, other: &&str) -> bool { matches!(self, Val::Str(s) if s == other) }
+}
+impl PartialEq<String> for Val {
+	fn eq(&self, other: &String) -> bool { matches!(self, Val::Str(s) if s == other) }
+}
+impl PartialEq<Val> for i64 {
+	fn eq(&self, other: &Val) -> bool { other == self }
+}
+impl PartialEq<Val> for i32 {
+	fn eq(&self, other: &Val) -> bool { other == self }
+}
+impl PartialEq<Val> for f64 {
+	fn eq(&self, other: &Val) -> bool { other == self }
+}
+impl PartialEq<Val> for &str {
+	fn eq(&self, other: &Val) -> bool { other == self }
+}
+impl PartialOrd<i64> for Val {
+	fn partial_cmp(&self, other: &i64) -> Option<std::cmp::Ordering> { self.partial_cmp(&Val::Int(*other)) }
+}
+impl PartialOrd<i32> for Val {
+	fn partial_cmp(&self, other: &i32) -> Option<std::cmp::Ordering> { self.partial_cmp(&Val::Int(*other as i64)) }
+}
+impl PartialOrd<f64> for Val {
+	fn partial_cmp(&self, other: &f64) -> Option<std::cmp::Ordering> { self.partial_cmp(&Val::Float(*other)) }
+}
+
+// Arithmetic. `+` also concatenates strings (with anything), lists and maps (right wins);
+// `*` repeats a string or list.
+#[allow(dead_code)]
+fn __val_arith(op: char, lhs: Val, rhs: Val) -> Val {
+	match (lhs, rhs) {
+		(Val::Int(a), Val::Int(b)) => {
+			let exact = match op {
+				'+' => a.checked_add(b),
+				'-' => a.checked_sub(b),
+				'*' => a.checked_mul(b),
+				'/' if a.checked_rem(b) == Some(0) => a.checked_div(b),
+				'/' => None,
+				// Only i64::MIN % -1 overflows, and it is 0.
+				_ if b == -1 => Some(0),
+				_ => a.checked_rem(b),
+			};
+			exact.map_or_else(|| __val_float_arith(op, a as f64, b as f64), Val::Int)
+		}
+		(Val::Int(a), Val::Float(b)) => __val_float_arith(op, a as f64, b),
+		(Val::Float(a), Val::Int(b)) => __val_float_arith(op, a, b as f64),
+		(Val::Float(a), Val::Float(b)) => __val_float_arith(op, a, b),
+		(Val::Str(a), b) if op == '+' => Val::Str(a + &b.to_string()),
+		(a, Val::Str(b)) if op == '+' => Val::Str(a.to_string() + &b),
+		(Val::Str(s), Val::Int(n)) if op == '*' => Val::Str(s.repeat(n.max(0) as usize)),
+		(Val::List(mut a), Val::List(b)) if op == '+' => {
+			a.extend(b);
+			Val::List(a)
+		}
+		(Val::List(v), Val::Int(n)) if op == '*' => {
+			Val::List((0..n.max(0)).flat_map(|_| v.iter().cloned()).collect())
+		}
+		(Val::Map(mut a), Val::Map(b)) if op == '+' => {
+			a.extend(b);
+			Val::Map(a)
+		}
+		(a, b) => panic!("unsupported operand types for {}: {} and {}", op, a.type_name(), b.type_name()),
+	}
+}
+
+#[allow(dead_code)]
+fn __val_float_arith(op: char, a: f64, b: f64) -> Val {
+	Val::Float(match op {
+		'+' => a + b,
+		'-' => a - b,
+		'*' => a * b,
+		'/' => a / b,
+		_ => a % b,
+	})
+}
+
+impl<T: Into<Val>> std::ops::Add<T> for Val {
+	type Output = Val;
+	fn add(self, rhs: T) -> Val { __val_arith('+', self, rhs.into()) }
+}
+impl<T: Into<Val>> std::ops::Sub<T> for Val {
+	type Output = Val;
+	fn sub(self, rhs: T) -> Val { __val_arith('-', self, rhs.into()) }
+}
+impl<T: Into<Val>> std::ops::Mul<T> for Val {
+	type Output = Val;
+	fn mul(self, rhs: T) -> Val { __val_arith('*', self, rhs.into()) }
+}
+impl<T: Into<Val>> std::ops::Div<T> for Val {
+	type Output = Val;
+	fn div(self, rhs: T) -> Val { __val_arith('/', self, rhs.into()) }
+}
+impl<T: Into<Val>> std::ops::Rem<T> for Val {
+	type Output = Val;
+	fn rem(self, rhs: T) -> Val { __val_arith('%', self, rhs.into()) }
+}
+impl<T: Into<Val>> std::ops::Add<T> for &Val {
+	type Output = Val;
+	fn add(self, rhs: T) -> Val { __val_arith('+', self.clone(), rhs.into()) }
+}
+impl<T: Into<Val>> std::ops::Sub<T> for &Val {
+	type Output = Val;
+	fn sub(self, rhs: T) -> Val { __val_arith('-', self.clone(), rhs.into()) }
+}
+impl<T: Into<Val>> std::ops::Mul<T> for &Val {
+	type Output = Val;
+	fn mul(self, rhs: T) -> Val { __val_arith('*', self.clone(), rhs.into()) }
+}
+impl<T: Into<Val>> std::ops::Div<T> for &Val {
+	type Output = Val;
+	fn div(self, rhs: T) -> Val { __val_arith('/', self.clone(), rhs.into()) }
+}
+impl<T: Into<Val>> std::ops::AddAssign<T> for Val {
+	fn add_assign(&mut self, rhs: T) {
+		let lhs = std::mem::replace(self, Val::Nil);
+		*self = __val_arith('+', lhs, rhs.into());
+	}
+}
+impl<T: Into<Val>> std::ops::SubAssign<T> for Val {
+	fn sub_assign(&mut self, rhs: T) {
+		let lhs = std::mem::replace(self, Val::Nil);
+		*self = __val_arith('-', lhs, rhs.into());
+	}
+}
+impl<T: Into<Val>> std::ops::MulAssign<T> for Val {
+	fn mul_assign(&mut self, rhs: T) {
+		let lhs = std::mem::replace(self, Val::Nil);
+		*self = __val_arith('*', lhs, rhs.into());
+	}
+}
+impl std::ops::Neg for Val {
+	type Output = Val;
+	fn neg(self) -> Val {
+		match self {
+			Val::Int(n) => n.checked_neg().map_or(Val::Float(-(n as f64)), Val::Int),
+			Val::Float(n) => Val::Float(-n),
+			other => panic!("cannot negate {}", other.type_name()),
+		}
+	}
+}
+// Plain numbers on the left: `1 + val`
+impl std::ops::Add<Val> for i64 {
+	type Output = Val;
+	fn add(self, rhs: Val) -> Val { __val_arith('+', Val::Int(self), rhs) }
+}
+impl std::ops::Add<Val> for i32 {
+	type Output = Val;
+	fn add(self, rhs: Val) -> Val { __val_arith('+', Val::from(self), rhs) }
+}
+impl std::ops::Add<Val> for f64 {
+	type Output = Val;
+	fn add(self, rhs: Val) -> Val { __val_arith('+', Val::Float(self), rhs) }
+}
+impl std::ops::Mul<Val> for i64 {
+	type Output = Val;
+	fn mul(self, rhs: Val) -> Val { __val_arith('*', Val::Int(self), rhs) }
+}
+impl std::ops::Mul<Val> for f64 {
+	type Output = Val;
+	fn mul(self, rhs: Val) -> Val { __val_arith('*', Val::Float(self), rhs) }
+}
+
+// `x in val`: an item of a list, a key of a map, a substring or char of a string.
+// Scalars contain nothing.
+impl<T: Into<Val> + Clone> std::ops::Contains<T> for Val {
+	fn contains(&self, needle: &T) -> bool {
+		match (self, needle.clone().into()) {
+			(Val::List(v), needle) => v.contains(&needle),
+			(Val::Map(m), Val::Str(key)) => m.contains_key(&key),
+			(Val::Str(s), Val::Str(sub)) => s.contains(sub.as_str()),
+			_ => false,
+		}
+	}
+}
+
+// Indexing: `val["key"]` on maps, `val[0]` on lists. Assigning through a missing
+// map key inserts it.
+impl std::ops::Index<&str> for Val {
+	type Output = Val;
+	fn index(&self, key: &str) -> &Val {
+		match self {
+			Val::Map(m) => match m.get(key) {
+				Some(v) => v,
+				None => panic!("map has no key `{}` (available keys: {})",
+					key, m.keys().map(|k| k.as_str()).collect::<Vec<_>>().join(", ")),
+			},
+			other => panic!("cannot index {} with key `{}`", other.type_name(), key),
+		}
+	}
+}
+impl std::ops::IndexMut<&str> for Val {
+	fn index_mut(&mut self, key: &str) -> &mut Val {
+		match self {
+			Val::Map(m) => m.entry(key.to_string()).or_insert(Val::Nil),
+			other => panic!("cannot index {} with key `{}`", other.type_name(), key),
+		}
+	}
+}
+impl std::ops::Index<usize> for Val {
+	type Output = Val;
+	fn index(&self, index: usize) -> &Val {
+		match self {
+			Val::List(v) if index < v.len() => &v[index],
+			Val::List(v) => panic!("list index {} out of range for length {}", index, v.len()),
+			other => panic!("cannot index {} with {}", other.type_name(), index),
+		}
+	}
+}
+impl std::ops::IndexMut<usize> for Val {
+	fn index_mut(&mut self, index: usize) -> &mut Val {
+		match self {
+			Val::List(v) => {
+				let len = v.len();
+				v.get_mut(index).unwrap_or_else(|| panic!("list index {} out of range for length {}", index, len))
+			}
+			other => panic!("cannot index {} with {}", other.type_name(), index),
+		}
+	}
+}
+
+// Iteration yields list items, map keys or the chars of a string.
+impl IntoIterator for Val {
+	type Item = Val;
+	type IntoIter = std::vec::IntoIter<Val>;
+	fn into_iter(self) -> Self::IntoIter {
+		match self {
+			Val::List(v) => v.into_iter(),
+			Val::Map(m) => m.into_keys().map(Val::Str).collect::<Vec<_>>().into_iter(),
+			Val::Str(s) => s.chars().map(Val::from).collect::<Vec<_>>().into_iter(),
+			Val::Nil => Vec::new().into_iter(),
+			other => panic!("cannot iterate over {}", other.type_name()),
+		}
+	}
+}
+impl IntoIterator for &Val {
+	type Item = Val;
+	type IntoIter = std::vec::IntoIter<Val>;
+	fn into_iter(self) -> Self::IntoIter { self.clone().into_iter() }
+}
+
+// JSON text: `Val::parse_json(text)` / `text.parse::<Val>()` and `val.to_json()`.
+// Integral JSON numbers become Int, everything else Float. Input follows RFC 8259 (no
+// leading zeros, `+` or bare `.`), nested at most __JSON_MAX_DEPTH arrays and objects deep.
+#[derive(Debug, Clone, PartialEq)]
+#[allow(dead_code)]
+pub struct JsonError {
+	pub message: String,
+	pub offset: usize,
+}
+
+impl std::fmt::Display for JsonError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "invalid JSON at byte {}: {}", self.offset, self.message)
+	}
+}
+
+impl std::error::Error for JsonError {}
+
+impl std::str::FromStr for Val {
+	type Err = JsonError;
+	fn from_str(text: &str) -> Result<Val, JsonError> { Val::parse_json(text) }
+}
+
+#[allow(dead_code)]
+impl Val {
+	pub fn parse_json(text: &str) -> Result<Val, JsonError> {
+		let mut parser = __JsonParser { text, pos: 0, depth: 0 };
+		let val = parser.value()?;
+		parser.skip_ws();
+		if parser.pos < text.len() {
+			return Err(parser.error("trailing characters"));
+		}
+		Ok(val)
+	}
+
+	pub fn to_json(&self) -> String {
+		let mut out = String::new();
+		self.write_json(&mut out);
+		out
+	}
+
+	fn write_json(&self, out: &mut String) {
+		match self {
+			Val::Str(s) => __json_write_str(s, out),
+			Val::Int(n) => out.push_str(&n.to_string()),
+			// Debug keeps the `.0` of integral floats; JSON has no NaN or infinity.
+			Val::Float(n) if n.is_finite() => out.push_str(&format!("{:?}", n)),
+			Val::Float(_) | Val::Nil => out.push_str("null"),
+			Val::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+			Val::List(v) => {
+				out.push('[');
+				for (i, item) in v.iter().enumerate() {
+					if i > 0 {
+						out.push(',');
+					}
+					item.write_json(out);
+				}
+				out.push(']');
+			}
+			Val::Map(m) => {
+				out.push('{');
+				for (i, (k, v)) in m.iter().enumerate() {
+					if i > 0 {
+						out.push(',');
+					}
+					__json_write_str(k, out);
+					out.push(':');
+					v.write_json(out);
+				}
+				out.push('}');
+			}
+		}
+	}
+}
+
+#[allow(dead_code)]
+fn __json_write_str(s: &str, out: &mut String) {
+	out.push('"');
+	for c in s.chars() {
+		match c {
+			'"' => out.push_str("\\\""),
+			'\\' => out.push_str("\\\\"),
+			'\n' => out.push_str("\\n"),
+			'\r' => out.push_str("\\r"),
+			'\t' => out.push_str("\\t"),
+			c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+			c => out.push(c),
+		}
+	}
+	out.push('"');
+}
+
+#[allow(dead_code)]
+struct __JsonParser<'a> {
+	text: &'a str,
+	pos: usize,
+	depth: usize,
+}
+
+#[allow(dead_code)]
+const __JSON_MAX_DEPTH: usize = 128;
+
+#[allow(dead_code)]
+impl<'a> __JsonParser<'a> {
+	fn error(&self, message: &str) -> JsonError {
+		JsonError { message: message.to_string(), offset: self.pos }
+	}
+
+	fn peek(&self) -> Option<u8> {
+		self.text.as_bytes().get(self.pos).copied()
+	}
+
+	fn skip_ws(&mut self) {
+		while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+			self.pos += 1;
+		}
+	}
+
+	fn eat_keyword(&mut self, word: &str, val: Val) -> Result<Val, JsonError> {
+		if self.text[self.pos..].starts_with(word) {
+			self.pos += word.len();
+			Ok(val)
+		} else {
+			Err(self.error("unexpected token"))
+		}
+	}
+
+	fn value(&mut self) -> Result<Val, JsonError> {
+		self.skip_ws();
+		match self.peek() {
+			Some(b'{') => self.nested(Self::object),
+			Some(b'[') => self.nested(Self::array),
+			Some(b'"') => Ok(Val::Str(self.string()?)),
+			Some(b't') => self.eat_keyword("true", Val::Bool(true)),
+			Some(b'f') => self.eat_keyword("false", Val::Bool(false)),
+			Some(b'n') => self.eat_keyword("null", Val::Nil),
+			Some(b'-' | b'0'..=b'9') => self.number(),
+			Some(_) => Err(self.error("unexpected character")),
+			None => Err(self.error("unexpected end of input")),
+		}
+	}
+
+	fn nested(&mut self, parse: fn(&mut Self) -> Result<Val, JsonError>) -> Result<Val, JsonError> {
+		if self.depth == __JSON_MAX_DEPTH {
+			return Err(self.error("nesting too deep"));
+		}
+		self.depth += 1;
+		let val = parse(self);
+		self.depth -= 1;
+		val
+	}
+
+	fn object(&mut self) -> Result<Val, JsonError> {
+		self.pos += 1; // {
+		let mut map = std::collections::BTreeMap::new();
+		self.skip_ws();
+		if self.peek() == Some(b'}') {
+			self.pos += 1;
+			return Ok(Val::Map(map));
+		}
+		loop {
+			self.skip_ws();
+			if self.peek() != Some(b'"') {
+				return Err(self.error("expected string key"));
+			}
+			let key = self.string()?;
+			self.skip_ws();
+			if self.peek() != Some(b':') {
+				return Err(self.error("expected `:`"));
+			}
+			self.pos += 1;
+			let value = self.value()?;
+			map.insert(key, value);
+			self.skip_ws();
+			match self.peek() {
+				Some(b',') => self.pos += 1,
+				Some(b'}') => {
+					self.pos += 1;
+					return Ok(Val::Map(map));
+				}
+				_ => return Err(self.error("expected `,` or `}`")),
+			}
+		}
+	}
+
+	fn array(&mut self) -> Result<Val, JsonError> {
+		self.pos += 1; // [
+		let mut items = Vec::new();
+		self.skip_ws();
+		if self.peek() == Some(b']') {
+			self.pos += 1;
+			return Ok(Val::List(items));
+		}
+		loop {
+			items.push(self.value()?);
+			self.skip_ws();
+			match self.peek() {
+				Some(b',') => self.pos += 1,
+				Some(b']') => {
+					self.pos += 1;
+					return Ok(Val::List(items));
+				}
+				_ => return Err(self.error("expected `,` or `]`")),
+			}
+		}
+	}
+
+	fn string(&mut self) -> Result<String, JsonError> {
+		self.pos += 1; // opening quote
+		let mut out = String::new();
+		loop {
+			let Some(c) = self.text[self.pos..].chars().next() else {
+				return Err(self.error("unterminated string"));
+			};
+			self.pos += c.len_utf8();
+			match c {
+				'"' => return Ok(out),
+				'\\' => {
+					let escape = self.peek().ok_or_else(|| self.error("unterminated string"))?;
+					self.pos += 1;
+					match escape {
+						b'"' => out.push('"'),
+						b'\\' => out.push('\\'),
+						b'/' => out.push('/'),
+						b'b' => out.push('\u{8}'),
+						b'f' => out.push('\u{c}'),
+						b'n' => out.push('\n'),
+						b'r' => out.push('\r'),
+						b't' => out.push('\t'),
+						b'u' => out.push(self.unicode_escape()?),
+						_ => return Err(self.error("invalid escape")),
+					}
+				}
+				c => out.push(c),
+			}
+		}
+	}
+
+	fn hex4(&mut self) -> Result<u32, JsonError> {
+		let digits = self.text.get(self.pos..self.pos + 4).ok_or_else(|| self.error("invalid \\u escape"))?;
+		if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+			return Err(self.error("invalid \\u escape"));
+		}
+		let code = u32::from_str_radix(digits, 16).map_err(|_| self.error("invalid \\u escape"))?;
+		self.pos += 4;
+		Ok(code)
+	}
+
+	// `\uXXXX`, combining UTF-16 surrogate pairs.
+	fn unicode_escape(&mut self) -> Result<char, JsonError> {
+		let high = self.hex4()?;
+		let code = if (0xD800..0xDC00).contains(&high) {
+			if !self.text[self.pos..].starts_with("\\u") {
+				return Err(self.error("unpaired surrogate"));
+			}
+			self.pos += 2;
+			let low = self.hex4()?;
+			if !(0xDC00..0xE000).contains(&low) {
+				return Err(self.error("unpaired surrogate"));
+			}
+			0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+		} else {
+			high
+		};
+		char::from_u32(code).ok_or_else(|| self.error("invalid \\u escape"))
+	}
+
+	fn digits(&mut self) -> usize {
+		let start = self.pos;
+		while let Some(b'0'..=b'9') = self.peek() {
+			self.pos += 1;
+		}
+		self.pos - start
+	}
+
+	// -? (0 | [1-9][0-9]*) (. [0-9]+)? ([eE] [+-]? [0-9]+)?
+	fn number(&mut self) -> Result<Val, JsonError> {
+		let start = self.pos;
+		let invalid = || JsonError { message: "invalid number".to_string(), offset: start };
+		if self.peek() == Some(b'-') {
+			self.pos += 1;
+		}
+		match self.peek() {
+			Some(b'0') => {
+				self.pos += 1;
+				if let Some(b'0'..=b'9') = self.peek() {
+					return Err(JsonError { message: "leading zero".to_string(), offset: start });
+				}
+			}
+			Some(b'1'..=b'9') => {
+				self.digits();
+			}
+			_ => return Err(invalid()),
+		}
+		let mut integral = true;
+		if self.peek() == Some(b'.') {
+			integral = false;
+			self.pos += 1;
+			if self.digits() == 0 {
+				return Err(invalid());
+			}
+		}
+		if let Some(b'e' | b'E') = self.peek() {
+			integral = false;
+			self.pos += 1;
+			if let Some(b'+' | b'-') = self.peek() {
+				self.pos += 1;
+			}
+			if self.digits() == 0 {
+				return Err(invalid());
+			}
+		}
+		let text = &self.text[start..self.pos];
+		if integral {
+			if let Ok(n) = text.parse::<i64>() {
+				return Ok(Val::Int(n));
+			}
+		}
+		text.parse::<f64>().map(Val::Float).map_err(|_| invalid())
+	}
+}
@@ -0,0 +1,150 @@
+//! Stable signature hashes for `#[dynexport]` items.
+//!
+//! Every `#[dynexport]` item gets a `dynexport_meta_<name>` record carrying a 64-bit hash of its
+//! type, which a loader compares against the signature it expects before calling through the
+//! symbol. Both sides must agree on that hash without sharing a compiler, a crate graph or even a
+//! Rust release, so it is computed in two steps that are fixed here:
+//!
+//! 1. The type is written out as a canonical *encoding*: a string that mentions only what decides
+//!    how values are laid out and passed, and nothing about where the type was defined.
+//! 2. The encoding is hashed with [`dynexport_hash`], 64-bit FNV-1a over a version tag followed by
+//!    the UTF-8 bytes of the encoding.
+//!
+//! ## Encoding
+//!
+//! ```text
+//! fn      = "extern " ABI " fn(" (ty ",")* ("..." ",")? ")->" ty
+//! ty      = "bool" | "char" | "str" | "!" | "i8" ... "i128" | "isize" | "u8" ... "usize"
+//!         | "f16" | "f32" | "f64" | "f128"
+//!         | "&" ty | "&mut " ty | "*const " ty | "*mut " ty
+//!         | "[" ty "]" | "[" ty ";" LEN "]" | "(" (ty ",")* ")"
+//!         | NAME ("<" (ty ",")* ">")?                          standard library types
+//!         | repr? "struct " NAME "{" (FIELD ":" ty ",")* "}"
+//!         | repr? "union " NAME "{" (FIELD ":" ty ",")* "}"
+//!         | repr? "enum " NAME "{" (VARIANT "=" DISCR "{" (FIELD ":" ty ",")* "}" ",")* "}"
+//!         | "dyn " NAME | fn
+//! repr    = "#[repr(" ("C" | "transparent" | "packed(" N ")" | "align(" N ")" | INT) ","... ")]"
+//! ```
+//!
+//! `()` is the empty tuple. Lifetimes are erased. Field names are part of the encoding; tuple
+//! struct fields are named `0`, `1`, ... Types from `std`, `core` and `alloc` are written by name
+//! with their generic arguments (`String`, `Vec<i32,>`, `Option<&str,>`), leaving out parameters
+//! that have a default such as `Vec`'s allocator: their internals are not something either side
+//! can see or control. Every other type is expanded structurally, so two
+//! crates that each define `struct Point { x: f64, y: f64 }` produce the same encoding, while
+//! renaming a field, reordering fields or adding `#[repr(C)]` changes it. A type that refers back
+//! to itself is written by name at the point of recursion.
+//!
+//! Statics are encoded as their type alone.
+//!
+//! ## Interface descriptions
+//!
+//! Next to the record, `dynexport_wit_<name>` holds a NUL-terminated, WIT-style description of
+//! the item in the `.rustc_dynexport_wit` section (`__DATA,__dynexport_wit` on Darwin), so a host
+//! can list a library's items and their types by reading one section. Each description is the
+//! definitions of the records, variants and enums it uses, one per line with dependencies first,
+//! followed by the item itself:
+//!
+//! ```text
+//! record point { x: f64, y: f64 }
+//! midpoint: func(a: point, b: point) -> point;
+//! ```
+//!
+//! Statics read `NAME: static TYPE;`. Type, field and case names are kebab-case as in WIT, item
+//! and parameter names are kept as in Rust since they name symbols. `String` and `str` are
+//! `string`, sequences are `list<T>`, maps `list<tuple<K, V>>`, references and smart pointers
+//! are their pointee, and raw pointers `ptr<T>`. Unlike the hash, the description is meant for
+//! people and tools, not for checking compatibility.
+//!
+//! ## Flags
+//!
+//! The `u32` after the compiler hash in the record describes the item:
+//!
+//! ```text
+//! bit 0       DYNEXPORT_FLAG_STATIC    a static; clear for functions
+//! bit 1       DYNEXPORT_FLAG_NOUNWIND  the function can't unwind, a panic in it aborts instead
+//!                                      of reaching the caller
+//! bit 2       DYNEXPORT_FLAG_C_SHIM    a `dynexport_c_<name>` shim taking and returning the
+//!                                      converted std types exists next to the function
+//! bits 3..16  reserved, zero; loaders ignore them
+//! bits 16..32 the N of `#[dynexport(version = N)]`, 0 without one
+//! ```
+//!
+//! The version is a minor version: a breaking change to the signature already shows up in the
+//! type hash, so a loader accepts an item whose version is at least the one it was written
+//! against.
+
+use crate::ExternAbi;
+
+#[cfg(test)]
+mod tests;
+
+/// Bumped whenever the encoding changes, so old and new hashes never collide.
+pub const DYNEXPORT_HASH_VERSION: u32 = 1;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Hash a canonical type encoding (see the module docs) into the value stored in
+/// `dynexport_meta_<name>`.
+pub fn dynexport_hash(encoding: &str) -> u64 {
+    let tag = format!("dynexport-v{DYNEXPORT_HASH_VERSION}\0");
+    tag.bytes().chain(encoding.bytes()).fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+/// Hash of the compiler version stored next to the type hash, for loaders that require the
+/// library to come from the same compiler.
+pub fn dynexport_compiler_hash(version: &str) -> u32 {
+    dynexport_hash(version) as u32
+}
+
+/// Flag bit: the item is a static.
+pub const DYNEXPORT_FLAG_STATIC: u32 = 1 << 0;
+/// Flag bit: the function can't unwind into its caller.
+pub const DYNEXPORT_FLAG_NOUNWIND: u32 = 1 << 1;
+/// Flag bit: the function has a C-ABI `dynexport_c_<name>` shim.
+pub const DYNEXPORT_FLAG_C_SHIM: u32 = 1 << 2;
+/// Position of the item version in the flags.
+pub const DYNEXPORT_VERSION_SHIFT: u32 = 16;
+
+/// The flags word of a `dynexport_meta_<name>` record (see the module docs).
+pub fn dynexport_flags(is_static: bool, nounwind: bool, c_shim: bool, version: u16) -> u32 {
+    let mut flags = u32::from(version) << DYNEXPORT_VERSION_SHIFT;
+    if is_static {
+        flags |= DYNEXPORT_FLAG_STATIC;
+    }
+    if nounwind {
+        flags |= DYNEXPORT_FLAG_NOUNWIND;
+    }
+    if c_shim {
+        flags |= DYNEXPORT_FLAG_C_SHIM;
+    }
+    flags
+}
+
+/// The `#[dynexport(version = N)]` stored in a flags word.
+pub fn dynexport_version(flags: u32) -> u16 {
+    (flags >> DYNEXPORT_VERSION_SHIFT) as u16
+}
+
+/// Assemble the encoding of a function signature from the encodings of its parts.
+pub fn encode_fn_sig<'a>(
+    abi: ExternAbi,
+    inputs: impl IntoIterator<Item = &'a str>,
+    c_variadic: bool,
+    output: &str,
+) -> String {
+    let mut out = format!("extern \"{}\" fn(", abi.as_str());
+    for input in inputs {
+        out.push_str(input);
+        out.push(',');
+    }
+    if c_variadic {
+        out.push_str("...,");
+    }
+    out.push_str(")->");
+    out.push_str(output);
+    out
+}
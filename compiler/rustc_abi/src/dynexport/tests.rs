@@ -0,0 +1,45 @@
+use super::*;
+
+#[test]
+fn encode_fn_sig_layout() {
+    let rust = ExternAbi::Rust;
+    assert_eq!(
+        encode_fn_sig(rust, ["i32", "&str"], false, "String"),
+        "extern \"Rust\" fn(i32,&str,)->String"
+    );
+    assert_eq!(encode_fn_sig(rust, [], false, "()"), "extern \"Rust\" fn()->()");
+    assert_eq!(
+        encode_fn_sig(ExternAbi::C { unwind: false }, ["*const u8"], true, "i32"),
+        "extern \"C\" fn(*const u8,...,)->i32"
+    );
+}
+
+#[test]
+fn dynexport_hash_is_pinned() {
+    // These values are part of the on-disk format: a loader built by another compiler computes
+    // the same numbers. Changing them requires bumping `DYNEXPORT_HASH_VERSION`.
+    assert_eq!(dynexport_hash(""), 0x073d_a531_d9a6_0304);
+    assert_eq!(dynexport_hash("extern \"Rust\" fn(i32,i32,)->i32"), 0x6a1d_0d3b_5856_0146);
+    assert_eq!(dynexport_hash("extern \"C\" fn(*const u8,...,)->i32"), 0x3c1e_26c3_33f3_d0f7);
+}
+
+#[test]
+fn dynexport_hash_distinguishes_signatures() {
+    let add = encode_fn_sig(ExternAbi::Rust, ["i32", "i32"], false, "i32");
+    let add_c = encode_fn_sig(ExternAbi::C { unwind: false }, ["i32", "i32"], false, "i32");
+    let add_long = encode_fn_sig(ExternAbi::Rust, ["i64", "i32"], false, "i32");
+    assert_eq!(dynexport_hash(&add), dynexport_hash(&add.clone()));
+    assert_ne!(dynexport_hash(&add), dynexport_hash(&add_c));
+    assert_ne!(dynexport_hash(&add), dynexport_hash(&add_long));
+    assert_ne!(dynexport_hash("struct P{x:f64,y:f64,}"), dynexport_hash("struct P{y:f64,x:f64,}"));
+}
+
+#[test]
+fn dynexport_flags_layout() {
+    // Also part of the on-disk format.
+    assert_eq!(dynexport_flags(false, false, false, 0), 0);
+    assert_eq!(dynexport_flags(true, false, false, 0), 0b001);
+    assert_eq!(dynexport_flags(false, true, true, 0), 0b110);
+    assert_eq!(dynexport_flags(false, true, false, 3), 0x0003_0002);
+    assert_eq!(dynexport_version(dynexport_flags(false, false, true, u16::MAX)), u16::MAX);
+}
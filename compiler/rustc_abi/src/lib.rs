@@ -54,6 +54,7 @@
 
 mod callconv;
 mod canon_abi;
+pub mod dynexport;
 mod extern_abi;
 mod layout;
 #[cfg(test)]
@@ -22,6 +22,10 @@ pub enum AssocOp {
     NullCoalesce,
     /// `≈` approximate equality
     ApproxEq,
+    /// `|>` reverse function application (script mode)
+    Pipe,
+    /// `not in` negated containment (script mode)
+    NotIn,
 }
 
 #[derive(PartialEq, Debug)]
@@ -90,7 +94,8 @@ pub fn precedence(&self) -> ExprPrecedence {
             Binary(bin_op) => bin_op.precedence(),
             Range(_) => ExprPrecedence::Range,
             NullCoalesce => ExprPrecedence::NullCoalesce,
-            ApproxEq => ExprPrecedence::Compare,
+            ApproxEq | NotIn => ExprPrecedence::Compare,
+            Pipe => ExprPrecedence::BitOr,
             Assign | AssignOp(_) => ExprPrecedence::Assign,
         }
     }
@@ -105,7 +110,8 @@ pub fn fixity(&self) -> Fixity {
             Cast | Is => Fixity::Left,
             Range(_) => Fixity::None,
             NullCoalesce => Fixity::Right, // a ?? b ?? c == a ?? (b ?? c)
-            ApproxEq => Fixity::None, // comparison operators are non-associative
+            ApproxEq | NotIn => Fixity::None, // comparison operators are non-associative
+            Pipe => Fixity::Left,          // x |> f |> g == (x |> f) |> g
         }
     }
 
@@ -113,8 +119,8 @@ pub fn is_comparison(&self) -> bool {
         use AssocOp::*;
         match *self {
             Binary(binop) => binop.is_comparison(),
-            ApproxEq => true,
-            Assign | AssignOp(_) | Cast | Is | Range(_) | NullCoalesce => false,
+            ApproxEq | NotIn => true,
+            Assign | AssignOp(_) | Cast | Is | Range(_) | NullCoalesce | Pipe => false,
         }
     }
 
@@ -122,7 +128,7 @@ pub fn is_assign_like(&self) -> bool {
         use AssocOp::*;
         match *self {
             Assign | AssignOp(_) => true,
-            Cast | Is | Binary(_) | Range(_) | NullCoalesce | ApproxEq => false,
+            Cast | Is | Binary(_) | Range(_) | NullCoalesce | ApproxEq | Pipe | NotIn => false,
         }
     }
 
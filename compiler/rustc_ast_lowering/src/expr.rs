@@ -122,6 +122,24 @@ pub(super) fn lower_expr_mut(&mut self, e: &Expr) -> hir::Expr<'hir> {
                         hir::ExprKind::Call(f, self.lower_exprs(args))
                     }
                 }
+                // Script mode: `helper.Hello(x)` on a module is `helper::Hello(x)`,
+                // see `resolve_script_module_item`
+                ExprKind::MethodCall(box MethodCall { seg, receiver, args, .. })
+                    if self.is_script_module_item(e.id) =>
+                {
+                    let item = self.lower_path_segment(
+                        e.span,
+                        seg,
+                        ParamMode::Optional,
+                        GenericArgsMode::Err,
+                        ImplTraitContext::Disallowed(ImplTraitPosition::Path),
+                        None,
+                    );
+                    let path_span = receiver.span.to(seg.ident.span);
+                    let qpath = self.lower_script_module_item(e.id, receiver, item, path_span);
+                    let f = self.arena.alloc(self.expr(path_span, hir::ExprKind::Path(qpath)));
+                    hir::ExprKind::Call(f, self.lower_exprs(args))
+                }
                 ExprKind::MethodCall(box MethodCall { seg, receiver, args, span }) => {
                     let hir_seg = self.arena.alloc(self.lower_path_segment(
                         e.span,
@@ -137,6 +155,16 @@ pub(super) fn lower_expr_mut(&mut self, e: &Expr) -> hir::Expr<'hir> {
                         self.arena.alloc_from_iter(args.iter().map(|x| self.lower_expr_mut(x)));
                     hir::ExprKind::MethodCall(hir_seg, receiver, args, self.lower_span(*span))
                 }
+                // Script-mode pipe: `x | f` is `f(x)` when `f` names a function,
+                // and stays bitwise-or otherwise (`2 | 4 == 6`)
+                ExprKind::Binary(binop, lhs, rhs)
+                    if binop.node == BinOpKind::BitOr
+                        && self.tcx.sess.is_script_mode()
+                        && self.is_fn_path(rhs) =>
+                {
+                    let f = self.lower_expr(rhs);
+                    hir::ExprKind::Call(f, self.lower_exprs(std::slice::from_ref(lhs)))
+                }
                 ExprKind::Binary(binop, lhs, rhs) => {
                     let binop = self.lower_binop(*binop);
                     let lhs = self.lower_expr(lhs);
@@ -286,6 +314,17 @@ pub(super) fn lower_expr_mut(&mut self, e: &Expr) -> hir::Expr<'hir> {
                     self.lower_expr(el),
                     self.lower_expr(er),
                 ),
+                // Script mode: `units.KiloMeter` on a module is the path `units::KiloMeter`
+                ExprKind::Field(el, ident) if self.is_script_module_item(e.id) => {
+                    let res = self.resolver.get_partial_res(e.id).and_then(|pr| pr.full_res());
+                    let res = res.unwrap_or(Res::Err);
+                    let item = hir::PathSegment::new(
+                        self.lower_ident(*ident),
+                        self.next_id(),
+                        self.lower_res(res),
+                    );
+                    hir::ExprKind::Path(self.lower_script_module_item(e.id, el, item, e.span))
+                }
                 ExprKind::Field(el, ident) => {
                     hir::ExprKind::Field(self.lower_expr(el), self.lower_ident(*ident))
                 }
@@ -2183,6 +2222,58 @@ fn lower_expr_optional_method_call(
         )
     }
 
+    /// Whether the resolver took the field access or method call `id` for a script-mode
+    /// `module.item` path (see `resolve_script_module_item`).
+    fn is_script_module_item(&self, id: NodeId) -> bool {
+        self.tcx.sess.is_script_mode() && self.resolver.get_partial_res(id).is_some()
+    }
+
+    /// The path `module::item` for a script-mode `module.item`, where `module` is the single
+    /// segment path `base` and `item` is already lowered.
+    fn lower_script_module_item(
+        &mut self,
+        id: NodeId,
+        base: &Expr,
+        item: hir::PathSegment<'hir>,
+        span: Span,
+    ) -> hir::QPath<'hir> {
+        let ExprKind::Path(None, base) = &base.kind else {
+            span_bug!(base.span, "script module item on a non-path expression")
+        };
+        let module = self.lower_path_segment(
+            base.span,
+            &base.segments[0],
+            ParamMode::Optional,
+            GenericArgsMode::Err,
+            ImplTraitContext::Disallowed(ImplTraitPosition::Path),
+            None,
+        );
+        let res = self.resolver.get_partial_res(id).and_then(|pr| pr.full_res());
+        let path = self.arena.alloc(hir::Path {
+            span: self.lower_span(span),
+            res: self.lower_res(res.unwrap_or(Res::Err)),
+            segments: self.arena.alloc_from_iter([module, item]),
+        });
+        hir::QPath::Resolved(None, path)
+    }
+
+    /// Whether `e` is a path resolving to a function or tuple-struct constructor,
+    /// i.e. something the script-mode pipe `x | f` can call.
+    fn is_fn_path(&self, e: &Expr) -> bool {
+        if !matches!(e.kind, ExprKind::Path(None, _)) {
+            return false;
+        }
+        self.get_partial_res(e.id).and_then(|res| res.full_res()).is_some_and(|res| {
+            matches!(
+                res,
+                Res::Def(
+                    DefKind::Fn | DefKind::AssocFn | DefKind::Ctor(_, hir::def::CtorKind::Fn),
+                    _
+                )
+            )
+        })
+    }
+
     /// Desugar `expr ?? default` into:
     /// ```ignore (pseudo-rust)
     /// match expr {
@@ -508,6 +508,7 @@ macro_rules! gate_all {
     gate_all!(default_field_values, "default values on fields are experimental");
     gate_all!(fn_delegation, "functions delegation is not yet fully implemented");
     gate_all!(postfix_match, "postfix match is experimental");
+    gate_all!(in_operator, "the `in` operator is experimental");
     gate_all!(mut_ref, "mutable by-reference bindings are experimental");
     gate_all!(min_generic_const_args, "unbraced const blocks as const args are experimental");
     // associated_const_equality is stabilized as part of min_generic_const_args
@@ -369,17 +369,65 @@ impl<S: Stage> NoArgsAttributeParser<S> for NoMangleParser {
 }
 
 pub(crate) struct DynExportParser;
-impl<S: Stage> NoArgsAttributeParser<S> for DynExportParser {
+impl<S: Stage> SingleAttributeParser<S> for DynExportParser {
     const PATH: &[Symbol] = &[sym::dynexport];
+    const ATTRIBUTE_ORDER: AttributeOrder = AttributeOrder::KeepOutermost;
     const ON_DUPLICATE: OnDuplicate<S> = OnDuplicate::Warn;
     const ALLOWED_TARGETS: AllowedTargets = AllowedTargets::AllowListWarnRest(&[
         Allow(Target::Fn),
         Allow(Target::Static),
+        // Structs and enums get a `#[repr(C)]` mirror for the C-ABI shims (see
+        // `rustc_builtin_macros::dynexport`); they emit no symbol themselves.
+        Allow(Target::Struct),
+        Allow(Target::Enum),
         Allow(Target::Method(MethodKind::Inherent)),
         Allow(Target::Method(MethodKind::TraitImpl)),
         Error(Target::Closure),
     ]);
-    const CREATE: fn(Span) -> AttributeKind = AttributeKind::DynExport;
+    const TEMPLATE: AttributeTemplate = template!(Word, List: &["version = N"]);
+
+    fn convert(cx: &mut AcceptContext<'_, '_, S>, args: &ArgParser) -> Option<AttributeKind> {
+        let list = match args {
+            ArgParser::NoArgs => {
+                return Some(AttributeKind::DynExport { span: cx.attr_span, version: None });
+            }
+            ArgParser::List(list) => list,
+            ArgParser::NameValue(_) => {
+                cx.expected_list_or_no_args(cx.attr_span);
+                return None;
+            }
+        };
+        let Some(arg) = list.single() else {
+            cx.expected_single_argument(list.span);
+            return None;
+        };
+        let Some(meta_item) = arg.meta_item() else {
+            cx.expected_name_value(arg.span(), Some(sym::version));
+            return None;
+        };
+        if meta_item.path().word_sym() != Some(sym::version) {
+            cx.expected_specific_argument(meta_item.path().span(), &[sym::version]);
+            return None;
+        }
+        let Some(name_value) = meta_item.args().name_value() else {
+            cx.expected_name_value(meta_item.span(), Some(sym::version));
+            return None;
+        };
+        let rustc_ast::LitKind::Int(version, _) = name_value.value_as_lit().kind else {
+            cx.expected_integer_literal(name_value.value_span);
+            return None;
+        };
+        // The version is stored in the upper half of the metadata flags.
+        let Ok(version) = version.get().try_into() else {
+            cx.expected_integer_literal_in_range(
+                name_value.value_span,
+                u16::MIN as isize,
+                u16::MAX as isize,
+            );
+            return None;
+        };
+        Some(AttributeKind::DynExport { span: cx.attr_span, version: Some(version) })
+    }
 }
 
 #[derive(Default)]
@@ -0,0 +1,108 @@
+//! Adds the C-ABI companions of `#[dynexport]` items before expansion.
+//!
+//! Each `#[dynexport]` Rust-ABI fn whose signature has a C representation gets a
+//! `dynexport_c_<name>` shim next to it and is marked `#[rustc_dynexport_c_shim]`, which sets
+//! the C-shim bit of its metadata flags. Each `#[dynexport]` struct or enum gets a `#[repr(C)]`
+//! mirror with its conversions. The representation is documented in
+//! compiler/extensions/src/ffi.rs and injected once per crate as `__dynexport_ffi`. Items in
+//! out-of-line modules are not loaded yet at this point and keep their Rust-ABI export only.
+
+use rustc_ast as ast;
+use rustc_ast::attr::{contains_name, mk_attr_word};
+use rustc_expand::base::ResolverExpand;
+use rustc_parse::transformer::dynexport;
+use rustc_session::Session;
+use rustc_span::hygiene::AstPass;
+use rustc_span::{DUMMY_SP, LocalExpnId, Symbol, sym};
+use thin_vec::ThinVec;
+
+pub fn inject(krate: &mut ast::Crate, sess: &Session, resolver: &mut dyn ResolverExpand) {
+    if contains_name(&krate.attrs, sym::no_std) || contains_name(&krate.attrs, sym::no_core) {
+        return;
+    }
+
+    let mut adts = Vec::new();
+    collect_adts(&krate.items, &mut adts);
+
+    // Scripts already carry the FFI support as `__script_helpers::dynexport`, where it also
+    // decodes what they load from other libraries.
+    let module =
+        if sess.is_script_mode() { "__script_helpers::dynexport" } else { dynexport::FFI_MODULE };
+    let cx = dynexport::ShimCx { adts: &adts, ffi: format!("crate::{module}") };
+    // Only crates with shims get the expansion that lets their marker use `rustc_attrs`.
+    let mut expn_id = None;
+    let mut marker_expn = || {
+        *expn_id.get_or_insert_with(|| {
+            let allow = [sym::rustc_attrs];
+            resolver.expansion_for_ast_pass(DUMMY_SP, AstPass::DynexportShims, &allow, None)
+        })
+    };
+    if !inject_in_items(&mut krate.items, sess, &cx, &mut marker_expn) || sess.is_script_mode() {
+        return;
+    }
+    let source = dynexport::ffi_module_source(module);
+    krate.items.extend(dynexport::parse_generated_items(&sess.psess, source, DUMMY_SP));
+}
+
+/// Names of the `#[dynexport]` structs and enums, which shim signatures may mention.
+fn collect_adts(items: &[Box<ast::Item>], adts: &mut Vec<Symbol>) {
+    for item in items {
+        match &item.kind {
+            ast::ItemKind::Struct(ident, ..) | ast::ItemKind::Enum(ident, ..)
+                if contains_name(&item.attrs, sym::dynexport) =>
+            {
+                adts.push(ident.name)
+            }
+            ast::ItemKind::Mod(_, _, ast::ModKind::Loaded(items, ..)) => collect_adts(items, adts),
+            _ => {}
+        }
+    }
+}
+
+/// Insert companions after their items, returning whether any were added. Functions that got
+/// a shim are marked `#[rustc_dynexport_c_shim]`, with a span in the expansion `marker_expn`
+/// returns.
+fn inject_in_items(
+    items: &mut ThinVec<Box<ast::Item>>,
+    sess: &Session,
+    cx: &dynexport::ShimCx<'_>,
+    marker_expn: &mut dyn FnMut() -> LocalExpnId,
+) -> bool {
+    let mut injected = false;
+    let mut i = 0;
+    while i < items.len() {
+        if let ast::ItemKind::Mod(_, _, ast::ModKind::Loaded(inner, ..)) = &mut items[i].kind {
+            injected |= inject_in_items(inner, sess, cx, marker_expn);
+        }
+        if contains_name(&items[i].attrs, sym::dynexport) {
+            match dynexport::companion_source(&items[i], cx) {
+                Ok(Some(source)) => {
+                    if matches!(items[i].kind, ast::ItemKind::Fn(_)) {
+                        let span = items[i].span.with_def_site_ctxt(marker_expn().to_expn_id());
+                        items[i].attrs.push(mk_attr_word(
+                            &sess.psess.attr_id_generator,
+                            ast::AttrStyle::Outer,
+                            ast::Safety::Default,
+                            sym::rustc_dynexport_c_shim,
+                            span,
+                        ));
+                    }
+                    let companions =
+                        dynexport::parse_generated_items(&sess.psess, source, items[i].span);
+                    let count = companions.len();
+                    for (offset, companion) in companions.into_iter().enumerate() {
+                        items.insert(i + 1 + offset, companion);
+                    }
+                    i += count;
+                    injected = true;
+                }
+                Ok(None) => {}
+                Err((span, msg)) => {
+                    sess.dcx().struct_span_err(span, msg).emit();
+                }
+            }
+        }
+        i += 1;
+    }
+    injected
+}
@@ -245,6 +245,15 @@ fn make_format_args(
 
     let fmt_str = fmt_str.as_str(); // for the suggestions below
     let fmt_snippet = ecx.source_map().span_to_snippet(unexpanded_fmt_span).ok();
+
+    // A printf-style template is parsed in its translated form. Its spans no longer line up with
+    // the source, so diagnostics point at the whole format string instead.
+    let printf_fmt_str = translate_printf(ecx, fmt_str, str_style, &args, append_newline);
+    let (fmt_str, fmt_snippet) = match &printf_fmt_str {
+        Some(translated) => (translated.as_str(), None),
+        None => (fmt_str, fmt_snippet),
+    };
+
     let mut parser = parse::Parser::new(
         fmt_str,
         str_style,
@@ -614,6 +623,49 @@ enum ArgRef<'a> {
     }))
 }
 
+/// Translates a `printf`-style template like `"%s is %5.1f"` into the equivalent Rust template.
+///
+/// Plenty of code uses `%` as literal text (e.g. `%f` in `strftime`-like templates), so this only
+/// kicks in when the template leaves explicit arguments without a reference. Outside of script
+/// crates, the template must also not contain a single `{}` placeholder. Any leftover mismatch
+/// between the translated placeholders and the arguments is then reported as usual, while
+/// directives without a Rust equivalent keep the template as is and get explained by
+/// `report_missing_placeholders`.
+fn translate_printf(
+    ecx: &ExtCtxt<'_>,
+    fmt_str: &str,
+    str_style: Option<usize>,
+    args: &FormatArguments,
+    append_newline: bool,
+) -> Option<String> {
+    if args.explicit_args().is_empty() || !fmt_str.contains('%') {
+        return None;
+    }
+
+    let mut parser =
+        parse::Parser::new(fmt_str, str_style, None, append_newline, parse::ParseMode::Format);
+    let mut placeholders = 0;
+    let mut references = 0;
+    while let Some(piece) = parser.next() {
+        let parse::Piece::NextArgument(arg) = piece else { continue };
+        placeholders += 1;
+        references += 1;
+        for count in [&arg.format.width, &arg.format.precision] {
+            if !matches!(count, parse::CountIs(_) | parse::CountImplied) {
+                references += 1;
+            }
+        }
+    }
+    if !parser.errors.is_empty()
+        || (placeholders > 0 && !ecx.sess.is_script_mode())
+        || references >= args.explicit_args().len()
+    {
+        return None;
+    }
+
+    super::format_foreign::printf::translate_format(fmt_str)
+}
+
 fn invalid_placeholder_type_error(
     ecx: &ExtCtxt<'_>,
     ty: &str,
@@ -128,8 +128,10 @@ pub(crate) fn translate(&self) -> Result<String, Option<String>> {
                 "d" | "i" | "u" => (None, true, true),
                 "f" | "F" => (None, false, false),
                 "s" | "c" => (None, false, false),
+                "v" | "t" => (None, false, false),
+                "?" => (Some(self.type_), false, false),
                 "e" | "E" => (Some(self.type_), true, false),
-                "x" | "X" | "o" => (Some(self.type_), true, true),
+                "x" | "X" | "o" | "b" => (Some(self.type_), true, true),
                 "p" => (Some(self.type_), false, true),
                 "g" => (Some("e"), true, false),
                 "G" => (Some("E"), true, false),
@@ -155,7 +157,7 @@ pub(crate) fn translate(&self) -> Result<String, Option<String>> {
             };
 
             let align = match (self.type_, width.is_some(), align.is_some()) {
-                ("s", true, false) => Some(">"),
+                ("s" | "v" | "t" | "?", true, false) => Some(">"),
                 _ => align,
             };
 
@@ -165,7 +167,7 @@ pub(crate) fn translate(&self) -> Result<String, Option<String>> {
             };
 
             let alt = match type_ {
-                Some("x" | "X") => alt,
+                Some("x" | "X" | "?") => alt,
                 _ => false,
             };
 
@@ -289,6 +291,30 @@ fn translate(&self, s: &mut String) -> std::fmt::Result {
         }
     }
 
+    /// Translates every `printf`-style directive in `s` into the equivalent Rust formatting
+    /// directive, turning `%%` escapes into a plain `%` and leaving all other text untouched.
+    ///
+    /// Returns `None` if `s` contains no directive at all, or one without a Rust equivalent.
+    pub(crate) fn translate_format(s: &str) -> Option<String> {
+        let mut translated = String::with_capacity(s.len());
+        let mut has_directive = false;
+        let mut last = 0;
+        for sub in iter_subs(s, 0) {
+            let InnerSpan { start, end } = sub.position();
+            translated.push_str(&s[last..start]);
+            match &sub {
+                Substitution::Format(fmt) => {
+                    has_directive = true;
+                    translated.push_str(&fmt.translate().ok()?);
+                }
+                Substitution::Escape(_) => translated.push('%'),
+            }
+            last = end;
+        }
+        translated.push_str(&s[last..]);
+        has_directive.then_some(translated)
+    }
+
     /// Returns an iterator over all substitutions in a given string.
     pub(crate) fn iter_subs(s: &str, start_pos: usize) -> Substitutions<'_> {
         Substitutions { s, pos: start_pos }
@@ -538,7 +564,10 @@ macro_rules! move_to {
                     move_to!(next1);
                 }
 
-                ('h' | 'l' | 'L' | 'z' | 'j' | 't' | 'q', _) => {
+                // `t` is only a length modifier in front of an integer conversion; on its own,
+                // it's the `%t` boolean conversion.
+                ('h' | 'l' | 'L' | 'z' | 'j' | 'q', _)
+                | ('t', Some(('d' | 'i' | 'o' | 'u' | 'x' | 'X' | 'n', _))) => {
                     state = Type;
                     length = Some(at.slice_between(next).unwrap());
                     move_to!(next);
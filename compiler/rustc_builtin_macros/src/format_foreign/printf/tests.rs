@@ -1,4 +1,7 @@
-use super::{Format as F, Num as N, Substitution as S, iter_subs, parse_next_substitution as pns};
+use super::{
+    Format as F, Num as N, Substitution as S, iter_subs, parse_next_substitution as pns,
+    translate_format,
+};
 
 macro_rules! assert_eq_pnsat {
     ($lhs:expr, $rhs:expr) => {
@@ -93,6 +96,12 @@ macro_rules! assert_pns_eq_sub {
         { Some(1), "", Some(N::Arg(2)), Some(N::Arg(3)), None, "d", (0, 11), });
     assert_pns_eq_sub!("%-8ld",
         { None, "-", Some(N::Num(8)), None, Some("l"), "d", (0, 5), });
+    assert_pns_eq_sub!("%t",
+        { None, "", None, None, None, "t", (0, 2), });
+    assert_pns_eq_sub!("%td",
+        { None, "", None, None, Some("t"), "d", (0, 3), });
+    assert_pns_eq_sub!("%#?",
+        { None, "#", None, None, None, "?", (0, 3), });
 }
 
 #[test]
@@ -142,4 +151,29 @@ fn test_translation() {
     assert_eq_pnsat!("%2$.*3$d", Some("{1:02$}"));
     assert_eq_pnsat!("%1$*2$.*3$s", Some("{0:>1$.2$}"));
     assert_eq_pnsat!("%-8ld", Some("{:<8}"));
+
+    assert_eq_pnsat!("%b", Some("{:b}"));
+    assert_eq_pnsat!("%08b", Some("{:08b}"));
+    assert_eq_pnsat!("%v", Some("{}"));
+    assert_eq_pnsat!("%t", Some("{}"));
+    assert_eq_pnsat!("%?", Some("{:?}"));
+    assert_eq_pnsat!("%#?", Some("{:#?}"));
+    assert_eq_pnsat!("%8v", Some("{:>8}"));
+    assert_eq_pnsat!("%-6t", Some("{:<6}"));
+    assert_eq_pnsat!("%.3v", Some("{:.3}"));
+}
+
+#[test]
+fn test_translate_format() {
+    assert_eq!(translate_format("no directives"), None);
+    assert_eq!(translate_format("100%% sure"), None);
+    assert_eq!(translate_format("trailing %"), None);
+    assert_eq!(
+        translate_format("%s is %d years (%5.1f%%)\n").as_deref(),
+        Some("{} is {} years ({:5.1}%)\n")
+    );
+    assert_eq!(translate_format("{{%x}}").as_deref(), Some("{{{:x}}}"));
+    assert_eq!(translate_format("%2$s %1$s").as_deref(), Some("{1} {0}"));
+    assert_eq!(translate_format("ok: %d, bad: %*d"), None);
+    assert_eq!(translate_format("%y"), None);
 }
@@ -52,6 +52,7 @@
 pub mod asm;
 pub mod cmdline_attrs;
 pub mod contracts;
+pub mod dynexport;
 pub mod proc_macro_harness;
 pub mod script_harness;
 pub mod standard_library_imports;
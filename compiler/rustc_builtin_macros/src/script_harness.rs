@@ -7,11 +7,14 @@
 use rustc_ast as ast;
 use rustc_ast::attr::contains_name;
 use rustc_ast::entry::EntryPointType;
+use rustc_ast::tokenstream::{TokenStream, TokenTree};
+use rustc_ast::visit::Visitor;
+use rustc_data_structures::fx::FxHashSet;
 use rustc_expand::base::ResolverExpand;
 use rustc_feature::Features;
 use rustc_session::Session;
 use rustc_span::hygiene::AstPass;
-use rustc_span::{DUMMY_SP, Ident, Span, sym};
+use rustc_span::{DUMMY_SP, Ident, Span, Symbol, sym};
 use thin_vec::{ThinVec, thin_vec};
 
 use rustc_parse::transformer;
@@ -28,11 +31,13 @@ pub fn inject(
         return;
     }
 
-    // Set up expansion context for proper hygiene (like standard_library_imports does)
+    // Set up expansion context for proper hygiene (like standard_library_imports does).
+    // The injected extensions implement `Contains` for their containers and mark `MapField`
+    // as the lang item typeck falls back to for `map.key`.
     let expn_id = resolver.expansion_for_ast_pass(
         DUMMY_SP,
         AstPass::ScriptMain,
-        &[],
+        &[sym::in_operator, sym::lang_items],
         None,
     );
     let def_site = DUMMY_SP.with_def_site_ctxt(expn_id.to_expn_id());
@@ -63,7 +68,17 @@ fn has_entry_point(krate: &ast::Crate) -> bool {
 }
 
 /// Inject script mode helpers and optionally generate main function.
+///
+/// Every helper goes into `mod __script_helpers`, where desugared script syntax reaches it by
+/// path. The prelude is what the crate root gets on top: the helpers themselves for macros,
+/// type aliases and imports, and a re-export for everything else. Helpers whose name the
+/// script already defines at its root are left out of the prelude, so a script's own `put!` or
+/// `exit` wins over the built-in one. `#![no_script_prelude]` and
+/// `#![script_prelude(only(put, eq))]` narrow the prelude down further.
 fn inject_helpers(krate: &mut ast::Crate, sess: &Session, def_site: Span, call_site: Span, has_main: bool) {
+    let prelude = take_prelude_attrs(krate, sess);
+    let user_names = RootNames::collect(&krate.items);
+
     // Build items with proper hygiene contexts:
     // - def_site: for internal macro implementation (invisible to user)
     // - call_site: for macro names (visible to user code)
@@ -80,12 +95,15 @@ fn inject_helpers(krate: &mut ast::Crate, sess: &Session, def_site: Span, call_s
     // Partition items and optionally build main
     let (module_items, main_stmts) = partition_items(&krate.items);
 
-    // Rebuild crate: use statements and helpers first, then module items
-    krate.items = use_statements;
-    krate.items.extend(type_aliases);
-    krate.items.extend(script_macros);
-    // Extensions parsed from library code (replaces AST-generated helpers)
-    krate.items.extend(parsed_extensions);
+    // Rebuild crate: helpers and the prelude first, then module items
+    let mut root_helpers = use_statements;
+    root_helpers.extend(type_aliases);
+    root_helpers.extend(script_macros.iter().cloned());
+    let (root_helpers, reexports) =
+        select_helpers(root_helpers, &parsed_extensions, &prelude, &user_names, sess);
+    krate.items = thin_vec![build_helpers_module(call_site, &script_macros, parsed_extensions)];
+    krate.items.extend(root_helpers);
+    krate.items.push(build_prelude_use(call_site, &reexports));
     krate.items.extend(module_items);
 
     // Only generate main if file doesn't have one and there's content to wrap
@@ -96,6 +114,362 @@ fn inject_helpers(krate: &mut ast::Crate, sess: &Session, def_site: Span, call_s
     }
 }
 
+/// Which helpers the crate attributes ask for.
+enum ScriptPrelude {
+    /// All of them, the default.
+    All,
+    /// `#![no_script_prelude]`: none but the required ones; script syntax reaches the rest
+    /// through `__script_helpers`.
+    Nothing,
+    /// `#![script_prelude(only(put, eq))]`: the named helpers and whatever they use.
+    Only(Vec<Ident>),
+}
+
+/// Helpers the prelude keeps whatever the crate attributes say, unless the script defines the
+/// name itself: `errors.New` is spelled like Go's package rather than desugared to a path.
+const REQUIRED_HELPERS: [Symbol; 1] = [sym::errors];
+
+/// Traits whose methods script syntax desugars to (`if x`, `x == nil`), imported anonymously
+/// at the root whatever the prelude and the script's own names.
+const DESUGARED_TRAITS: [Symbol; 2] = [sym::Truthy, sym::IsNil];
+
+/// Read and remove `#![no_script_prelude]` and `#![script_prelude(..)]`, which nothing after
+/// this pass knows about.
+fn take_prelude_attrs(krate: &mut ast::Crate, sess: &Session) -> ScriptPrelude {
+    let mut prelude = ScriptPrelude::All;
+    for attr in &krate.attrs {
+        if attr.has_name(sym::no_script_prelude) {
+            prelude = ScriptPrelude::Nothing;
+        } else if attr.has_name(sym::script_prelude) {
+            match prelude_only_list(attr) {
+                Some(names) => prelude = ScriptPrelude::Only(names),
+                None => {
+                    let msg = "expected `#![script_prelude(only(name, ...))]`";
+                    sess.dcx().struct_span_err(attr.span, msg).emit();
+                }
+            }
+        }
+    }
+    krate.attrs.retain(|attr| {
+        !attr.has_name(sym::no_script_prelude) && !attr.has_name(sym::script_prelude)
+    });
+    prelude
+}
+
+/// The names in `script_prelude(only(put, eq))`.
+fn prelude_only_list(attr: &ast::Attribute) -> Option<Vec<Ident>> {
+    let list = attr.meta_item_list()?;
+    let [only] = &list[..] else { return None };
+    if !only.has_name(sym::only) {
+        return None;
+    }
+    only.meta_item_list()?.iter().map(|name| name.ident()).collect()
+}
+
+/// Names the script defines or imports at its root, per namespace.
+#[derive(Default)]
+struct RootNames {
+    macros: FxHashSet<Symbol>,
+    items: FxHashSet<Symbol>,
+}
+
+impl RootNames {
+    fn collect(items: &[Box<ast::Item>]) -> RootNames {
+        let mut names = RootNames::default();
+        for item in items {
+            match &item.kind {
+                ast::ItemKind::MacroDef(ident, _) => {
+                    names.macros.insert(ident.name);
+                }
+                // An import can bring in a macro as well as an item.
+                ast::ItemKind::Use(tree) => {
+                    let mut imported = Vec::new();
+                    use_tree_names(tree, &mut imported);
+                    names.macros.extend(imported.iter().copied());
+                    names.items.extend(imported);
+                }
+                kind => names.items.extend(kind.ident().map(|ident| ident.name)),
+            }
+        }
+        names
+    }
+
+    /// Whether injecting `helper` would clash with (or silently lose to) a root item.
+    fn clash_with(&self, helper: &ast::Item) -> bool {
+        match &helper.kind {
+            ast::ItemKind::MacroDef(ident, _) => self.macros.contains(&ident.name),
+            kind => defined_names(kind).iter().any(|name| self.items.contains(name)),
+        }
+    }
+}
+
+/// The names `use` brings into scope: `use a::{b, c as d}` defines `b` and `d`.
+fn use_tree_names(tree: &ast::UseTree, names: &mut Vec<Symbol>) {
+    match &tree.kind {
+        ast::UseTreeKind::Simple(_) => names.push(tree.ident().name),
+        ast::UseTreeKind::Nested { items, .. } => {
+            for (tree, _) in items {
+                use_tree_names(tree, names);
+            }
+        }
+        ast::UseTreeKind::Glob => {}
+    }
+}
+
+/// The names an item defines; none for impls.
+fn defined_names(kind: &ast::ItemKind) -> Vec<Symbol> {
+    match kind {
+        ast::ItemKind::Use(tree) => {
+            let mut names = Vec::new();
+            use_tree_names(tree, &mut names);
+            names
+        }
+        kind => kind.ident().map(|ident| ident.name).into_iter().collect(),
+    }
+}
+
+/// Every identifier mentioned in an AST fragment, over-approximating what it refers to.
+#[derive(Default)]
+struct MentionedNames(FxHashSet<Symbol>);
+
+impl MentionedNames {
+    fn visit_tokens(&mut self, tokens: &TokenStream) {
+        for tree in tokens.iter() {
+            match tree {
+                TokenTree::Token(token, _) => {
+                    if let Some((ident, _)) = token.ident() {
+                        self.0.insert(ident.name);
+                    }
+                }
+                TokenTree::Delimited(.., tokens) => self.visit_tokens(tokens),
+            }
+        }
+    }
+}
+
+impl<'ast> Visitor<'ast> for MentionedNames {
+    fn visit_ident(&mut self, ident: &'ast Ident) {
+        self.0.insert(ident.name);
+    }
+
+    // Macro bodies and arguments are plain tokens, which the default walk skips.
+    fn visit_mac_call(&mut self, mac: &'ast ast::MacCall) {
+        self.visit_path(&mac.path);
+        self.visit_tokens(&mac.args.tokens);
+    }
+
+    fn visit_macro_def(&mut self, def: &'ast ast::MacroDef) {
+        self.visit_tokens(&def.body.tokens);
+    }
+}
+
+/// Choose the prelude: the root helpers to inject and the names to re-export from
+/// `__script_helpers`. Helpers that clash with the script's root items or that the prelude
+/// attributes leave out are dropped, but those still used by a kept root helper stay, since
+/// macro expansions look names up where the macro is called.
+fn select_helpers(
+    root_helpers: ThinVec<Box<ast::Item>>,
+    extensions: &[Box<ast::Item>],
+    prelude: &ScriptPrelude,
+    user_names: &RootNames,
+    sess: &Session,
+) -> (ThinVec<Box<ast::Item>>, Vec<Symbol>) {
+    // Impls and the extensions' own imports have nothing to re-export.
+    let extensions: Vec<&ast::Item> = extensions
+        .iter()
+        .map(|item| &**item)
+        .filter(|item| {
+            !matches!(
+                item.kind,
+                ast::ItemKind::Impl(_) | ast::ItemKind::Use(_) | ast::ItemKind::ExternCrate(..)
+            )
+        })
+        .collect();
+    let helpers: Vec<&ast::Item> =
+        root_helpers.iter().map(|item| &**item).chain(extensions.iter().copied()).collect();
+    let defined: Vec<_> = helpers.iter().map(|item| defined_names(&item.kind)).collect();
+    let helper_names: FxHashSet<Symbol> = defined.iter().flatten().copied().collect();
+
+    if let ScriptPrelude::Only(names) = prelude {
+        for name in names.iter().filter(|name| !helper_names.contains(&name.name)) {
+            let msg = format!("`{name}` is not a script prelude helper");
+            sess.dcx().struct_span_err(name.span, msg).emit();
+        }
+    }
+
+    let clashes: Vec<bool> = helpers.iter().map(|item| user_names.clash_with(item)).collect();
+    let mut keep: Vec<bool> = helpers
+        .iter()
+        .zip(&defined)
+        .zip(&clashes)
+        .map(|((item, names), &clash)| {
+            if clash {
+                return false;
+            }
+            if names.iter().any(|name| REQUIRED_HELPERS.contains(name)) {
+                return true;
+            }
+            match (&item.kind, prelude) {
+                (_, ScriptPrelude::Nothing) => false,
+                (ast::ItemKind::Use(_), _) | (_, ScriptPrelude::All) => true,
+                (_, ScriptPrelude::Only(only)) => {
+                    only.iter().any(|name| names.contains(&name.name))
+                }
+            }
+        })
+        .collect();
+
+    // Only root helpers pass their needs on: re-exported extensions find what they use in
+    // `__script_helpers`.
+    let mentioned: Vec<_> = root_helpers
+        .iter()
+        .map(|item| {
+            let mut mentioned = MentionedNames::default();
+            mentioned.visit_item(item);
+            mentioned.0
+        })
+        .collect();
+
+    // Pull in what the kept root helpers use until nothing changes.
+    let mut changed = true;
+    while changed {
+        changed = false;
+        let used_names: FxHashSet<Symbol> = mentioned
+            .iter()
+            .zip(&keep)
+            .filter(|(_, kept)| **kept)
+            .flat_map(|(uses, _)| uses.iter().copied())
+            .collect();
+        for (i, kept) in keep.iter_mut().enumerate() {
+            if *kept || clashes[i] {
+                continue;
+            }
+            *kept = defined[i].iter().any(|name| used_names.contains(name));
+            changed |= *kept;
+        }
+    }
+
+    let reexports = defined[root_helpers.len()..]
+        .iter()
+        .zip(&keep[root_helpers.len()..])
+        .filter(|(_, kept)| **kept)
+        .flat_map(|(names, _)| names.iter().copied())
+        .collect();
+    let root_helpers = root_helpers
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(item, kept)| kept.then_some(item))
+        .collect();
+    (root_helpers, reexports)
+}
+
+/// `mod __script_helpers`, holding every helper whatever the prelude. Its macros are
+/// re-exported with `use` so that desugarings can name them, as in
+/// `crate::__script_helpers::__if!(..)`.
+fn build_helpers_module(
+    span: Span,
+    script_macros: &[Box<ast::Item>],
+    extensions: ThinVec<Box<ast::Item>>,
+) -> Box<ast::Item> {
+    let mut items = ThinVec::new();
+    for item in script_macros {
+        if let ast::ItemKind::MacroDef(ident, _) = item.kind {
+            items.push(item.clone());
+            let tree = ast::UseTree {
+                prefix: ast::Path::from_ident(ident),
+                kind: ast::UseTreeKind::Simple(None),
+                span,
+            };
+            items.push(build_use(span, crate_visibility(span), tree));
+        }
+    }
+    for mut item in extensions {
+        // Desugarings and the prelude reach into the module, so nothing can stay private to it.
+        let private = matches!(item.vis.kind, ast::VisibilityKind::Inherited);
+        if private
+            && !matches!(
+                item.kind,
+                ast::ItemKind::Impl(_)
+                    | ast::ItemKind::Use(_)
+                    | ast::ItemKind::ExternCrate(..)
+                    | ast::ItemKind::MacCall(_)
+            )
+        {
+            item.vis = crate_visibility(span);
+        }
+        items.push(item);
+    }
+
+    let spans = ast::ModSpans { inner_span: span, inject_use_span: span };
+    Box::new(ast::Item {
+        attrs: thin_vec![
+            create_allow_attr(span, sym::dead_code),
+            create_allow_attr(span, sym::unused_imports),
+        ],
+        id: ast::DUMMY_NODE_ID,
+        kind: ast::ItemKind::Mod(
+            ast::Safety::Default,
+            Ident::new(sym::__script_helpers, span),
+            ast::ModKind::Loaded(items, ast::Inline::Yes, spans),
+        ),
+        vis: ast::Visibility { span, kind: ast::VisibilityKind::Inherited, tokens: None },
+        span,
+        tokens: None,
+    })
+}
+
+/// `use __script_helpers::{Val, .., Truthy as _, IsNil as _};`, the prelude's re-exports.
+fn build_prelude_use(span: Span, reexports: &[Symbol]) -> Box<ast::Item> {
+    let simple = |name: Symbol, rename: Option<Ident>| {
+        let tree = ast::UseTree {
+            prefix: ast::Path::from_ident(Ident::new(name, span)),
+            kind: ast::UseTreeKind::Simple(rename),
+            span,
+        };
+        (tree, ast::DUMMY_NODE_ID)
+    };
+    let underscore = Ident::new(rustc_span::kw::Underscore, span);
+    let items = reexports
+        .iter()
+        .map(|&name| simple(name, None))
+        .chain(DESUGARED_TRAITS.iter().map(|&name| simple(name, Some(underscore))))
+        .collect();
+    let tree = ast::UseTree {
+        prefix: ast::Path::from_ident(Ident::new(sym::__script_helpers, span)),
+        kind: ast::UseTreeKind::Nested { items, span },
+        span,
+    };
+    let vis = ast::Visibility { span, kind: ast::VisibilityKind::Inherited, tokens: None };
+    let mut item = build_use(span, vis, tree);
+    item.attrs.push(create_allow_attr(span, sym::unused_imports));
+    item
+}
+
+/// `pub(crate)`.
+fn crate_visibility(span: Span) -> ast::Visibility {
+    let path = ast::Path::from_ident(Ident::new(rustc_span::kw::Crate, span));
+    ast::Visibility {
+        span,
+        kind: ast::VisibilityKind::Restricted {
+            path: Box::new(path),
+            id: ast::DUMMY_NODE_ID,
+            shorthand: true,
+        },
+        tokens: None,
+    }
+}
+
+fn build_use(span: Span, vis: ast::Visibility, tree: ast::UseTree) -> Box<ast::Item> {
+    Box::new(ast::Item {
+        attrs: ThinVec::new(),
+        id: ast::DUMMY_NODE_ID,
+        kind: ast::ItemKind::Use(tree),
+        vis,
+        span,
+        tokens: None,
+    })
+}
+
 /// Build use statements for script mode: use std::collections::HashMap;
 fn build_use_statements(span: Span) -> ThinVec<Box<ast::Item>> {
     let mut items = ThinVec::new();
@@ -46,6 +46,36 @@ pub(crate) fn codegen_static(tcx: TyCtxt<'_>, module: &mut dyn Module, def_id: D
     )
 }
 
+/// Define the `dynexport_meta_<name>` record and `dynexport_wit_<name>` description of a
+/// `#[dynexport]` item, with the same sections and layout as the LLVM backend.
+pub(crate) fn define_dynexport_metadata(
+    tcx: TyCtxt<'_>,
+    module: &mut dyn Module,
+    meta: &rustc_codegen_ssa::dynexport::DynexportMetadata<'_>,
+) {
+    use rustc_codegen_ssa::dynexport::{interface_section, meta_record, meta_section};
+
+    let target = &tcx.sess.target;
+    let record = meta_record(tcx, meta.type_hash, meta.flags).to_vec();
+    let mut description = meta.interface.clone().into_bytes();
+    description.push(0);
+    let globals = [
+        ("meta", record, 8, meta_section(target)),
+        ("wit", description, 1, interface_section(target)),
+    ];
+    for (kind, bytes, align, section) in globals {
+        let name = format!("dynexport_{kind}_{}", meta.symbol_name);
+        let data_id = module.declare_data(&name, Linkage::Export, false, false).unwrap();
+        let mut data = DataDescription::new();
+        data.set_align(align);
+        data.set_used(true);
+        let (segment, section) = section.split_once(',').unwrap_or(("", section));
+        data.set_segment_section(segment, section);
+        data.define(bytes.into_boxed_slice());
+        module.define_data(data_id, &data).unwrap();
+    }
+}
+
 pub(crate) fn codegen_tls_ref<'tcx>(
     fx: &mut FunctionCx<'_, '_, 'tcx>,
     def_id: DefId,
@@ -224,7 +224,12 @@ fn produce_final_output_artifacts(
                 user_wants_objects = true;
                 copy_if_one_unit(OutputType::Object, true);
             }
-            OutputType::Mir | OutputType::Metadata | OutputType::Exe | OutputType::DepInfo => {}
+            OutputType::Mir
+            | OutputType::Metadata
+            | OutputType::Exe
+            | OutputType::DepInfo
+            | OutputType::DynexportHeader
+            | OutputType::DynexportPy => {}
         }
     }
 
@@ -522,6 +527,9 @@ fn codegen_cgu_content(
     super::predefine_mono_items(tcx, module, &mono_items);
     let mut codegened_functions = vec![];
     for (mono_item, item_data) in mono_items {
+        if let Some(meta) = rustc_codegen_ssa::dynexport::dynexport_metadata(tcx, mono_item) {
+            crate::constant::define_dynexport_metadata(tcx, module, &meta);
+        }
         match mono_item {
             MonoItem::Fn(instance) => {
                 let flags = tcx.codegen_instance_attrs(instance.def).flags;
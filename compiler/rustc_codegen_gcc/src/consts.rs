@@ -2,6 +2,7 @@
 use gccjit::{FnAttribute, VarAttribute, Visibility};
 use gccjit::{Function, GlobalKind, LValue, RValue, ToRValue, Type};
 use rustc_abi::{self as abi, Align, HasDataLayout, Primitive, Size, WrappingRange};
+use rustc_codegen_ssa::dynexport;
 use rustc_codegen_ssa::traits::{
     BaseTypeCodegenMethods, ConstCodegenMethods, StaticCodegenMethods,
 };
@@ -16,6 +17,7 @@
 use rustc_middle::ty::layout::LayoutOf;
 use rustc_middle::ty::{self, Instance};
 use rustc_middle::{bug, span_bug};
+use rustc_span::Symbol;
 use rustc_span::def_id::DefId;
 
 use crate::base;
@@ -153,6 +155,41 @@ fn codegen_static(&mut self, def_id: DefId) {
             self.add_used_global(global.to_rvalue());
         }
     }
+
+    fn emit_dynexport_metadata(
+        &mut self,
+        symbol_name: &str,
+        type_hash: u64,
+        flags: u32,
+        interface: &str,
+    ) {
+        // Same symbols, sections and layout as the LLVM backend, so loaders can't tell the
+        // backends apart; see `rustc_abi::dynexport`.
+        let target = &self.tcx.sess.target;
+        let record = dynexport::meta_record(self.tcx, type_hash, flags);
+        let mut description = interface.as_bytes().to_vec();
+        description.push(0);
+        let globals = [
+            ("meta", &record[..], Align::EIGHT, dynexport::meta_section(target)),
+            ("wit", &description[..], Align::ONE, dynexport::interface_section(target)),
+        ];
+        for (kind, bytes, align, section) in globals {
+            let value = self.const_bytes(bytes);
+            let name = format!("dynexport_{kind}_{symbol_name}");
+            let global = self.declare_global(
+                &name,
+                value.get_type(),
+                GlobalKind::Exported,
+                false,
+                Some(Symbol::intern(section)),
+            );
+            set_global_alignment(self, global, align);
+            global.global_set_initializer_rvalue(value);
+            #[cfg(feature = "master")]
+            global.global_set_readonly();
+            self.add_used_global(global.to_rvalue());
+        }
+    }
 }
 
 impl<'gcc, 'tcx> CodegenCx<'gcc, 'tcx> {
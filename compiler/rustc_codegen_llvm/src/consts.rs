@@ -1,5 +1,6 @@
 use std::ops::Range;
 
+use rustc_abi::dynexport::dynexport_compiler_hash;
 use rustc_abi::{Align, HasDataLayout, Primitive, Scalar, Size, WrappingRange};
 use rustc_codegen_ssa::common;
 use rustc_codegen_ssa::traits::*;
@@ -783,22 +784,21 @@ fn codegen_static(&mut self, def_id: DefId) {
         self.codegen_static_item(def_id)
     }
 
-    fn emit_dynexport_metadata(&mut self, symbol_name: &str, type_hash: u64) {
-        // Create metadata struct: { u64 type_hash, u32 compiler_version, u32 flags }
-
-        // Compiler version - use a simple hash of the version string
-        let version_str = env!("CFG_VERSION");
-        let compiler_hash: u32 = {
-            use std::hash::{Hash, Hasher};
-            let mut hasher = std::collections::hash_map::DefaultHasher::new();
-            version_str.hash(&mut hasher);
-            hasher.finish() as u32
-        };
+    fn emit_dynexport_metadata(
+        &mut self,
+        symbol_name: &str,
+        type_hash: u64,
+        flags: u32,
+        interface: &str,
+    ) {
+        // Create metadata struct: { u64 type_hash, u32 compiler_version, u32 flags }, see
+        // `rustc_abi::dynexport` for the flags
+        let compiler_hash = dynexport_compiler_hash(env!("CFG_VERSION"));
 
         // Build the metadata struct
         let type_hash_val = self.const_u64(type_hash);
         let compiler_hash_val = self.const_u32(compiler_hash);
-        let flags_val = self.const_u32(0); // Reserved for future use
+        let flags_val = self.const_u32(flags);
 
         let metadata_struct = self.const_struct(&[type_hash_val, compiler_hash_val, flags_val], false);
 
@@ -825,5 +825,26 @@ fn emit_dynexport_metadata(&mut self, symbol_name: &str, type_hash: u64) {
             // Prevent linker from stripping this symbol (use llvm.used, not llvm.compiler.used)
             self.add_used_global(g);
         }
+
+        // The WIT-style interface description, NUL-terminated, in its own section so readers
+        // can walk all descriptions of a library without knowing the symbol names
+        let mut bytes = interface.as_bytes().to_vec();
+        bytes.push(0);
+        let interface_val = self.const_bytes(&bytes);
+        let wit_name = format!("dynexport_wit_{}", symbol_name);
+        if let Some(g) = self.define_global(&wit_name, self.val_ty(interface_val)) {
+            llvm::set_initializer(g, interface_val);
+            llvm::set_global_constant(g, true);
+            llvm::set_linkage(g, llvm::Linkage::ExternalLinkage);
+            llvm::set_visibility(g, llvm::Visibility::Default);
+            set_global_alignment(self, g, Align::ONE);
+            let section_name = if self.tcx.sess.target.is_like_darwin {
+                c"__DATA,__dynexport_wit"
+            } else {
+                c".rustc_dynexport_wit"
+            };
+            llvm::set_section(g, section_name);
+            self.add_used_global(g);
+        }
     }
 }
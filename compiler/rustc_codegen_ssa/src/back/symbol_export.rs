@@ -707,7 +707,7 @@ pub(crate) fn exporting_symbol_name_for_instance_in_crate<'tcx>(
 /// On amdhsa, `gpu-kernel` functions have an associated metadata object with a `.kd` suffix.
 /// Add it to the symbols list for all kernel functions, so that it is exported in the linked
 /// object.
-/// Also adds dynexport metadata symbols for items marked with #[dynexport].
+/// Also adds dynexport metadata and interface symbols for items marked with #[dynexport].
 pub(crate) fn extend_exported_symbols<'tcx>(
     symbols: &mut Vec<(String, SymbolExportKind)>,
     tcx: TyCtxt<'tcx>,
@@ -729,6 +729,7 @@ pub(crate) fn extend_exported_symbols<'tcx>(
             let undecorated = symbol_name_for_instance_in_crate(tcx, symbol, instantiating_crate);
             // Add the dynexport metadata symbol (must match the name generated in codegen)
             symbols.push((format!("dynexport_meta_{undecorated}"), SymbolExportKind::Data));
+            symbols.push((format!("dynexport_wit_{undecorated}"), SymbolExportKind::Data));
         }
     }
 
@@ -147,7 +147,7 @@ fn process_builtin_attrs(
                     &mut codegen_fn_attrs.target_features,
                 );
             }
-            AttributeKind::DynExport(attr_span) => {
+            AttributeKind::DynExport { span: attr_span, .. } => {
                 // #[dynexport] marks items for stable dynamic export
                 // Sets NO_MANGLE for stable symbol names and DYNEXPORT for metadata emission
                 if tcx.opt_item_name(did.to_def_id()).is_some() {
@@ -0,0 +1,495 @@
+//! Type hashes and interface descriptions for `#[dynexport]` metadata.
+//!
+//! This walks `rustc_middle` types to produce the canonical encoding described in
+//! [`rustc_abi::dynexport`]; the hash itself is computed there so that loaders which never see a
+//! `Ty` can produce the same value from a signature they spell out themselves. The WIT-style
+//! descriptions and the flags stored next to the hashes are computed here as well.
+
+use std::fmt::Write as _;
+
+use rustc_abi::dynexport::{
+    dynexport_compiler_hash, dynexport_flags, dynexport_hash, encode_fn_sig,
+};
+use rustc_abi::{Endian, IntegerType};
+use rustc_hir::attrs::AttributeKind;
+use rustc_hir::def_id::DefId;
+use rustc_hir::find_attr;
+use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
+use rustc_middle::mir::mono::MonoItem;
+use rustc_middle::ty::layout::fn_can_unwind;
+use rustc_middle::ty::{self, AdtDef, Instance, Ty, TyCtxt};
+use rustc_span::{kw, sym};
+use rustc_target::spec::Target;
+
+pub mod bindings;
+
+/// What a backend emits for a `#[dynexport]` item: the `dynexport_meta_<symbol_name>` record
+/// and the `dynexport_wit_<symbol_name>` description.
+pub struct DynexportMetadata<'tcx> {
+    pub symbol_name: &'tcx str,
+    pub type_hash: u64,
+    pub flags: u32,
+    pub interface: String,
+}
+
+/// The metadata to emit for `item`, or `None` if it isn't `#[dynexport]`.
+pub fn dynexport_metadata<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    item: MonoItem<'tcx>,
+) -> Option<DynexportMetadata<'tcx>> {
+    match item {
+        MonoItem::Fn(instance) => {
+            let attrs = tcx.codegen_instance_attrs(instance.def);
+            if !attrs.flags.contains(CodegenFnAttrFlags::DYNEXPORT) {
+                return None;
+            }
+            let symbol_name = item.symbol_name(tcx).name;
+            Some(DynexportMetadata {
+                symbol_name,
+                type_hash: compute_fn_type_hash(tcx, instance),
+                flags: fn_flags(tcx, instance),
+                interface: fn_interface(tcx, instance, symbol_name),
+            })
+        }
+        MonoItem::Static(def_id) => {
+            if !tcx.codegen_fn_attrs(def_id).flags.contains(CodegenFnAttrFlags::DYNEXPORT) {
+                return None;
+            }
+            let symbol_name = item.symbol_name(tcx).name;
+            Some(DynexportMetadata {
+                symbol_name,
+                type_hash: compute_static_type_hash(tcx, def_id),
+                flags: static_flags(tcx, def_id),
+                interface: static_interface(tcx, def_id, symbol_name),
+            })
+        }
+        MonoItem::GlobalAsm(_) => None,
+    }
+}
+
+/// The bytes of a `dynexport_meta_<name>` record, `{ u64 type_hash, u32 compiler_hash,
+/// u32 flags }` in the target's byte order, for backends that emit it as raw data.
+pub fn meta_record(tcx: TyCtxt<'_>, type_hash: u64, flags: u32) -> [u8; 16] {
+    let compiler_hash = dynexport_compiler_hash(env!("CFG_VERSION"));
+    let mut record = [0; 16];
+    if tcx.data_layout.endian == Endian::Big {
+        record[..8].copy_from_slice(&type_hash.to_be_bytes());
+        record[8..12].copy_from_slice(&compiler_hash.to_be_bytes());
+        record[12..].copy_from_slice(&flags.to_be_bytes());
+    } else {
+        record[..8].copy_from_slice(&type_hash.to_le_bytes());
+        record[8..12].copy_from_slice(&compiler_hash.to_le_bytes());
+        record[12..].copy_from_slice(&flags.to_le_bytes());
+    }
+    record
+}
+
+/// Section of the `dynexport_meta_<name>` records, `segment,section` on Darwin.
+pub fn meta_section(target: &Target) -> &'static str {
+    if target.is_like_darwin { "__DATA,__dynexport" } else { ".rustc_dynexport" }
+}
+
+/// Section of the `dynexport_wit_<name>` descriptions, `segment,section` on Darwin.
+pub fn interface_section(target: &Target) -> &'static str {
+    if target.is_like_darwin { "__DATA,__dynexport_wit" } else { ".rustc_dynexport_wit" }
+}
+
+/// Hash of the monomorphic signature of a `#[dynexport]` function.
+pub fn compute_fn_type_hash<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> u64 {
+    dynexport_hash(&encode_fn_type(tcx, instance))
+}
+
+/// Hash of the type of a `#[dynexport]` static.
+pub fn compute_static_type_hash(tcx: TyCtxt<'_>, def_id: DefId) -> u64 {
+    dynexport_hash(&encode_ty(tcx, tcx.type_of(def_id).instantiate_identity()))
+}
+
+/// Flags word of the record of a `#[dynexport]` function.
+pub fn fn_flags<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> u32 {
+    let def_id = instance.def_id();
+    let abi = tcx.fn_sig(def_id).skip_binder().abi();
+    let nounwind = !fn_can_unwind(tcx, Some(def_id), abi);
+    dynexport_flags(false, nounwind, has_c_shim(tcx, def_id), item_version(tcx, def_id))
+}
+
+/// Flags word of the record of a `#[dynexport]` static.
+pub fn static_flags(tcx: TyCtxt<'_>, def_id: DefId) -> u32 {
+    dynexport_flags(true, false, false, item_version(tcx, def_id))
+}
+
+/// The `N` of `#[dynexport(version = N)]`, 0 without one.
+fn item_version(tcx: TyCtxt<'_>, def_id: DefId) -> u16 {
+    find_attr!(tcx.get_all_attrs(def_id), AttributeKind::DynExport { version, .. } => *version)
+        .flatten()
+        .unwrap_or(0)
+}
+
+/// Whether `rustc_builtin_macros::dynexport` put a `dynexport_c_<name>` shim next to the
+/// function, which it marks with `#[rustc_dynexport_c_shim]` when it does.
+fn has_c_shim(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    tcx.has_attr(def_id, sym::rustc_dynexport_c_shim)
+}
+
+/// Canonical encoding of the monomorphic signature of `instance`.
+pub fn encode_fn_type<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> String {
+    let sig = tcx.fn_sig(instance.def_id()).instantiate(tcx, instance.args);
+    let sig = tcx.normalize_erasing_late_bound_regions(ty::TypingEnv::fully_monomorphized(), sig);
+    encode_sig(tcx, sig, &[])
+}
+
+/// Canonical encoding of a single type.
+pub fn encode_ty<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> String {
+    encode_with_stack(tcx, ty, &[])
+}
+
+fn encode_with_stack<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>, stack: &[DefId]) -> String {
+    let mut out = String::new();
+    TypeEncoder { tcx, stack: stack.to_vec(), out: &mut out }.ty(ty);
+    out
+}
+
+fn encode_sig<'tcx>(tcx: TyCtxt<'tcx>, sig: ty::FnSig<'tcx>, stack: &[DefId]) -> String {
+    let inputs: Vec<String> =
+        sig.inputs().iter().map(|&ty| encode_with_stack(tcx, ty, stack)).collect();
+    let output = encode_with_stack(tcx, sig.output(), stack);
+    encode_fn_sig(sig.abi, inputs.iter().map(String::as_str), sig.c_variadic, &output)
+}
+
+struct TypeEncoder<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    /// ADTs currently being expanded, to cut recursion off at the type's name.
+    stack: Vec<DefId>,
+    out: &'a mut String,
+}
+
+impl<'tcx> TypeEncoder<'_, 'tcx> {
+    fn ty(&mut self, ty: Ty<'tcx>) {
+        match *ty.kind() {
+            ty::Bool => self.out.push_str("bool"),
+            ty::Char => self.out.push_str("char"),
+            ty::Str => self.out.push_str("str"),
+            ty::Never => self.out.push('!'),
+            ty::Int(int) => self.out.push_str(int.name_str()),
+            ty::Uint(uint) => self.out.push_str(uint.name_str()),
+            ty::Float(float) => self.out.push_str(float.name_str()),
+            ty::Ref(_, pointee, mutbl) => {
+                self.out.push_str(if mutbl.is_mut() { "&mut " } else { "&" });
+                self.ty(pointee);
+            }
+            ty::RawPtr(pointee, mutbl) => {
+                self.out.push_str(if mutbl.is_mut() { "*mut " } else { "*const " });
+                self.ty(pointee);
+            }
+            ty::Slice(elem) => {
+                self.out.push('[');
+                self.ty(elem);
+                self.out.push(']');
+            }
+            ty::Array(elem, len) => {
+                self.out.push('[');
+                self.ty(elem);
+                match len.try_to_target_usize(self.tcx) {
+                    Some(len) => write!(self.out, ";{len}]").unwrap(),
+                    None => self.out.push_str(";?]"),
+                }
+            }
+            ty::Tuple(tys) => {
+                self.out.push('(');
+                for ty in tys {
+                    self.ty(ty);
+                    self.out.push(',');
+                }
+                self.out.push(')');
+            }
+            ty::Adt(def, args) => self.adt(def, args),
+            ty::FnPtr(sig_tys, hdr) => {
+                let sig = self.tcx.instantiate_bound_regions_with_erased(sig_tys.with(hdr));
+                let encoded = encode_sig(self.tcx, sig, &self.stack);
+                self.out.push_str(&encoded);
+            }
+            ty::Dynamic(preds, ..) => {
+                self.out.push_str("dyn ");
+                match preds.principal_def_id() {
+                    Some(def_id) => self.out.push_str(self.tcx.item_name(def_id).as_str()),
+                    None => self.out.push('_'),
+                }
+            }
+            // Nothing a loader could spell out; keep the kind so the encoding stays total.
+            _ => write!(self.out, "?{}", kind_name(ty)).unwrap(),
+        }
+    }
+
+    fn adt(&mut self, def: AdtDef<'tcx>, args: ty::GenericArgsRef<'tcx>) {
+        let tcx = self.tcx;
+        let did = def.did();
+        let name = tcx.item_name(did);
+
+        if matches!(tcx.crate_name(did.krate), sym::std | sym::core | sym::alloc) {
+            self.out.push_str(name.as_str());
+            // `Vec<T>`, not `Vec<T, Global>`: a loader only knows what it can spell out.
+            let generics = tcx.generics_of(did);
+            let mut types = generics
+                .own_params
+                .iter()
+                .filter(|param| {
+                    matches!(param.kind, ty::GenericParamDefKind::Type { has_default: false, .. })
+                })
+                .map(|param| args.type_at(param.index as usize))
+                .peekable();
+            if types.peek().is_some() {
+                self.out.push('<');
+                for ty in types {
+                    self.ty(ty);
+                    self.out.push(',');
+                }
+                self.out.push('>');
+            }
+            return;
+        }
+
+        if self.stack.contains(&did) {
+            self.out.push_str(name.as_str());
+            return;
+        }
+        self.stack.push(did);
+
+        self.repr(def);
+        let keyword = if def.is_enum() {
+            "enum"
+        } else if def.is_union() {
+            "union"
+        } else {
+            "struct"
+        };
+        write!(self.out, "{keyword} {name}").unwrap();
+        if def.is_enum() {
+            self.out.push('{');
+            for (idx, discr) in def.discriminants(tcx) {
+                let variant = def.variant(idx);
+                write!(self.out, "{}={discr}", variant.name).unwrap();
+                self.fields(variant, args);
+                self.out.push(',');
+            }
+            self.out.push('}');
+        } else {
+            self.fields(def.non_enum_variant(), args);
+        }
+
+        self.stack.pop();
+    }
+
+    fn fields(&mut self, variant: &ty::VariantDef, args: ty::GenericArgsRef<'tcx>) {
+        self.out.push('{');
+        for field in &variant.fields {
+            write!(self.out, "{}:", field.name).unwrap();
+            self.ty(field.ty(self.tcx, args));
+            self.out.push(',');
+        }
+        self.out.push('}');
+    }
+
+    fn repr(&mut self, def: AdtDef<'tcx>) {
+        let repr = def.repr();
+        let mut parts = Vec::new();
+        if repr.c() {
+            parts.push("C".to_string());
+        }
+        if repr.transparent() {
+            parts.push("transparent".to_string());
+        }
+        if let Some(pack) = repr.pack {
+            parts.push(format!("packed({})", pack.bytes()));
+        }
+        if let Some(align) = repr.align {
+            parts.push(format!("align({})", align.bytes()));
+        }
+        if let Some(int) = repr.int {
+            parts.push(match int {
+                IntegerType::Pointer(true) => "isize".to_string(),
+                IntegerType::Pointer(false) => "usize".to_string(),
+                IntegerType::Fixed(int, signed) => {
+                    format!("{}{}", if signed { "i" } else { "u" }, int.size().bits())
+                }
+            });
+        }
+        if !parts.is_empty() {
+            write!(self.out, "#[repr({})]", parts.join(",")).unwrap();
+        }
+    }
+}
+
+/// WIT-style description of a `#[dynexport]` function exported as `name`.
+pub fn fn_interface<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>, name: &str) -> String {
+    let sig = tcx.fn_sig(instance.def_id()).instantiate(tcx, instance.args);
+    let sig = tcx.normalize_erasing_late_bound_regions(ty::TypingEnv::fully_monomorphized(), sig);
+    let idents = tcx.fn_arg_idents(instance.def_id());
+    let mut writer = InterfaceWriter { tcx, defined: Vec::new(), defs: String::new() };
+    let params: Vec<String> = sig
+        .inputs()
+        .iter()
+        .enumerate()
+        .map(|(i, &ty)| {
+            let param = match idents.get(i).copied().flatten() {
+                Some(ident) if ident.name != kw::Underscore => ident.to_string(),
+                _ => format!("arg{i}"),
+            };
+            format!("{param}: {}", writer.ty(ty))
+        })
+        .collect();
+    let output = sig.output();
+    let result =
+        if output.is_unit() { String::new() } else { format!(" -> {}", writer.ty(output)) };
+    format!("{}{name}: func({}){result};", writer.defs, params.join(", "))
+}
+
+/// WIT-style description of a `#[dynexport]` static exported as `name`.
+pub fn static_interface(tcx: TyCtxt<'_>, def_id: DefId, name: &str) -> String {
+    let mut writer = InterfaceWriter { tcx, defined: Vec::new(), defs: String::new() };
+    let ty = writer.ty(tcx.type_of(def_id).instantiate_identity());
+    format!("{}{name}: static {ty};", writer.defs)
+}
+
+/// Writes WIT type names, collecting definitions of the records, variants and enums used.
+struct InterfaceWriter<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    /// Types that already have a definition, or are being defined.
+    defined: Vec<DefId>,
+    /// One definition per line, dependencies first.
+    defs: String,
+}
+
+impl<'tcx> InterfaceWriter<'tcx> {
+    fn ty(&mut self, ty: Ty<'tcx>) -> String {
+        let bits = self.tcx.data_layout.pointer_size().bits();
+        match *ty.kind() {
+            ty::Bool => "bool".to_string(),
+            ty::Char => "char".to_string(),
+            ty::Str => "string".to_string(),
+            ty::Int(int) => format!("s{}", int.bit_width().unwrap_or(bits)),
+            ty::Uint(uint) => format!("u{}", uint.bit_width().unwrap_or(bits)),
+            ty::Float(float) => float.name_str().to_string(),
+            ty::Ref(_, pointee, _) => self.ty(pointee),
+            ty::RawPtr(pointee, _) => format!("ptr<{}>", self.ty(pointee)),
+            ty::Slice(elem) | ty::Array(elem, _) => format!("list<{}>", self.ty(elem)),
+            ty::Tuple(tys) => {
+                let tys: Vec<String> = tys.iter().map(|ty| self.ty(ty)).collect();
+                format!("tuple<{}>", tys.join(", "))
+            }
+            ty::Adt(def, args) => self.adt(def, args),
+            ty::FnPtr(..) => "func".to_string(),
+            ty::Never => "never".to_string(),
+            _ => format!("unknown<{}>", kind_name(ty)),
+        }
+    }
+
+    /// `result<T, E>` spells a unit side as `_`.
+    fn ty_or_blank(&mut self, ty: Ty<'tcx>) -> String {
+        if ty.is_unit() { "_".to_string() } else { self.ty(ty) }
+    }
+
+    fn adt(&mut self, def: AdtDef<'tcx>, args: ty::GenericArgsRef<'tcx>) -> String {
+        let tcx = self.tcx;
+        let did = def.did();
+        let name = tcx.item_name(did);
+
+        if matches!(tcx.crate_name(did.krate), sym::std | sym::core | sym::alloc) {
+            let mut arg = |i: usize| args.types().nth(i).map(|ty| self.ty(ty)).unwrap_or_default();
+            return match name.as_str() {
+                "String" | "OsString" | "PathBuf" | "CString" => "string".to_string(),
+                "Vec" | "VecDeque" | "LinkedList" | "BinaryHeap" | "HashSet" | "BTreeSet" => {
+                    format!("list<{}>", arg(0))
+                }
+                "HashMap" | "BTreeMap" => format!("list<tuple<{}, {}>>", arg(0), arg(1)),
+                "Option" => format!("option<{}>", arg(0)),
+                "Box" | "Rc" | "Arc" | "Cow" => arg(0),
+                "Result" => {
+                    let (ok, err) = (args.type_at(0), args.type_at(1));
+                    format!("result<{}, {}>", self.ty_or_blank(ok), self.ty_or_blank(err))
+                }
+                _ => kebab(name.as_str()),
+            };
+        }
+
+        let wit_name = kebab(name.as_str());
+        if self.defined.contains(&did) {
+            return wit_name;
+        }
+        self.defined.push(did);
+
+        let def_line = if def.is_enum() {
+            if def.variants().iter().all(|variant| variant.fields.is_empty()) {
+                let cases: Vec<String> =
+                    def.variants().iter().map(|variant| kebab(variant.name.as_str())).collect();
+                format!("enum {wit_name} {{ {} }}", cases.join(", "))
+            } else {
+                let cases: Vec<String> = def
+                    .variants()
+                    .iter()
+                    .map(|variant| {
+                        let case = kebab(variant.name.as_str());
+                        let tys: Vec<String> = variant
+                            .fields
+                            .iter()
+                            .map(|field| self.ty(field.ty(tcx, args)))
+                            .collect();
+                        match &tys[..] {
+                            [] => case,
+                            [ty] => format!("{case}({ty})"),
+                            tys => format!("{case}(tuple<{}>)", tys.join(", ")),
+                        }
+                    })
+                    .collect();
+                format!("variant {wit_name} {{ {} }}", cases.join(", "))
+            }
+        } else {
+            let fields: Vec<String> = def
+                .non_enum_variant()
+                .fields
+                .iter()
+                .enumerate()
+                .map(|(i, field)| {
+                    let field_name = match field.name.as_str() {
+                        name if name.starts_with(|c: char| c.is_ascii_digit()) => format!("f{i}"),
+                        name => kebab(name),
+                    };
+                    format!("{field_name}: {}", self.ty(field.ty(tcx, args)))
+                })
+                .collect();
+            format!("record {wit_name} {{ {} }}", fields.join(", "))
+        };
+        self.defs.push_str(&def_line);
+        self.defs.push('\n');
+        wit_name
+    }
+}
+
+/// `MyPoint` and `my_point` both become `my-point`.
+fn kebab(name: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in name.char_indices() {
+        if c == '_' {
+            out.push('-');
+        } else if c.is_uppercase() {
+            let prev = name[..i].chars().next_back();
+            if prev.is_some_and(|prev| prev.is_lowercase() || prev.is_ascii_digit()) {
+                out.push('-');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn kind_name(ty: Ty<'_>) -> &'static str {
+    match ty.kind() {
+        ty::Foreign(..) => "extern",
+        ty::FnDef(..) => "fndef",
+        ty::Closure(..) | ty::CoroutineClosure(..) => "closure",
+        ty::Coroutine(..) | ty::CoroutineWitness(..) => "coroutine",
+        ty::Pat(..) => "pat",
+        ty::UnsafeBinder(..) => "unsafe",
+        _ => "ty",
+    }
+}
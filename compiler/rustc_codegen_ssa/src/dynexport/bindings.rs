@@ -0,0 +1,941 @@
+//! `--emit=dynexport-header` and `--emit=dynexport-py`: C and Python bindings for the
+//! `#[dynexport]` items of the crate being compiled.
+//!
+//! Both outputs describe the same items. A function with the C ABI is declared as itself and a
+//! Rust-ABI function through its `dynexport_c_<name>` shim, whose types follow the
+//! representation documented in compiler/extensions/src/ffi.rs (`FfiStr`, `FfiString`,
+//! `FfiVec*`, `FfiOption*` and mirrors of `#[dynexport]` structs and enums). Statics of a C
+//! type are declared as themselves. An item without a C representation is still listed with
+//! its type hash, so a loader can tell whether the library changed under it.
+//!
+//! The header defines `<CRATE>_<ITEM>_TYPE_HASH` for every item and a `<crate>_dynexport_check()`
+//! comparing them with the `dynexport_meta_<name>` records of the library it is linked with.
+//! The Python module makes the same comparison when `Library(path)` loads the library and
+//! raises `DynexportError` instead of binding functions whose signature changed.
+
+use std::fmt::Write as _;
+
+use rustc_abi::dynexport::{DYNEXPORT_VERSION_SHIFT, dynexport_compiler_hash, dynexport_version};
+use rustc_abi::{ExternAbi, IntegerType};
+use rustc_hir::attrs::AttributeKind;
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::{DefId, LOCAL_CRATE};
+use rustc_hir::find_attr;
+use rustc_middle::bug;
+use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
+use rustc_middle::ty::{self, AdtDef, Instance, Ty, TyCtxt};
+use rustc_session::config::{OutFileName, OutputType};
+use rustc_span::{kw, sym};
+
+use super::{
+    compute_fn_type_hash, compute_static_type_hash, fn_flags, has_c_shim, kebab, static_flags,
+};
+
+/// Write the bindings for `--emit=dynexport-header` or `--emit=dynexport-py`.
+pub fn emit(tcx: TyCtxt<'_>, output_type: OutputType) {
+    let bindings = Bindings::collect(tcx);
+    let crate_name = tcx.crate_name(LOCAL_CRATE);
+    let source = match output_type {
+        OutputType::DynexportHeader => bindings.header(crate_name.as_str()),
+        OutputType::DynexportPy => {
+            let target = &tcx.sess.target;
+            let library = format!("{}{crate_name}{}", target.dll_prefix, target.dll_suffix);
+            bindings.python(crate_name.as_str(), &library)
+        }
+        _ => bug!("`{}` is not a dynexport binding", output_type.shorthand()),
+    };
+    let out = tcx.output_filenames(()).path(output_type);
+    out.overwrite(&source, tcx.sess);
+    if let OutFileName::Real(path) = &out
+        && tcx.sess.opts.json_artifact_notifications
+    {
+        tcx.dcx().emit_artifact_notification(path, output_type.shorthand());
+    }
+}
+
+/// A type as both outputs spell it.
+#[derive(Clone, PartialEq)]
+enum CType {
+    /// Only as a return type.
+    Void,
+    Bool,
+    Int {
+        bits: u64,
+        signed: bool,
+    },
+    /// `size_t` or `intptr_t`, for `usize` and `isize`.
+    Size {
+        signed: bool,
+    },
+    Float {
+        bits: u64,
+    },
+    Ptr {
+        pointee: Box<CType>,
+        mutable: bool,
+    },
+    /// A struct or tagged union of the bindings.
+    Named(String),
+}
+
+/// A type the bindings define, in dependency order.
+enum TypeDef {
+    /// `ffi` is set for the representation types, which are the same in every crate.
+    Struct { name: String, fields: Vec<(String, CType)>, ffi: Option<Ffi> },
+    /// A tag, then a union with one struct per variant that has fields. Without any such
+    /// variant the enum is the tag alone.
+    Enum { name: String, tag: CType, variants: Vec<Variant> },
+}
+
+/// The representation types of compiler/extensions/src/ffi.rs.
+#[derive(Clone, Copy, PartialEq)]
+enum Ffi {
+    Str,
+    String,
+    Vec,
+    Option,
+}
+
+struct Variant {
+    name: String,
+    discr: String,
+    fields: Vec<(String, CType)>,
+}
+
+struct Item {
+    /// The exported symbol, which also names the constants and the Python attribute.
+    symbol: String,
+    type_hash: u64,
+    flags: u32,
+    kind: ItemKind,
+}
+
+enum ItemKind {
+    /// `callee` is the symbol itself or the `dynexport_c_<name>` shim.
+    Fn {
+        callee: String,
+        params: Vec<(String, CType)>,
+        ret: CType,
+    },
+    Static {
+        ty: CType,
+        mutable: bool,
+    },
+    /// No C representation: only the type hash is bound.
+    Opaque,
+}
+
+/// Which mapping of Rust types applies.
+#[derive(Clone, Copy, PartialEq)]
+enum Repr {
+    /// The types of a C-ABI function or a static: scalars, pointers and `#[repr(C)]` types.
+    C,
+    /// The types of a `dynexport_c_<name>` shim: the `FfiRepr::Repr` of the Rust types.
+    Shim,
+}
+
+struct Bindings<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    defs: Vec<TypeDef>,
+    /// ADTs by the name of their definition, to reuse it and to refuse two types of one name.
+    adts: Vec<(String, DefId, Repr)>,
+    /// ADTs being defined, which can't contain themselves.
+    defining: Vec<DefId>,
+    items: Vec<Item>,
+}
+
+impl<'tcx> Bindings<'tcx> {
+    fn collect(tcx: TyCtxt<'tcx>) -> Self {
+        let mut bindings = Bindings {
+            tcx,
+            defs: Vec::new(),
+            adts: Vec::new(),
+            defining: Vec::new(),
+            items: Vec::new(),
+        };
+        for def_id in tcx.hir_crate_items(()).definitions() {
+            let def_id = def_id.to_def_id();
+            let is_static = match tcx.def_kind(def_id) {
+                DefKind::Fn | DefKind::AssocFn => false,
+                DefKind::Static { nested: false, .. } => true,
+                _ => continue,
+            };
+            if tcx.is_foreign_item(def_id)
+                || !tcx.codegen_fn_attrs(def_id).flags.contains(CodegenFnAttrFlags::DYNEXPORT)
+                || tcx.generics_of(def_id).requires_monomorphization(tcx)
+            {
+                continue;
+            }
+            let instance = Instance::mono(tcx, def_id);
+            let symbol = tcx.symbol_name(instance).name.to_string();
+            let item = if is_static {
+                Item {
+                    symbol,
+                    type_hash: compute_static_type_hash(tcx, def_id),
+                    flags: static_flags(tcx, def_id),
+                    kind: bindings.static_kind(def_id).unwrap_or(ItemKind::Opaque),
+                }
+            } else {
+                let kind = bindings.fn_kind(def_id, &symbol).unwrap_or(ItemKind::Opaque);
+                Item {
+                    symbol,
+                    type_hash: compute_fn_type_hash(tcx, instance),
+                    flags: fn_flags(tcx, instance),
+                    kind,
+                }
+            };
+            bindings.items.push(item);
+        }
+        bindings
+    }
+
+    fn fn_kind(&mut self, def_id: DefId, symbol: &str) -> Option<ItemKind> {
+        let tcx = self.tcx;
+        let sig = tcx.fn_sig(def_id).instantiate_identity();
+        let sig =
+            tcx.normalize_erasing_late_bound_regions(ty::TypingEnv::fully_monomorphized(), sig);
+        let (callee, repr) = match sig.abi {
+            ExternAbi::C { .. } if !sig.c_variadic => (symbol.to_string(), Repr::C),
+            ExternAbi::Rust if has_c_shim(tcx, def_id) => {
+                (format!("dynexport_c_{}", tcx.item_name(def_id)), Repr::Shim)
+            }
+            _ => return None,
+        };
+        let idents = tcx.fn_arg_idents(def_id);
+        let mut params = Vec::new();
+        for (i, &ty) in sig.inputs().iter().enumerate() {
+            let name = match idents.get(i).copied().flatten() {
+                Some(ident) if ident.name != kw::Underscore => c_ident(ident.as_str()),
+                _ => format!("arg{i}"),
+            };
+            params.push((name, self.value_ty(ty, repr)?));
+        }
+        let ret = self.ty(sig.output(), repr)?;
+        Some(ItemKind::Fn { callee, params, ret })
+    }
+
+    fn static_kind(&mut self, def_id: DefId) -> Option<ItemKind> {
+        let ty = self.value_ty(self.tcx.type_of(def_id).instantiate_identity(), Repr::C)?;
+        let mutable = self.tcx.static_mutability(def_id).is_some_and(|mutbl| mutbl.is_mut());
+        Some(ItemKind::Static { ty, mutable })
+    }
+
+    /// Like [`Self::ty`], for where `void` can't appear.
+    fn value_ty(&mut self, ty: Ty<'tcx>, repr: Repr) -> Option<CType> {
+        self.ty(ty, repr).filter(|ty| *ty != CType::Void)
+    }
+
+    fn ty(&mut self, ty: Ty<'tcx>, repr: Repr) -> Option<CType> {
+        Some(match *ty.kind() {
+            ty::Bool => CType::Bool,
+            ty::Char => CType::Int { bits: 32, signed: false },
+            ty::Int(int) => match int.bit_width() {
+                None => CType::Size { signed: true },
+                Some(128) => return None,
+                Some(bits) => CType::Int { bits, signed: true },
+            },
+            ty::Uint(uint) => match uint.bit_width() {
+                None => CType::Size { signed: false },
+                Some(128) => return None,
+                Some(bits) => CType::Int { bits, signed: false },
+            },
+            ty::Float(ty::FloatTy::F32) => CType::Float { bits: 32 },
+            ty::Float(ty::FloatTy::F64) => CType::Float { bits: 64 },
+            ty::Tuple(tys) if tys.is_empty() => CType::Void,
+            ty::Ref(_, pointee, mutbl) if repr == Repr::Shim => {
+                if !pointee.is_str() || mutbl.is_mut() {
+                    return None;
+                }
+                self.ffi_buffer(Ffi::Str, "FfiStr", CType::Int { bits: 8, signed: false })
+            }
+            ty::Ref(_, pointee, mutbl) | ty::RawPtr(pointee, mutbl) if repr == Repr::C => {
+                if matches!(pointee.kind(), ty::Str | ty::Slice(_) | ty::Dynamic(..)) {
+                    return None;
+                }
+                // A pointer to something without a C type is still a pointer.
+                let pointee = self.ty(pointee, repr).unwrap_or(CType::Void);
+                CType::Ptr { pointee: Box::new(pointee), mutable: mutbl.is_mut() }
+            }
+            ty::Adt(def, args) => return self.adt(def, args, repr),
+            _ => return None,
+        })
+    }
+
+    fn adt(
+        &mut self,
+        def: AdtDef<'tcx>,
+        args: ty::GenericArgsRef<'tcx>,
+        repr: Repr,
+    ) -> Option<CType> {
+        let tcx = self.tcx;
+        let did = def.did();
+
+        if matches!(tcx.crate_name(did.krate), sym::std | sym::core | sym::alloc) {
+            if repr != Repr::Shim {
+                return None;
+            }
+            return match tcx.item_name(did).as_str() {
+                "String" => Some(self.ffi_buffer(
+                    Ffi::String,
+                    "FfiString",
+                    CType::Int { bits: 8, signed: false },
+                )),
+                "Vec" => {
+                    let elem = self.value_ty(args.type_at(0), repr)?;
+                    Some(self.ffi_buffer(Ffi::Vec, &format!("FfiVec{}", suffix(&elem)), elem))
+                }
+                "Option" => {
+                    let value = self.value_ty(args.type_at(0), repr)?;
+                    let fields =
+                        vec![("is_some".to_string(), CType::Bool), ("value".to_string(), value)];
+                    Some(self.define(&format!("FfiOption{}", suffix(&fields[1].1)), |name| {
+                        TypeDef::Struct { name, fields, ffi: Some(Ffi::Option) }
+                    }))
+                }
+                _ => None,
+            };
+        }
+
+        let supported = match repr {
+            // The shim takes the `#[repr(C)]` mirror the `#[dynexport]` macro generates.
+            Repr::Shim => {
+                did.is_local()
+                    && find_attr!(tcx.get_all_attrs(did), AttributeKind::DynExport { .. })
+            }
+            Repr::C => def.repr().c() || (def.is_enum() && def.repr().int.is_some()),
+        };
+        if !supported || def.is_union() || !args.is_empty() || self.defining.contains(&did) {
+            return None;
+        }
+        let name = tcx.item_name(did).to_string();
+        if let Some((_, known, known_repr)) = self.adts.iter().find(|(known, ..)| *known == name) {
+            return (*known == did && *known_repr == repr).then(|| CType::Named(name));
+        }
+
+        self.defining.push(did);
+        let def = if def.is_enum() {
+            self.enum_def(def, args, repr, &name)
+        } else {
+            self.struct_def(def, args, repr, &name)
+        };
+        self.defining.pop();
+        self.defs.push(def?);
+        self.adts.push((name.clone(), did, repr));
+        Some(CType::Named(name))
+    }
+
+    fn struct_def(
+        &mut self,
+        def: AdtDef<'tcx>,
+        args: ty::GenericArgsRef<'tcx>,
+        repr: Repr,
+        name: &str,
+    ) -> Option<TypeDef> {
+        let fields = self.fields(def.non_enum_variant(), args, repr)?;
+        // C has no empty structs.
+        if fields.is_empty() {
+            return None;
+        }
+        Some(TypeDef::Struct { name: name.to_string(), fields, ffi: None })
+    }
+
+    fn enum_def(
+        &mut self,
+        def: AdtDef<'tcx>,
+        args: ty::GenericArgsRef<'tcx>,
+        repr: Repr,
+        name: &str,
+    ) -> Option<TypeDef> {
+        let tcx = self.tcx;
+        // The mirror of a `#[dynexport]` enum is `#[repr(C, u32)]`. A `#[repr(C)]` enum
+        // without an integer type has the tag of a C enum, an `int` on the targets we support.
+        let tag = match (repr, def.repr().int) {
+            (Repr::Shim, _) => CType::Int { bits: 32, signed: false },
+            (Repr::C, Some(IntegerType::Fixed(int, signed))) => {
+                CType::Int { bits: int.size().bits(), signed }
+            }
+            (Repr::C, Some(IntegerType::Pointer(signed))) => CType::Size { signed },
+            (Repr::C, None) => CType::Int { bits: 32, signed: true },
+        };
+        let mut variants = Vec::new();
+        for (idx, discr) in def.discriminants(tcx) {
+            let variant = def.variant(idx);
+            let fields = self.fields(variant, args, repr)?;
+            variants.push(Variant {
+                name: variant.name.to_string(),
+                discr: discr.to_string(),
+                fields,
+            });
+        }
+        // Without `repr(C)`, `#[repr(u8)]` and such put the tag inside every variant instead.
+        let has_fields = variants.iter().any(|variant| !variant.fields.is_empty());
+        if variants.is_empty() || (repr == Repr::C && has_fields && !def.repr().c()) {
+            return None;
+        }
+        Some(TypeDef::Enum { name: name.to_string(), tag, variants })
+    }
+
+    /// Fields of a struct or variant; tuple fields are `_0`, `_1`, ...
+    fn fields(
+        &mut self,
+        variant: &ty::VariantDef,
+        args: ty::GenericArgsRef<'tcx>,
+        repr: Repr,
+    ) -> Option<Vec<(String, CType)>> {
+        variant
+            .fields
+            .iter()
+            .map(|field| {
+                let name = field.name.as_str();
+                let name = if name.starts_with(|c: char| c.is_ascii_digit()) {
+                    format!("_{name}")
+                } else {
+                    c_ident(name)
+                };
+                Some((name, self.value_ty(field.ty(self.tcx, args), repr)?))
+            })
+            .collect()
+    }
+
+    /// `FfiStr`, `FfiString` and `FfiVec*`: a pointer and a length. Only `FfiStr` is borrowed.
+    fn ffi_buffer(&mut self, ffi: Ffi, name: &str, pointee: CType) -> CType {
+        let ptr = CType::Ptr { pointee: Box::new(pointee), mutable: ffi != Ffi::Str };
+        let fields =
+            vec![("ptr".to_string(), ptr), ("len".to_string(), CType::Size { signed: false })];
+        self.define(name, |name| TypeDef::Struct { name, fields, ffi: Some(ffi) })
+    }
+
+    /// `CType::Named(name)`, defining it first unless it already is.
+    fn define(&mut self, name: &str, def: impl FnOnce(String) -> TypeDef) -> CType {
+        if !self.defs.iter().any(|known| known.name() == name) {
+            self.defs.push(def(name.to_string()));
+        }
+        CType::Named(name.to_string())
+    }
+}
+
+impl TypeDef {
+    fn name(&self) -> &str {
+        match self {
+            TypeDef::Struct { name, .. } | TypeDef::Enum { name, .. } => name,
+        }
+    }
+}
+
+/// The part of `FfiVec<T>` and `FfiOption<T>` names standing for `T`: `FfiVecString`,
+/// `FfiOptionUsize`.
+fn suffix(ty: &CType) -> String {
+    match ty {
+        CType::Void => "Unit".to_string(),
+        CType::Bool => "Bool".to_string(),
+        CType::Int { bits, signed } => format!("{}{bits}", if *signed { 'I' } else { 'U' }),
+        CType::Size { signed } => if *signed { "Isize" } else { "Usize" }.to_string(),
+        CType::Float { bits } => format!("F{bits}"),
+        CType::Ptr { pointee, .. } => format!("Ptr{}", suffix(pointee)),
+        CType::Named(name) => name.strip_prefix("Ffi").unwrap_or(name).to_string(),
+    }
+}
+
+/// `MyShape` becomes `my_shape`.
+fn snake(name: &str) -> String {
+    kebab(name).replace('-', "_")
+}
+
+const C_KEYWORDS: &[&str] = &[
+    "auto", "bool", "case", "char", "const", "default", "do", "double", "float", "goto", "inline",
+    "int", "long", "register", "restrict", "short", "signed", "sizeof", "switch", "typedef",
+    "union", "unsigned", "void", "volatile",
+];
+
+/// A Rust identifier usable as a C one.
+fn c_ident(name: &str) -> String {
+    if C_KEYWORDS.contains(&name) { format!("{name}_") } else { name.to_string() }
+}
+
+/// The C spelling of `ty`, as in a cast.
+fn c_type(ty: &CType) -> String {
+    match ty {
+        CType::Void => "void".to_string(),
+        CType::Bool => "bool".to_string(),
+        CType::Int { bits, signed } => format!("{}int{bits}_t", if *signed { "" } else { "u" }),
+        CType::Size { signed } => if *signed { "intptr_t" } else { "size_t" }.to_string(),
+        CType::Float { bits } => if *bits == 32 { "float" } else { "double" }.to_string(),
+        CType::Ptr { pointee, mutable } => match **pointee {
+            // `uint8_t *const *`: the `const` goes right of the pointer it applies to.
+            CType::Ptr { .. } => {
+                format!("{}{}*", c_type(pointee), if *mutable { "" } else { "const " })
+            }
+            _ => format!("{}{} *", if *mutable { "" } else { "const " }, c_type(pointee)),
+        },
+        CType::Named(name) => name.clone(),
+    }
+}
+
+/// `ty name` in C, `const uint8_t *ptr` for pointers.
+fn c_decl(ty: &CType, name: &str) -> String {
+    let ty = c_type(ty);
+    if ty.ends_with('*') { format!("{ty}{name}") } else { format!("{ty} {name}") }
+}
+
+const PYTHON_KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+/// A Rust identifier usable as a Python attribute.
+fn py_ident(name: &str) -> String {
+    if PYTHON_KEYWORDS.contains(&name) { format!("{name}_") } else { name.to_string() }
+}
+
+/// The ctypes spelling of `ty`.
+fn py_type(ty: &CType) -> String {
+    match ty {
+        CType::Void => "None".to_string(),
+        CType::Bool => "ctypes.c_bool".to_string(),
+        CType::Int { bits, signed } => {
+            format!("ctypes.c_{}int{bits}", if *signed { "" } else { "u" })
+        }
+        CType::Size { signed } => {
+            if *signed { "ctypes.c_ssize_t" } else { "ctypes.c_size_t" }.to_string()
+        }
+        CType::Float { bits } => {
+            if *bits == 32 { "ctypes.c_float" } else { "ctypes.c_double" }.to_string()
+        }
+        CType::Ptr { pointee, .. } if **pointee == CType::Void => "ctypes.c_void_p".to_string(),
+        CType::Ptr { pointee, .. } => format!("ctypes.POINTER({})", py_type(pointee)),
+        CType::Named(name) => name.clone(),
+    }
+}
+
+impl Bindings<'_> {
+    fn header(&self, crate_name: &str) -> String {
+        let upper = crate_name.to_uppercase();
+        let mut out = String::new();
+        write!(
+            out,
+            "/* C declarations of the `#[dynexport]` items of `{crate_name}`,\n \
+             * generated by rustc {}.\n \
+             *\n \
+             * Functions with the Rust ABI are declared as their `dynexport_c_<name>` shims,\n \
+             * which pass strings, vectors, options and `#[dynexport]` types as the structs\n \
+             * below. FfiString and FfiVec* values belong to whoever receives them and are\n \
+             * released with free(), elements first. Call {crate_name}_dynexport_check() once\n \
+             * the library is loaded: it names the first item whose type changed since this\n \
+             * header was generated.\n \
+             */\n\n",
+            env!("CFG_VERSION"),
+        )
+        .unwrap();
+        writeln!(out, "#ifndef {upper}_DYNEXPORT_H\n#define {upper}_DYNEXPORT_H\n").unwrap();
+        out.push_str("#include <stdbool.h>\n#include <stddef.h>\n#include <stdint.h>\n\n");
+        out.push_str("#ifdef __cplusplus\nextern \"C\" {\n#endif\n\n");
+        out.push_str(
+            "#ifndef DYNEXPORT_META_DEFINED\n\
+             #define DYNEXPORT_META_DEFINED\n\
+             /* The `dynexport_meta_<name>` record of every item. */\n\
+             typedef struct dynexport_meta {\n    \
+             uint64_t type_hash;\n    \
+             uint32_t compiler_hash;\n    \
+             uint32_t flags;\n\
+             } dynexport_meta;\n\
+             #endif\n\n",
+        );
+        writeln!(
+            out,
+            "#define {upper}_COMPILER_HASH {:#010x}u\n",
+            dynexport_compiler_hash(env!("CFG_VERSION"))
+        )
+        .unwrap();
+
+        for def in &self.defs {
+            // The representation types are the same in every header, and headers of several
+            // libraries may be included together.
+            let shared = matches!(def, TypeDef::Struct { ffi: Some(_), .. });
+            if shared {
+                writeln!(out, "#ifndef DYNEXPORT_TYPE_{0}\n#define DYNEXPORT_TYPE_{0}", def.name())
+                    .unwrap();
+            }
+            match def {
+                TypeDef::Struct { name, fields, .. } => {
+                    writeln!(out, "typedef struct {name} {{").unwrap();
+                    for (field, ty) in fields {
+                        writeln!(out, "    {};", c_decl(ty, field)).unwrap();
+                    }
+                    writeln!(out, "}} {name};").unwrap();
+                }
+                TypeDef::Enum { name, tag, variants } => {
+                    let prefix = snake(name).to_uppercase();
+                    out.push_str("enum {\n");
+                    for variant in variants {
+                        let variant_name = snake(&variant.name).to_uppercase();
+                        writeln!(out, "    {prefix}_{variant_name} = {},", variant.discr).unwrap();
+                    }
+                    out.push_str("};\n");
+                    if variants.iter().all(|variant| variant.fields.is_empty()) {
+                        writeln!(out, "typedef {} {name};", c_type(tag)).unwrap();
+                    } else {
+                        writeln!(
+                            out,
+                            "typedef struct {name} {{\n    {};\n    union {{",
+                            c_decl(tag, "tag")
+                        )
+                        .unwrap();
+                        for variant in variants.iter().filter(|variant| !variant.fields.is_empty())
+                        {
+                            out.push_str("        struct {\n");
+                            for (field, ty) in &variant.fields {
+                                writeln!(out, "            {};", c_decl(ty, field)).unwrap();
+                            }
+                            writeln!(out, "        }} {};", c_ident(&snake(&variant.name)))
+                                .unwrap();
+                        }
+                        writeln!(out, "    }};\n}} {name};").unwrap();
+                    }
+                }
+            }
+            if shared {
+                out.push_str("#endif\n");
+            }
+            out.push('\n');
+        }
+
+        for item in &self.items {
+            let symbol = &item.symbol;
+            let macro_name = format!("{upper}_{}", symbol.to_uppercase());
+            writeln!(out, "#define {macro_name}_TYPE_HASH {:#018x}ull", item.type_hash).unwrap();
+            writeln!(out, "#define {macro_name}_VERSION {}u", dynexport_version(item.flags))
+                .unwrap();
+            writeln!(out, "extern const dynexport_meta dynexport_meta_{symbol};").unwrap();
+            match &item.kind {
+                ItemKind::Fn { callee, params, ret } => {
+                    let params: Vec<String> =
+                        params.iter().map(|(name, ty)| c_decl(ty, name)).collect();
+                    let params =
+                        if params.is_empty() { "void".to_string() } else { params.join(", ") };
+                    writeln!(out, "{}({params});", c_decl(ret, callee)).unwrap();
+                }
+                ItemKind::Static { ty, mutable } => {
+                    let qualifier = if *mutable { "" } else { "const " };
+                    writeln!(out, "extern {qualifier}{};", c_decl(ty, symbol)).unwrap();
+                }
+                ItemKind::Opaque => {
+                    writeln!(out, "/* `{symbol}` has no C representation. */").unwrap();
+                }
+            }
+            out.push('\n');
+        }
+
+        out.push_str(
+            "/* The name of the first item whose `dynexport_meta_<name>` record doesn't match\n \
+             * this header, or NULL if the library is the one it describes or a compatible one.\n \
+             */\n",
+        );
+        writeln!(out, "static inline const char *{crate_name}_dynexport_check(void) {{").unwrap();
+        for item in &self.items {
+            let symbol = &item.symbol;
+            let macro_name = format!("{upper}_{}", symbol.to_uppercase());
+            write!(out, "    if (dynexport_meta_{symbol}.type_hash != {macro_name}_TYPE_HASH")
+                .unwrap();
+            // Versions only go up, so a library with a later one still matches.
+            if dynexport_version(item.flags) > 0 {
+                let version = format!("dynexport_meta_{symbol}.flags >> {DYNEXPORT_VERSION_SHIFT}");
+                write!(out, "\n        || ({version}) < {macro_name}_VERSION").unwrap();
+            }
+            writeln!(out, ")\n        return \"{symbol}\";").unwrap();
+        }
+        out.push_str("    return NULL;\n}\n\n");
+        out.push_str("#ifdef __cplusplus\n}\n#endif\n\n");
+        writeln!(out, "#endif /* {upper}_DYNEXPORT_H */").unwrap();
+        out
+    }
+
+    fn python(&self, crate_name: &str, library: &str) -> String {
+        let mut out = String::new();
+        write!(
+            out,
+            "\"\"\"ctypes bindings for the `#[dynexport]` items of `{crate_name}`.\n\
+             \n\
+             Generated by rustc {}. Use them as\n\
+             \n    \
+             import {crate_name}\n    \
+             lib = {crate_name}.Library()  # or Library(\"path/to/{library}\")\n\
+             \n\
+             `Library` compares the type hash of every item with the library's\n\
+             `dynexport_meta_<name>` record and raises `DynexportError` if the library was built\n\
+             from other signatures. Functions with the Rust ABI are bound through their\n\
+             `dynexport_c_<name>` shims: `str` and `list` arguments are converted, and the\n\
+             owned `FfiString` and `FfiVec*` results are read and released with `take()`.\n\
+             \"\"\"\n\n",
+            env!("CFG_VERSION"),
+        )
+        .unwrap();
+        out.push_str(PYTHON_PRELUDE);
+        writeln!(out, "COMPILER_HASH = {:#010x}\n", dynexport_compiler_hash(env!("CFG_VERSION")))
+            .unwrap();
+
+        for def in &self.defs {
+            match def {
+                TypeDef::Struct { name, fields, ffi } => {
+                    writeln!(out, "\nclass {name}(ctypes.Structure):").unwrap();
+                    write_py_fields(&mut out, "    ", fields);
+                    let methods: &[(&str, &str)] = match ffi {
+                        None => &[],
+                        Some(Ffi::Str) => &[
+                            ("of", "classmethod(_ffi_str_of)"),
+                            ("from_param", "classmethod(_from_str_param)"),
+                        ],
+                        Some(Ffi::String) => &[
+                            ("of", "classmethod(_ffi_string_of)"),
+                            ("from_param", "classmethod(_from_str_param)"),
+                            ("take", "_take_string"),
+                        ],
+                        Some(Ffi::Vec) => &[
+                            ("of", "classmethod(_vec_of)"),
+                            ("from_param", "classmethod(_from_list_param)"),
+                            ("take", "_take_vec"),
+                        ],
+                        Some(Ffi::Option) => &[
+                            ("of", "classmethod(_option_of)"),
+                            ("from_param", "classmethod(_option_from_param)"),
+                            ("get", "_option_get"),
+                        ],
+                    };
+                    for (method, value) in methods {
+                        writeln!(out, "    {method} = {value}").unwrap();
+                    }
+                    out.push('\n');
+                }
+                TypeDef::Enum { name, tag, variants } => {
+                    let prefix = snake(name).to_uppercase();
+                    out.push('\n');
+                    for variant in variants {
+                        let variant_name = snake(&variant.name).to_uppercase();
+                        writeln!(out, "{prefix}_{variant_name} = {}", variant.discr).unwrap();
+                    }
+                    let with_fields: Vec<&Variant> =
+                        variants.iter().filter(|variant| !variant.fields.is_empty()).collect();
+                    if with_fields.is_empty() {
+                        writeln!(out, "{name} = {}\n", py_type(tag)).unwrap();
+                        continue;
+                    }
+                    for variant in &with_fields {
+                        writeln!(out, "\n\nclass _{name}_{}(ctypes.Structure):", variant.name)
+                            .unwrap();
+                        write_py_fields(&mut out, "    ", &variant.fields);
+                    }
+                    writeln!(out, "\n\nclass _{name}_Variants(ctypes.Union):").unwrap();
+                    let members: Vec<(String, CType)> = with_fields
+                        .iter()
+                        .map(|variant| {
+                            let member = c_ident(&snake(&variant.name));
+                            (member, CType::Named(format!("_{name}_{}", variant.name)))
+                        })
+                        .collect();
+                    write_py_fields(&mut out, "    ", &members);
+                    writeln!(out, "\n\nclass {name}(ctypes.Structure):").unwrap();
+                    out.push_str("    _anonymous_ = (\"_variants\",)\n");
+                    let fields = [
+                        ("tag".to_string(), tag.clone()),
+                        ("_variants".to_string(), CType::Named(format!("_{name}_Variants"))),
+                    ];
+                    write_py_fields(&mut out, "    ", &fields);
+                    for variant in &with_fields {
+                        writeln!(out, "    {0} = _{name}_{0}", variant.name).unwrap();
+                    }
+                    out.push('\n');
+                }
+            }
+        }
+
+        out.push_str("\n# Type hash and minimum version of every item, by symbol.\nITEMS = {\n");
+        for item in &self.items {
+            writeln!(
+                out,
+                "    \"{}\": ({:#018x}, {}),",
+                item.symbol,
+                item.type_hash,
+                dynexport_version(item.flags)
+            )
+            .unwrap();
+        }
+        out.push_str("}\n\n\n");
+
+        writeln!(
+            out,
+            "class Library:\n    \
+             \"\"\"The `#[dynexport]` items of a `{crate_name}` library.\"\"\"\n\n    \
+             def __init__(self, path=None, same_compiler=False):\n        \
+             if path is None:\n            \
+             here = os.path.dirname(os.path.abspath(__file__))\n            \
+             path = os.path.join(here, \"{library}\")\n        \
+             self._lib = ctypes.CDLL(path)\n        \
+             _check(self._lib, path, ITEMS, same_compiler)",
+        )
+        .unwrap();
+        for item in &self.items {
+            let attr = py_ident(&item.symbol);
+            match &item.kind {
+                ItemKind::Fn { callee, params, ret } => {
+                    let params: Vec<String> = params.iter().map(|(_, ty)| py_type(ty)).collect();
+                    writeln!(
+                        out,
+                        "        self.{attr} = _bind(self._lib, \"{callee}\", [{}], {})",
+                        params.join(", "),
+                        py_type(ret)
+                    )
+                    .unwrap();
+                }
+                ItemKind::Static { ty, .. } => {
+                    writeln!(
+                        out,
+                        "        self.{attr} = {}.in_dll(self._lib, \"{}\")",
+                        py_type(ty),
+                        item.symbol
+                    )
+                    .unwrap();
+                }
+                ItemKind::Opaque => {
+                    writeln!(out, "        # `{}` has no C representation.", item.symbol).unwrap();
+                }
+            }
+        }
+        out
+    }
+}
+
+fn write_py_fields(out: &mut String, indent: &str, fields: &[(String, CType)]) {
+    writeln!(out, "{indent}_fields_ = [").unwrap();
+    for (name, ty) in fields {
+        writeln!(out, "{indent}    (\"{name}\", {}),", py_type(ty)).unwrap();
+    }
+    writeln!(out, "{indent}]").unwrap();
+}
+
+/// The helpers every Python module starts with; the generated classes refer to them.
+const PYTHON_PRELUDE: &str = r#"import ctypes
+import ctypes.util
+import os
+
+_libc = ctypes.CDLL(ctypes.util.find_library("c"))
+_libc.malloc.argtypes = [ctypes.c_size_t]
+_libc.malloc.restype = ctypes.c_void_p
+_libc.free.argtypes = [ctypes.c_void_p]
+_libc.free.restype = None
+
+
+class DynexportError(Exception):
+    """The library doesn't match these bindings."""
+
+
+class DynexportMeta(ctypes.Structure):
+    """The `dynexport_meta_<name>` record of an item."""
+
+    _fields_ = [
+        ("type_hash", ctypes.c_uint64),
+        ("compiler_hash", ctypes.c_uint32),
+        ("flags", ctypes.c_uint32),
+    ]
+
+
+def _check(lib, path, items, same_compiler):
+    for symbol, (type_hash, version) in items.items():
+        try:
+            meta = DynexportMeta.in_dll(lib, "dynexport_meta_" + symbol)
+        except ValueError:
+            raise DynexportError(f"{path} has no `#[dynexport]` item `{symbol}`") from None
+        if meta.type_hash != type_hash:
+            raise DynexportError(
+                f"`{symbol}` in {path} has type hash {meta.type_hash:#018x}, "
+                f"these bindings were generated for {type_hash:#018x}"
+            )
+        if meta.flags >> 16 < version:
+            raise DynexportError(
+                f"`{symbol}` in {path} is version {meta.flags >> 16}, "
+                f"these bindings need version {version} or later"
+            )
+        if same_compiler and meta.compiler_hash != COMPILER_HASH:
+            raise DynexportError(f"{path} was built by another compiler than these bindings")
+
+
+def _bind(lib, symbol, argtypes, restype):
+    function = getattr(lib, symbol)
+    function.argtypes = argtypes
+    function.restype = restype
+    return function
+
+
+def _copy(value):
+    # Elements read through a pointer share its memory, which `take()` releases.
+    if isinstance(value, (ctypes.Structure, ctypes.Union)):
+        return type(value).from_buffer_copy(value)
+    return value
+
+
+def _convert(ty, value):
+    if isinstance(ty, type) and issubclass(ty, ctypes.Structure) and not isinstance(value, ty):
+        return ty.from_param(value)
+    return value
+
+
+def _ffi_str_of(cls, text):
+    # Borrowed: the struct keeps the encoded bytes alive.
+    data = text.encode("utf-8")
+    return cls(ctypes.cast(ctypes.c_char_p(data), ctypes.POINTER(ctypes.c_uint8)), len(data))
+
+
+def _ffi_string_of(cls, text):
+    # Owned: the receiver frees the copy.
+    data = text.encode("utf-8")
+    ptr = _libc.malloc(len(data) + 1)
+    ctypes.memmove(ptr, data + b"\0", len(data) + 1)
+    return cls(ctypes.cast(ptr, ctypes.POINTER(ctypes.c_uint8)), len(data))
+
+
+def _from_str_param(cls, value):
+    return cls.of(value) if isinstance(value, str) else value
+
+
+def _take_string(string):
+    text = ctypes.string_at(string.ptr, string.len).decode("utf-8") if string.ptr else ""
+    _libc.free(ctypes.cast(string.ptr, ctypes.c_void_p))
+    string.ptr = None
+    return text
+
+
+def _vec_of(cls, items):
+    ty = cls._fields_[0][1]._type_
+    ptr = _libc.malloc(max(len(items), 1) * ctypes.sizeof(ty))
+    vec = cls(ctypes.cast(ptr, ctypes.POINTER(ty)), len(items))
+    for i, item in enumerate(items):
+        vec.ptr[i] = _convert(ty, item)
+    return vec
+
+
+def _from_list_param(cls, value):
+    return cls.of(value) if isinstance(value, (list, tuple)) else value
+
+
+def _take_vec(vec):
+    # The elements are copied out, what they own is theirs to release.
+    items = [_copy(vec.ptr[i]) for i in range(vec.len)] if vec.ptr else []
+    _libc.free(ctypes.cast(vec.ptr, ctypes.c_void_p))
+    vec.ptr = None
+    return items
+
+
+def _option_of(cls, value):
+    if value is None:
+        return cls(False)
+    return cls(True, _convert(cls._fields_[1][1], value))
+
+
+def _option_from_param(cls, value):
+    return value if isinstance(value, cls) else cls.of(value)
+
+
+def _option_get(option):
+    return _copy(option.value) if option.is_some else None
+
+"#;
@@ -47,6 +47,7 @@
 pub mod codegen_attrs;
 pub mod common;
 pub mod debuginfo;
+pub mod dynexport;
 pub mod errors;
 pub mod meth;
 pub mod mir;
@@ -5,9 +5,9 @@
 use tracing::debug;
 
 use crate::base;
+use crate::dynexport::dynexport_metadata;
 use crate::mir::naked_asm;
 use crate::traits::*;
-use crate::traits::statics::compute_fn_type_hash;
 
 pub trait MonoItemExt<'a, 'tcx> {
     fn define<Bx: BuilderMethods<'a, 'tcx>>(
@@ -40,11 +40,13 @@ fn define<Bx: BuilderMethods<'a, 'tcx>>(
                 cx.codegen_static(def_id);
 
                 // Emit dynexport metadata if this static has the DYNEXPORT flag
-                let attrs = cx.tcx().codegen_fn_attrs(def_id);
-                if attrs.flags.contains(CodegenFnAttrFlags::DYNEXPORT) {
-                    let symbol_name = self.symbol_name(cx.tcx()).name;
-                    // For statics, use 0 as type hash (could be improved to hash the type)
-                    cx.emit_dynexport_metadata(symbol_name, 0);
+                if let Some(meta) = dynexport_metadata(cx.tcx(), *self) {
+                    cx.emit_dynexport_metadata(
+                        meta.symbol_name,
+                        meta.type_hash,
+                        meta.flags,
+                        &meta.interface,
+                    );
                 }
             }
             MonoItem::GlobalAsm(item_id) => {
@@ -55,10 +57,13 @@ fn define<Bx: BuilderMethods<'a, 'tcx>>(
                 let flags = attrs.flags;
 
                 // Emit dynexport metadata BEFORE codegen (due to lifetime constraints)
-                if flags.contains(CodegenFnAttrFlags::DYNEXPORT) {
-                    let symbol_name = self.symbol_name(cx.tcx()).name;
-                    let type_hash = compute_fn_type_hash(cx.tcx(), instance);
-                    cx.emit_dynexport_metadata(symbol_name, type_hash);
+                if let Some(meta) = dynexport_metadata(cx.tcx(), *self) {
+                    cx.emit_dynexport_metadata(
+                        meta.symbol_name,
+                        meta.type_hash,
+                        meta.flags,
+                        &meta.interface,
+                    );
                 }
 
                 if flags.contains(CodegenFnAttrFlags::NAKED) {
@@ -0,0 +1,266 @@
+//! `rustc --inspect-dynexport lib.so`: list the `#[dynexport]` items of a shared library.
+//!
+//! Every item has a `dynexport_meta_<name>` record (type hash, compiler hash, flags) and, since
+//! interface descriptions were added, a `dynexport_wit_<name>` string; see
+//! `rustc_abi::dynexport` for both. They are found through the library's symbol table, so a
+//! stripped library still works as long as it keeps its dynamic symbols. With two libraries,
+//! items whose type hash or compiler hash changed, whose version went down or that disappeared
+//! are reported as ABI drift: the dynamic loader rejects an item built by another compiler just
+//! like one whose signature changed.
+
+use std::collections::BTreeMap;
+use std::fs;
+
+use object::{BinaryFormat, Object, ObjectSection, ObjectSymbol};
+use rustc_abi::dynexport::{
+    DYNEXPORT_FLAG_C_SHIM, DYNEXPORT_FLAG_NOUNWIND, DYNEXPORT_FLAG_STATIC,
+    dynexport_compiler_hash, dynexport_version,
+};
+use rustc_session::EarlyDiagCtxt;
+use serde_json::json;
+
+#[allow(unreachable_pub)]
+#[path = "../../extensions/src/elf.rs"]
+mod elf;
+
+struct Item {
+    type_hash: u64,
+    compiler_hash: u32,
+    flags: u32,
+    /// The WIT-style description, absent in libraries built before they were emitted.
+    interface: Option<String>,
+}
+
+/// Entry point for `--inspect-dynexport`; `format` is the value of `--inspect-format`.
+pub(crate) fn inspect(early_dcx: &EarlyDiagCtxt, libs: &[String], format: Option<&str>) {
+    let json = match format {
+        None | Some("text") => false,
+        Some("json") => true,
+        Some(other) => early_dcx.early_fatal(format!(
+            "unknown `--inspect-format` `{other}`, expected `text` or `json`"
+        )),
+    };
+    let read = |lib: &String| {
+        read_items(lib).unwrap_or_else(|err| {
+            early_dcx.early_fatal(format!("cannot inspect `{lib}`: {err}"))
+        })
+    };
+    match libs {
+        [lib] => {
+            let items = read(lib);
+            if json {
+                safe_println!("{}", library_json(lib, &items));
+            } else {
+                print_library(lib, &items);
+            }
+        }
+        [old, new] => {
+            let (old_items, new_items) = (read(old), read(new));
+            if !compare(old, &old_items, new, &new_items, json) {
+                early_dcx.early_fatal(format!("`{new}` is not ABI compatible with `{old}`"));
+            }
+        }
+        _ => early_dcx.early_fatal("`--inspect-dynexport` takes one library, or two to compare"),
+    }
+}
+
+/// Every named symbol of the library with the bytes from its address to the end of its
+/// section, and whether the file is big-endian. ELF files are read with the same reader the
+/// script runtime uses for `DynexportInterface::read`, other formats with `object`.
+fn symbols(data: &[u8]) -> Result<(Vec<(&str, &[u8])>, bool), String> {
+    if let Some(elf) = elf::__Elf::parse(data) {
+        let symbols = elf
+            .symbols()
+            .iter()
+            .filter_map(|symbol| {
+                Some((std::str::from_utf8(symbol.name).ok()?, elf.symbol_data(symbol)?))
+            })
+            .collect();
+        return Ok((symbols, elf.is_big_endian()));
+    }
+    let file = object::File::parse(data).map_err(|err| err.to_string())?;
+    let mut symbols = Vec::new();
+    for symbol in file.dynamic_symbols().chain(file.symbols()) {
+        let Ok(mut name) = symbol.name() else { continue };
+        if file.format() == BinaryFormat::MachO {
+            name = name.strip_prefix('_').unwrap_or(name);
+        }
+        let bytes = || -> Option<&[u8]> {
+            let section = file.section_by_index(symbol.section_index()?).ok()?;
+            let offset = symbol.address().checked_sub(section.address())?;
+            section.data().ok()?.get(usize::try_from(offset).ok()?..)
+        };
+        if let Some(bytes) = bytes() {
+            symbols.push((name, bytes));
+        }
+    }
+    Ok((symbols, !file.is_little_endian()))
+}
+
+fn read_items(path: &str) -> Result<BTreeMap<String, Item>, String> {
+    let data = fs::read(path).map_err(|err| err.to_string())?;
+    let (symbols, big_endian) = symbols(&data)?;
+    let mut records = BTreeMap::new();
+    let mut interfaces = BTreeMap::new();
+    for (name, bytes) in symbols {
+        if let Some(item) = name.strip_prefix("dynexport_meta_") {
+            let Some(record) = bytes.get(..16) else { continue };
+            let int = |range: std::ops::Range<usize>| {
+                let bytes = &record[range];
+                let fold = |value: u64, &byte: &u8| (value << 8) | u64::from(byte);
+                if big_endian {
+                    bytes.iter().fold(0, fold)
+                } else {
+                    bytes.iter().rev().fold(0, fold)
+                }
+            };
+            records.insert(item.to_string(), (int(0..8), int(8..12) as u32, int(12..16) as u32));
+        } else if let Some(item) = name.strip_prefix("dynexport_wit_") {
+            let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+            let interface = String::from_utf8_lossy(&bytes[..len]).into_owned();
+            interfaces.insert(item.to_string(), interface);
+        }
+    }
+    Ok(records
+        .into_iter()
+        .map(|(name, (type_hash, compiler_hash, flags))| {
+            let interface = interfaces.remove(&name);
+            (name, Item { type_hash, compiler_hash, flags, interface })
+        })
+        .collect())
+}
+
+fn this_compiler_hash() -> u32 {
+    dynexport_compiler_hash(rustc_interface::util::rustc_version_str().unwrap_or("unknown"))
+}
+
+/// The flags spelled out, e.g. `fn, nounwind, c-shim, version 2`.
+fn describe_flags(flags: u32) -> String {
+    let mut parts = vec![if flags & DYNEXPORT_FLAG_STATIC != 0 { "static" } else { "fn" }];
+    if flags & DYNEXPORT_FLAG_NOUNWIND != 0 {
+        parts.push("nounwind");
+    }
+    if flags & DYNEXPORT_FLAG_C_SHIM != 0 {
+        parts.push("c-shim");
+    }
+    format!("{}, version {}", parts.join(", "), dynexport_version(flags))
+}
+
+/// The item line of a description, e.g. `add: func(a: s32) -> s32;`, or just the name.
+fn signature<'a>(name: &'a str, item: &'a Item) -> &'a str {
+    item.interface.as_deref().and_then(|interface| interface.lines().last()).unwrap_or(name)
+}
+
+fn print_library(lib: &str, items: &BTreeMap<String, Item>) {
+    let this_compiler = this_compiler_hash();
+    safe_println!("{lib}: {} #[dynexport] item(s)", items.len());
+    for (name, item) in items {
+        safe_println!("");
+        if let Some(interface) = &item.interface {
+            for line in interface.lines() {
+                safe_println!("{line}");
+            }
+        } else {
+            safe_println!("{name} (no interface description)");
+        }
+        let compiler = if item.compiler_hash == this_compiler { " (this compiler)" } else { "" };
+        safe_println!(
+            "    type hash {:#018x}, compiler hash {:#010x}{compiler}, flags {:#010x} ({})",
+            item.type_hash,
+            item.compiler_hash,
+            item.flags,
+            describe_flags(item.flags)
+        );
+    }
+}
+
+fn item_json(name: &str, item: &Item) -> serde_json::Value {
+    json!({
+        "name": name,
+        "signature": signature(name, item),
+        "interface": item.interface,
+        // Hex strings: JSON numbers can't hold every u64 exactly.
+        "type_hash": format!("{:#018x}", item.type_hash),
+        "compiler_hash": format!("{:#010x}", item.compiler_hash),
+        "same_compiler": item.compiler_hash == this_compiler_hash(),
+        "flags": item.flags,
+        "static": item.flags & DYNEXPORT_FLAG_STATIC != 0,
+        "nounwind": item.flags & DYNEXPORT_FLAG_NOUNWIND != 0,
+        "c_shim": item.flags & DYNEXPORT_FLAG_C_SHIM != 0,
+        "version": dynexport_version(item.flags),
+    })
+}
+
+fn library_json(lib: &str, items: &BTreeMap<String, Item>) -> serde_json::Value {
+    let items: Vec<_> = items.iter().map(|(name, item)| item_json(name, item)).collect();
+    json!({ "library": lib, "items": items })
+}
+
+/// Report what changed between two builds; `false` if the new one breaks users of the old.
+fn compare(
+    old: &str,
+    old_items: &BTreeMap<String, Item>,
+    new: &str,
+    new_items: &BTreeMap<String, Item>,
+    json: bool,
+) -> bool {
+    let removed: Vec<&String> =
+        old_items.keys().filter(|name| !new_items.contains_key(*name)).collect();
+    let added: Vec<&String> =
+        new_items.keys().filter(|name| !old_items.contains_key(*name)).collect();
+    let changed: Vec<(&String, &Item, &Item)> = old_items
+        .iter()
+        .filter_map(|(name, old_item)| {
+            let new_item = new_items.get(name)?;
+            let downgraded = dynexport_version(new_item.flags) < dynexport_version(old_item.flags);
+            let rebuilt = new_item.compiler_hash != old_item.compiler_hash;
+            (new_item.type_hash != old_item.type_hash || rebuilt || downgraded)
+                .then_some((name, old_item, new_item))
+        })
+        .collect();
+
+    if json {
+        let changed: Vec<_> = changed
+            .iter()
+            .map(|(name, old_item, new_item)| {
+                let (old_item, new_item) = (item_json(name, old_item), item_json(name, new_item));
+                json!({ "name": name, "old": old_item, "new": new_item })
+            })
+            .collect();
+        safe_println!(
+            "{}",
+            json!({
+                "old": old,
+                "new": new,
+                "compatible": removed.is_empty() && changed.is_empty(),
+                "added": added,
+                "removed": removed,
+                "changed": changed,
+            })
+        );
+    } else {
+        safe_println!("comparing {old} -> {new}");
+        for name in &added {
+            safe_println!("+ {}", signature(name, &new_items[*name]));
+        }
+        for name in &removed {
+            safe_println!("- {}", signature(name, &old_items[*name]));
+        }
+        for (name, old_item, new_item) in &changed {
+            safe_println!("~ {name}");
+            for (when, item) in [("was", old_item), ("now", new_item)] {
+                let version = dynexport_version(item.flags);
+                let (signature, hash) = (signature(name, item), item.type_hash);
+                let compiler = item.compiler_hash;
+                safe_println!(
+                    "    {when} {signature} ({hash:#018x}, compiler {compiler:#010x}, \
+                     version {version})"
+                );
+            }
+        }
+        if added.is_empty() && removed.is_empty() && changed.is_empty() {
+            safe_println!("no changes");
+        }
+    }
+    removed.is_empty() && changed.is_empty()
+}
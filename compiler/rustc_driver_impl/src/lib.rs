@@ -87,7 +87,9 @@
 pub mod pretty;
 #[macro_use]
 mod print;
+mod dynexport_inspect;
 pub mod highlighter;
+mod script_cache;
 mod session_diagnostics;
 
 // Keep the OS parts of this `cfg` in sync with the `cfg` on the `libc`
@@ -135,19 +137,6 @@ fn has_shebang(input: &Input) -> bool {
     }
 }
 
-/// Get the default output executable path for script mode.
-/// Returns a path relative to current directory (prefixed with ./) so Command can find it.
-fn get_script_output_path(input: &Input) -> Option<PathBuf> {
-    match input {
-        Input::File(path) => {
-            let stem = path.file_stem()?;
-            // Prefix with "./" so Command::new finds it in current directory
-            Some(PathBuf::from(".").join(stem))
-        }
-        Input::Str { .. } => None,
-    }
-}
-
 pub fn default_translator() -> Translator {
     Translator::with_fallback_bundle(DEFAULT_LOCALE_RESOURCES.to_vec(), false)
 }
@@ -283,9 +272,45 @@ pub fn run_compiler(at_args: &[String], callbacks: &mut (dyn Callbacks + Send))
         return;
     }
 
+    let dynexport_libs = matches.opt_strs("inspect-dynexport");
+    if !dynexport_libs.is_empty() {
+        let format = matches.opt_str("inspect-format");
+        dynexport_inspect::inspect(&default_early_dcx, &dynexport_libs, format.as_deref());
+        return;
+    }
+
     let input = make_input(&default_early_dcx, &matches.free);
     let has_input = input.is_some();
-    let (odir, ofile) = make_output(&matches);
+    let (odir, mut ofile) = make_output(&matches);
+
+    // Shebang scripts build into a content-addressed cache instead of the current
+    // directory; an up-to-date binary from an earlier run is executed right away.
+    let script_cache = match input {
+        Some(Input::File(ref path))
+            if !help_only
+                && odir.is_none()
+                && ofile.is_none()
+                && sopts.prints.is_empty()
+                && sopts.pretty.is_none()
+                && sopts.output_types.contains_key(&OutputType::Exe)
+                && (sopts.crate_types.is_empty()
+                    || sopts.crate_types.contains(&CrateType::Executable))
+                && has_shebang(&Input::File(path.clone())) =>
+        {
+            script_cache::ScriptCache::new(path, &args)
+        }
+        _ => None,
+    };
+    if let Some(cache) = &script_cache {
+        if let Some(exe) = cache.lookup() {
+            script_cache::run(&exe);
+        }
+        match cache.output_path() {
+            Ok(path) => ofile = Some(OutFileName::Real(path)),
+            Err(err) => default_early_dcx
+                .early_fatal(format!("failed to create script cache directory: {err}")),
+        }
+    }
 
     drop(default_early_dcx);
 
@@ -447,29 +472,17 @@ pub fn run_compiler(at_args: &[String], callbacks: &mut (dyn Callbacks + Send))
         if let Some(linker) = linker {
             linker.link(sess, codegen_backend);
 
-            // Auto-run the compiled binary if:
-            // 1. The source has a shebang line (script mode)
-            // 2. No explicit output file was specified (user wants script behavior)
-            // 3. No compilation errors
-            // 4. We're actually producing an executable (not just metadata, etc.)
-            let is_shebang_script = has_shebang(&sess.io.input)
-                && sess.io.output_file.is_none()
+            // Auto-run a shebang script once it is built, unless compilation failed
+            // or the user overrode where the output goes (then `script_cache` is None).
+            if let Some(cache) = &script_cache
                 && sess.dcx().has_errors().is_none()
-                && sess.opts.output_types.contains_key(&OutputType::Exe);
-
-            if is_shebang_script {
-                if let Some(exe_path) = get_script_output_path(&sess.io.input) {
-                    // Run the compiled binary and exit with its exit code
-                    match Command::new(&exe_path).status() {
-                        Ok(status) => {
-                            // Clean up the temporary binary after execution
-                            let _ = fs::remove_file(&exe_path);
-                            process::exit(status.code().unwrap_or(1));
-                        }
-                        Err(e) => {
-                            eprintln!("error: failed to run {}: {}", exe_path.display(), e);
-                            process::exit(1);
-                        }
+                && let Some(OutFileName::Real(output)) = &sess.io.output_file
+            {
+                match cache.store(sess, output) {
+                    Ok(exe) => script_cache::run(&exe),
+                    Err(e) => {
+                        eprintln!("error: failed to cache {}: {}", output.display(), e);
+                        script_cache::run(output);
                     }
                 }
             }
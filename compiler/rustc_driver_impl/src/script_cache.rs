@@ -0,0 +1,203 @@
+//! Cache of compiled shebang scripts.
+//!
+//! A script compiles to `$XDG_CACHE_HOME/rust-scripts/<key>/<stem>`, where the key hashes
+//! the script source, the compiler and the command-line flags. Next to the binary a
+//! `deps` file lists every local source file the compilation read (imported scripts,
+//! `mod` files, `include_str!` inputs) with the hash of its contents, so editing an
+//! imported file invalidates the entry even though the script itself is unchanged.
+//! A valid entry is exec'd directly without starting a compilation.
+//!
+//! Each hit touches the `deps` file, so its modification time is the entry's last use.
+//! Whenever a script is compiled, entries unused for `MAX_UNUSED` are deleted.
+
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+use std::time::{Duration, SystemTime};
+
+use rustc_data_structures::fingerprint::Fingerprint;
+use rustc_data_structures::stable_hasher::StableHasher;
+use rustc_session::Session;
+use rustc_span::FileName;
+
+/// How long an entry may go without being run before a later compilation deletes it.
+const MAX_UNUSED: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+pub(crate) struct ScriptCache {
+    /// `<cache root>/<key>`
+    dir: PathBuf,
+    /// File name of the binary inside `dir`: the script's file stem.
+    exe_name: OsString,
+}
+
+impl ScriptCache {
+    /// `args` are the compiler arguments; `script` is the one naming the input file.
+    pub(crate) fn new(script: &Path, args: &[String]) -> Option<ScriptCache> {
+        let source = fs::read(script).ok()?;
+        let exe_name = script.file_stem()?.to_os_string();
+
+        let mut hasher = StableHasher::new();
+        hash_bytes(&mut hasher, &source);
+        // The version string alone does not change between local builds of the
+        // compiler, so also key on the identity of the compiler binary.
+        let version = rustc_interface::util::rustc_version_str().unwrap_or("");
+        hash_bytes(&mut hasher, version.as_bytes());
+        if let Ok(meta) = env::current_exe().and_then(fs::metadata) {
+            hash_bytes(&mut hasher, &meta.len().to_le_bytes());
+            if let Ok(modified) = meta.modified()
+                && let Ok(since_epoch) = modified.duration_since(std::time::UNIX_EPOCH)
+            {
+                hash_bytes(&mut hasher, &since_epoch.as_nanos().to_le_bytes());
+            }
+        }
+        for arg in args.iter().filter(|arg| Path::new(arg) != script) {
+            hash_bytes(&mut hasher, arg.as_bytes());
+        }
+        let key: Fingerprint = hasher.finish();
+
+        Some(ScriptCache { dir: cache_root().join(key.to_hex()), exe_name })
+    }
+
+    /// The cached binary, if it exists and none of the files it was built from changed.
+    pub(crate) fn lookup(&self) -> Option<PathBuf> {
+        let exe = self.dir.join(&self.exe_name);
+        if !exe.is_file() {
+            return None;
+        }
+        let manifest = self.dir.join("deps");
+        let deps = fs::read_to_string(&manifest).ok()?;
+        for line in deps.lines() {
+            let (hash, path) = line.split_once('\t')?;
+            if file_hash(Path::new(path))? != hash {
+                return None;
+            }
+        }
+        // Keep the entry from being evicted; failing to is harmless.
+        let _ = fs::File::options()
+            .append(true)
+            .open(&manifest)
+            .and_then(|file| file.set_modified(SystemTime::now()));
+        Some(exe)
+    }
+
+    /// Where the compiler should write the binary. The name is unique per process so
+    /// concurrent compilations of the same script don't clobber each other; `store`
+    /// moves it into place.
+    pub(crate) fn output_path(&self) -> io::Result<PathBuf> {
+        fs::create_dir_all(&self.dir)?;
+        let mut name = self.exe_name.clone();
+        name.push(format!(".{}.tmp", process::id()));
+        Ok(self.dir.join(name))
+    }
+
+    /// Record the sources `sess` read and publish the binary written to `output_path`.
+    pub(crate) fn store(&self, sess: &Session, output: &Path) -> io::Result<PathBuf> {
+        let mut deps = Some(String::new());
+        for file in sess.source_map().files().iter() {
+            if file.is_imported() {
+                continue;
+            }
+            let FileName::Real(name) = &file.name else { continue };
+            let Some(path) = name.local_path().and_then(|path| fs::canonicalize(path).ok()) else {
+                continue;
+            };
+            let (Some(list), Some(hash)) = (&mut deps, file_hash(&path)) else { continue };
+            match path.to_str() {
+                Some(path) if !path.contains(['\t', '\n']) => {
+                    list.push_str(&format!("{hash}\t{path}\n"));
+                }
+                // Can't be written to the manifest; without one `lookup` never hits.
+                _ => deps = None,
+            }
+        }
+
+        let exe = self.dir.join(&self.exe_name);
+        let manifest = self.dir.join("deps");
+        // Drop the old manifest first so a reader never pairs it with the new binary.
+        match fs::remove_file(&manifest) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+            _ => {}
+        }
+        fs::rename(output, &exe)?;
+        if let Some(deps) = deps {
+            let deps_tmp = self.dir.join(format!("deps.{}.tmp", process::id()));
+            fs::write(&deps_tmp, deps)?;
+            fs::rename(&deps_tmp, manifest)?;
+        }
+        self.evict_unused();
+        Ok(exe)
+    }
+
+    /// Delete the other entries that haven't been used for `MAX_UNUSED`. An entry without a
+    /// `deps` file counts as used when its directory last changed.
+    fn evict_unused(&self) {
+        let Some(entries) = self.dir.parent().and_then(|root| fs::read_dir(root).ok()) else {
+            return;
+        };
+        let now = SystemTime::now();
+        for entry in entries.flatten() {
+            let dir = entry.path();
+            if dir == self.dir || !entry.file_type().is_ok_and(|ty| ty.is_dir()) {
+                continue;
+            }
+            let last_used = fs::metadata(dir.join("deps"))
+                .or_else(|_| entry.metadata())
+                .and_then(|meta| meta.modified());
+            if let Ok(last_used) = last_used
+                && now.duration_since(last_used).is_ok_and(|unused| unused > MAX_UNUSED)
+            {
+                let _ = fs::remove_dir_all(&dir);
+            }
+        }
+    }
+}
+
+/// Run a compiled script in place of the current process and exit with its status.
+pub(crate) fn run(exe: &Path) -> ! {
+    let mut command = Command::new(exe);
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        let err = command.exec();
+        eprintln!("error: failed to run {}: {}", exe.display(), err);
+        process::exit(1);
+    }
+    #[cfg(not(unix))]
+    match command.status() {
+        Ok(status) => process::exit(status.code().unwrap_or(1)),
+        Err(err) => {
+            eprintln!("error: failed to run {}: {}", exe.display(), err);
+            process::exit(1);
+        }
+    }
+}
+
+/// `$XDG_CACHE_HOME/rust-scripts`, falling back to `~/.cache` and then the temp dir.
+fn cache_root() -> PathBuf {
+    let base = env::var_os("XDG_CACHE_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            let home = env::var_os("HOME").filter(|dir| !dir.is_empty())?;
+            Some(Path::new(&home).join(".cache"))
+        })
+        .unwrap_or_else(env::temp_dir);
+    base.join("rust-scripts")
+}
+
+fn hash_bytes(hasher: &mut StableHasher, bytes: &[u8]) {
+    use std::hash::Hasher;
+    // Length prefix so that ("ab", "c") and ("a", "bc") hash differently.
+    hasher.write_usize(bytes.len());
+    hasher.write(bytes);
+}
+
+fn file_hash(path: &Path) -> Option<String> {
+    let contents = fs::read(path).ok()?;
+    let mut hasher = StableHasher::new();
+    hash_bytes(&mut hasher, &contents);
+    Some(hasher.finish::<Fingerprint>().to_hex())
+}
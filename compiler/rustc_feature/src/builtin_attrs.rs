@@ -661,7 +661,7 @@ pub struct BuiltinAttribute {
     ),
     ungated!(
         dynexport, Normal,
-        template!(Word),
+        template!(Word, List: &["version = N"]),
         WarnFollowing, EncodeCrossCrate::No
     ),
     ungated!(
@@ -1096,6 +1096,11 @@ pub struct BuiltinAttribute {
         rustc_std_internal_symbol, Normal, template!(Word), WarnFollowing,
         EncodeCrossCrate::No,
     ),
+    // Added by the compiler to `#[dynexport]` fns it generated a `dynexport_c_<name>` shim for.
+    rustc_attr!(
+        rustc_dynexport_c_shim, Normal, template!(Word), WarnFollowing,
+        EncodeCrossCrate::No,
+    ),
     rustc_attr!(
         rustc_objc_class, Normal, template!(NameValueStr: "ClassName"), ErrorPreceding,
         EncodeCrossCrate::No,
@@ -225,6 +225,8 @@ pub fn internal(&self, feature: Symbol) -> bool {
     (internal, eii_internals, "1.94.0", None),
     /// Outputs useful `assert!` messages
     (unstable, generic_assert, "1.63.0", None),
+    /// Allows `needle in haystack` and `needle not in haystack` outside script mode.
+    (unstable, in_operator, "CURRENT_RUSTC_VERSION", None),
     /// Allows using the #[rustc_intrinsic] attribute.
     (internal, intrinsics, "1.0.0", None),
     /// Allows using `#[lang = ".."]` attribute for linking items to special compiler logic.
@@ -851,8 +851,9 @@ pub enum AttributeKind {
     /// Represents `#[rustc_dummy]`.
     Dummy,
 
-    /// Represents `#[dynexport]` for stable dynamic library exports.
-    DynExport(Span),
+    /// Represents `#[dynexport]` and `#[dynexport(version = N)]` for stable dynamic library
+    /// exports.
+    DynExport { span: Span, version: Option<u16> },
 
     /// Represents `#[rustc_dyn_incompatible_trait]`.
     DynIncompatibleTrait(Span),
@@ -49,7 +49,7 @@ pub fn encode_cross_crate(&self) -> EncodeCrossCrate {
             Doc(_) => Yes,
             DocComment { .. } => Yes,
             Dummy => No,
-            DynExport(..) => No,
+            DynExport { .. } => No,
             DynIncompatibleTrait(..) => No,
             EiiDeclaration(_) => Yes,
             EiiForeignItem => No,
@@ -229,6 +229,9 @@ pub fn extract(attrs: &[impl AttributeExt]) -> Option<(Symbol, Span)> {
     ShrAssign,               sym::shr_assign,          shr_assign_trait,           Target::Trait,          GenericRequirement::Exact(1);
     Index,                   sym::index,               index_trait,                Target::Trait,          GenericRequirement::Exact(1);
     IndexMut,                sym::index_mut,           index_mut_trait,            Target::Trait,          GenericRequirement::Exact(1);
+    Contains,                sym::contains,            contains_trait,             Target::Trait,          GenericRequirement::Exact(1);
+    IterContains,            sym::iter_contains,       iter_contains_fn,           Target::Fn,             GenericRequirement::Exact(2);
+    ScriptMapField,          sym::script_map_field,    script_map_field_trait,     Target::Trait,          GenericRequirement::Exact(0);
 
     UnsafeCell,              sym::unsafe_cell,         unsafe_cell_type,           Target::Struct,         GenericRequirement::None;
     UnsafePinned,            sym::unsafe_pinned,       unsafe_pinned_type,         Target::Struct,         GenericRequirement::None;
@@ -2780,6 +2780,14 @@ fn check_expr_field(
             return final_ty;
         }
 
+        // Script mode: `m.key` on a string-keyed map reads `m["key"]`.
+        if private_candidate.is_none()
+            && self.tcx.sess.is_script_mode()
+            && let Some(ty) = self.check_script_map_field(expr, base, base_ty, field)
+        {
+            return ty;
+        }
+
         if let Some((adjustments, did)) = private_candidate {
             // (#90483) apply adjustments to avoid ExprUseVisitor from
             // creating erroneous projection.
@@ -2880,6 +2888,41 @@ fn check_expr_field(
         Ty::new_error(self.tcx(), guar)
     }
 
+    /// Script mode fallback for a failed field lookup: if the base is a map with
+    /// `String` or `&str` keys, resolve `base.field` to the `__map_field` method of
+    /// the injected `MapField` trait. THIR lowers the expression to
+    /// `*MapField::__map_field(&base, "field")`, so it stays a place like `base["field"]`.
+    fn check_script_map_field(
+        &self,
+        expr: &'tcx hir::Expr<'tcx>,
+        base: &'tcx hir::Expr<'tcx>,
+        base_ty: Ty<'tcx>,
+        field: Ident,
+    ) -> Option<Ty<'tcx>> {
+        let method_def_id = self.script_map_field_method(sym::__map_field)?;
+        let method_name = Ident::new(sym::__map_field, field.span);
+        let segment = self.tcx.arena.alloc(hir::PathSegment::new(
+            method_name,
+            expr.hir_id,
+            Res::Def(DefKind::AssocFn, method_def_id),
+        ));
+        // Fails for maps with other key types, which then get the usual field error.
+        let method = self.lookup_method(base_ty, segment, field.span, expr, base, &[]).ok()?;
+        self.write_method_call_and_enforce_effects(expr.hir_id, expr.span, method);
+        method.sig.output().builtin_deref(true)
+    }
+
+    /// Look up `name` (`__map_field` or `__map_field_mut`) in the `MapField` trait the
+    /// script harness injects, the `script_map_field` lang item.
+    pub(crate) fn script_map_field_method(&self, name: Symbol) -> Option<DefId> {
+        let trait_def_id = self.tcx.lang_items().script_map_field_trait()?;
+        self.tcx
+            .associated_items(trait_def_id)
+            .filter_by_name_unhygienic(name)
+            .next()
+            .map(|item| item.def_id)
+    }
+
     fn suggest_await_on_field_access(
         &self,
         err: &mut Diag<'_>,
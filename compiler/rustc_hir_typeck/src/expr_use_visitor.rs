@@ -1313,6 +1313,12 @@ fn cat_expr_unadjusted(&self, expr: &hir::Expr<'_>) -> Result<PlaceWithHirId<'tc
                 }
             }
 
+            hir::ExprKind::Field(base, _) if self.cx.typeck_results().is_method_call(expr) => {
+                // Script mode `m.key` on a map reads through a method call returning `&V`,
+                // just like an overloaded index.
+                self.cat_overloaded_place(expr, base)
+            }
+
             hir::ExprKind::Field(base, _) => {
                 let base = self.cat_expr(base)?;
                 debug!(?base);
@@ -3,7 +3,8 @@
 use rustc_data_structures::packed::Pu128;
 use rustc_errors::codes::*;
 use rustc_errors::{Applicability, Diag, struct_span_code_err};
-use rustc_infer::traits::ObligationCauseCode;
+use rustc_infer::infer::{BoundRegionConversionTime, InferOk};
+use rustc_infer::traits::{ObligationCause, ObligationCauseCode};
 use rustc_middle::bug;
 use rustc_middle::ty::adjustment::{
     Adjust, Adjustment, AllowTwoPhase, AutoBorrow, AutoBorrowMutability,
@@ -12,9 +13,12 @@
 use rustc_middle::ty::{self, IsSuggestable, Ty, TyCtxt, TypeVisitableExt};
 use rustc_session::errors::ExprParenthesesNeeded;
 use rustc_span::source_map::Spanned;
-use rustc_span::{Ident, Span, Symbol, sym};
+use rustc_span::{Span, Symbol, sym};
 use rustc_trait_selection::infer::InferCtxtExt;
-use rustc_trait_selection::traits::{FulfillmentError, Obligation, ObligationCtxt};
+use rustc_trait_selection::traits::query::evaluate_obligation::InferCtxtExt as _;
+use rustc_trait_selection::traits::{
+    self, FulfillmentError, NormalizeExt, Obligation, ObligationCtxt,
+};
 use tracing::debug;
 use {rustc_ast as ast, rustc_hir as hir};
 
@@ -166,8 +170,12 @@ pub(crate) fn check_expr_binop(
         }
     }
 
-    /// Check the `in` operator: `a in b` desugars to `b.contains(&a)`.
-    /// This is a Python-like containment check.
+    /// Check the `in` operator: `a in b` is `Contains::contains(&b, &a)`, or a call
+    /// to the `iter_contains` lang item when `b` is an iterator.
+    ///
+    /// The needle is borrowed unless it already is a reference. A reference needle
+    /// `&x` is looked for as `x` first and as `&x` itself otherwise, which is what
+    /// `"a" in ["a", "b"]` needs.
     fn check_in_operator(
         &self,
         expr: &'tcx hir::Expr<'tcx>,
@@ -179,84 +187,119 @@ fn check_in_operator(
         // Type-check the RHS (container) first
         let rhs_ty = self.check_expr(rhs_expr);
         let rhs_ty = self.try_structurally_resolve_type(rhs_expr.span, rhs_ty);
+        let lhs_ty = self.check_expr(lhs_expr);
+        let lhs_ty = self.try_structurally_resolve_type(lhs_expr.span, lhs_ty);
+        if let Err(guar) = (lhs_ty, rhs_ty).error_reported() {
+            return Ty::new_error(tcx, guar);
+        }
 
-        // Look up the `contains` method on the RHS type
-        // For `a in b`, we want to call `b.contains(a)` (or `b.contains(&a)`)
-        let method_name = Ident::new(sym::contains, expr.span);
-
-        // Create a path segment for method lookup - allocate in arena for 'tcx lifetime
-        let segment = tcx.arena.alloc(hir::PathSegment::new(method_name, expr.hir_id, hir::def::Res::Err));
-
-        // Use the full method lookup which handles type inference properly
-        // We pass an empty slice for args since lookup_method only uses them for lints
-        match self.lookup_method(rhs_ty, segment, expr.span, expr, rhs_expr, &[]) {
-            Ok(method) => {
-                // Record the method call
-                self.write_method_call_and_enforce_effects(expr.hir_id, expr.span, method);
+        // Needle types to try, and whether the LHS is borrowed to pass it
+        let mut needles = vec![];
+        if let ty::Ref(_, inner_ty, _) = *lhs_ty.kind() {
+            needles.push((inner_ty, false));
+        }
+        needles.push((lhs_ty, true));
+
+        let cause = self.misc(expr.span);
+        let contains = tcx.lang_items().contains_trait().and_then(|contains_trait| {
+            needles.iter().find_map(|&(needle_ty, borrow_needle)| {
+                let method = self.lookup_method_for_operator(
+                    cause.clone(),
+                    sym::contains,
+                    contains_trait,
+                    rhs_ty,
+                    Some(needle_ty),
+                    TreatNotYetDefinedOpaques::AsInfer,
+                )?;
+                Some((method, borrow_needle))
+            })
+        });
+        let iter_contains = || {
+            let iterator_trait = tcx.lang_items().iterator_trait()?;
+            if !self.type_implements_trait(iterator_trait, [rhs_ty], self.param_env).may_apply() {
+                return None;
+            }
+            needles.iter().find_map(|&(needle_ty, borrow_needle)| {
+                Some((self.lookup_iter_contains(&cause, rhs_ty, needle_ty)?, borrow_needle))
+            })
+        };
 
-                // Type-check the argument (lhs), coercing to expected type
-                // inputs[0] is self, inputs[1] is the argument
-                let expected_arg_ty = method.sig.inputs()[1];
+        let Some((method, borrow_needle)) = contains.or_else(iter_contains) else {
+            let guar = struct_span_code_err!(
+                self.dcx(),
+                expr.span,
+                E0369,
+                "cannot apply `in` operator to type `{}`",
+                rhs_ty
+            )
+            .with_span_label(lhs_expr.span, lhs_ty.to_string())
+            .with_note(format!(
+                "`in` operator requires `{rhs_ty}` to implement `std::ops::Contains<{}>` or `Iterator`",
+                needles[0].0
+            ))
+            .emit();
+            return Ty::new_error(tcx, guar);
+        };
+        let method = self.register_infer_ok_obligations(method);
+        self.write_method_call_and_enforce_effects(expr.hir_id, expr.span, method);
+
+        // `Contains::contains` borrows the haystack, `iter_contains` consumes it
+        let autoref = |region, ty, mutbl| Adjustment {
+            kind: Adjust::Borrow(AutoBorrow::Ref(AutoBorrowMutability::new(
+                mutbl,
+                AllowTwoPhase::No,
+            ))),
+            target: Ty::new_ref(tcx, region, ty, mutbl),
+        };
+        if let ty::Ref(region, _, mutbl) = *method.sig.inputs()[0].kind() {
+            self.apply_adjustments(rhs_expr, vec![autoref(region, rhs_ty, mutbl)]);
+        }
+        let needle_arg_ty = method.sig.inputs()[1];
+        if borrow_needle && let ty::Ref(region, _, mutbl) = *needle_arg_ty.kind() {
+            self.apply_adjustments(lhs_expr, vec![autoref(region, lhs_ty, mutbl)]);
+        } else {
+            self.demand_coerce(lhs_expr, lhs_ty, needle_arg_ty, None, AllowTwoPhase::No);
+        }
 
-                // Type-check the LHS first to get its type
-                let lhs_ty = self.check_expr(lhs_expr);
-                let lhs_ty = self.try_structurally_resolve_type(lhs_expr.span, lhs_ty);
-
-                // Auto-borrow: For `in` operator, add auto-ref for non-reference LHS when needed
-                // This enables `2 in [1,2,3]` instead of requiring `&2 in [1,2,3]`
-                // Also handles `needle in text` where needle: String (String doesn't impl Pattern but &String does)
-                if let ty::Ref(region, inner_ty, mutbl) = expected_arg_ty.kind() {
-                    // Expected type is &T - check if adding a reference helps
-                    if self.can_eq(self.param_env, lhs_ty, *inner_ty) {
-                        let autoref_mutbl = AutoBorrowMutability::new(*mutbl, AllowTwoPhase::No);
-                        let target = Ty::new_ref(tcx, *region, lhs_ty, *mutbl);
-                        let autoref = Adjustment {
-                            kind: Adjust::Borrow(AutoBorrow::Ref(autoref_mutbl)),
-                            target,
-                        };
-                        self.apply_adjustments(lhs_expr, vec![autoref]);
-                    } else {
-                        self.demand_coerce(lhs_expr, lhs_ty, expected_arg_ty, None, AllowTwoPhase::No);
-                    }
-                } else if !matches!(lhs_ty.kind(), ty::Ref(..)) {
-                    // LHS is not a reference, expected type is generic (e.g., P: Pattern)
-                    // String needs &String for Pattern but char works directly
-                    let needs_autoref = matches!(lhs_ty.kind(), ty::Adt(..));
-                    if needs_autoref {
-                        let ref_lhs_ty = Ty::new_imm_ref(tcx, tcx.lifetimes.re_erased, lhs_ty);
-                        let autoref = Adjustment {
-                            kind: Adjust::Borrow(AutoBorrow::Ref(AutoBorrowMutability::Not)),
-                            target: ref_lhs_ty,
-                        };
-                        self.apply_adjustments(lhs_expr, vec![autoref]);
-                        self.demand_coerce(lhs_expr, ref_lhs_ty, expected_arg_ty, None, AllowTwoPhase::No);
-                    } else {
-                        self.demand_coerce(lhs_expr, lhs_ty, expected_arg_ty, None, AllowTwoPhase::No);
-                    }
-                } else {
-                    // LHS is already a reference, use normal coercion
-                    self.demand_coerce(lhs_expr, lhs_ty, expected_arg_ty, None, AllowTwoPhase::No);
-                }
+        method.sig.output()
+    }
 
-                method.sig.output()
-            }
-            Err(_) => {
-                // Also check the lhs to avoid cascading errors
-                let _lhs_ty = self.check_expr(lhs_expr);
-
-                // No `contains` method found, emit an error
-                let guar = struct_span_code_err!(
-                    self.dcx(),
-                    expr.span,
-                    E0369,
-                    "cannot apply `in` operator to type `{}`",
-                    rhs_ty
-                )
-                .with_note(format!("`in` operator requires RHS type `{}` to have a `contains` method", rhs_ty))
-                .emit();
-                Ty::new_error(tcx, guar)
-            }
+    /// Instantiates the `iter_contains` lang item for `needle_ty in iter_ty`, if the
+    /// iterator's items may be borrowed as the needle.
+    fn lookup_iter_contains(
+        &self,
+        cause: &ObligationCause<'tcx>,
+        iter_ty: Ty<'tcx>,
+        needle_ty: Ty<'tcx>,
+    ) -> Option<InferOk<'tcx, MethodCallee<'tcx>>> {
+        let tcx = self.tcx;
+        let def_id = tcx.lang_items().iter_contains_fn()?;
+        let args = tcx.mk_args(&[iter_ty.into(), needle_ty.into()]);
+
+        let fn_sig = tcx.fn_sig(def_id).instantiate(tcx, args);
+        let fn_sig = self.instantiate_binder_with_fresh_vars(
+            cause.span,
+            BoundRegionConversionTime::FnCall,
+            fn_sig,
+        );
+        let InferOk { value: sig, mut obligations } =
+            self.at(cause, self.param_env).normalize(fn_sig);
+
+        let bounds = tcx.predicates_of(def_id).instantiate(tcx, args);
+        let InferOk { value: bounds, obligations: o } =
+            self.at(cause, self.param_env).normalize(bounds);
+        obligations.extend(o);
+        let predicates_cause = cause.clone();
+        obligations.extend(traits::predicates_for_generics(
+            move |_, _| predicates_cause.clone(),
+            self.param_env,
+            bounds,
+        ));
+
+        if !obligations.iter().all(|obligation| self.predicate_may_hold(obligation)) {
+            return None;
         }
+        Some(InferOk { value: MethodCallee { def_id, args, sig }, obligations })
     }
 
     /// Check the `**` (Pow) operator: allows both same-type and mixed int/float operations.
@@ -1224,10 +1267,8 @@ fn lang_item_for_binop(tcx: TyCtxt<'_>, op: Op) -> (Symbol, Option<hir::def_id::
             hir::BinOpKind::And | hir::BinOpKind::Or => {
                 bug!("&& and || are not overloadable")
             }
-            hir::BinOpKind::In => {
-                // `in` operator desugars to .contains() method call, not a trait
-                (sym::contains, None)
-            }
+            // Checked by `check_in_operator`, with the container as `Self`
+            hir::BinOpKind::In => (sym::contains, lang.contains_trait()),
         },
     }
 }
@@ -343,11 +343,42 @@ pub(crate) fn convert_place_derefs_to_mutable(&self, expr: &hir::Expr<'_>) {
                 hir::ExprKind::Unary(hir::UnOp::Deref, base_expr) => {
                     self.convert_place_op_to_mutable(PlaceOp::Deref, expr, base_expr);
                 }
+                hir::ExprKind::Field(base_expr, _) => {
+                    self.convert_map_field_to_mutable(expr, base_expr);
+                }
                 _ => {}
             }
         }
     }
 
+    /// Script mode: switch a `m.key` map access (see `check_script_map_field`) from
+    /// `__map_field` to `__map_field_mut`.
+    fn convert_map_field_to_mutable(&self, expr: &hir::Expr<'_>, base_expr: &hir::Expr<'_>) {
+        if !self.typeck_results.borrow().is_method_call(expr) {
+            return;
+        }
+        let Some(method_def_id) = self.script_map_field_method(sym::__map_field_mut) else {
+            return;
+        };
+        let base_ty = self
+            .typeck_results
+            .borrow()
+            .expr_ty_adjusted(base_expr)
+            .builtin_deref(false)
+            .expect("map field access takes something that is not a ref");
+        let method = self.lookup_method_for_operator(
+            self.misc(expr.span),
+            sym::__map_field_mut,
+            self.tcx.parent(method_def_id),
+            base_ty,
+            None,
+            TreatNotYetDefinedOpaques::AsInfer,
+        );
+        let Some(ok) = method else { return };
+        let method = self.register_infer_ok_obligations(ok);
+        self.write_mutable_place_op(expr, base_expr, method);
+    }
+
     fn convert_place_op_to_mutable(
         &self,
         op: PlaceOp,
@@ -389,6 +420,16 @@ fn convert_place_op_to_mutable(
             // current, immutable version.
             None => return,
         };
+        self.write_mutable_place_op(expr, base_expr, method);
+    }
+
+    /// Record the mutable variant of a place op and make the autoref of its base mutable.
+    fn write_mutable_place_op(
+        &self,
+        expr: &hir::Expr<'_>,
+        base_expr: &hir::Expr<'_>,
+        method: MethodCallee<'tcx>,
+    ) {
         debug!("convert_place_op_to_mutable: method={:?}", method);
         self.write_method_call_and_enforce_effects(expr.hir_id, expr.span, method);
 
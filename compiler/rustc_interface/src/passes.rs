@@ -170,6 +170,11 @@ fn configure_and_expand(
         rustc_builtin_macros::script_harness::inject(&mut krate, sess, features, resolver)
     });
 
+    // `#[dynexport]` items: add their C-ABI shims and `#[repr(C)]` mirrors
+    sess.time("dynexport_shims", || {
+        rustc_builtin_macros::dynexport::inject(&mut krate, sess, resolver)
+    });
+
     // TODO: check_attr_crate_type was removed in upstream - need to verify if this is still needed
     // util::check_attr_crate_type(sess, pre_configured_attrs, resolver.lint_buffer());
 
@@ -1264,6 +1269,12 @@ pub(crate) fn start_codegen<'tcx>(
 
     let metadata = rustc_metadata::fs::encode_and_write_metadata(tcx);
 
+    for output_type in [OutputType::DynexportHeader, OutputType::DynexportPy] {
+        if tcx.sess.opts.output_types.contains_key(&output_type) {
+            rustc_codegen_ssa::dynexport::bindings::emit(tcx, output_type);
+        }
+    }
+
     let codegen = tcx.sess.time("codegen_crate", move || {
         if tcx.sess.opts.unstable_opts.no_codegen || !tcx.sess.opts.output_types.should_codegen() {
             // Skip crate items and just output metadata in -Z no-codegen mode.
@@ -320,6 +320,14 @@ pub fn validate_raw_str(input: &str, prefix_len: u32) -> Result<(), RawStrError>
     cursor.raw_double_quoted_string(prefix_len).map(|_| ())
 }
 
+/// Finds the end of a script-mode `${ ... }` string placeholder. `input` starts
+/// right after the `${`; returns the byte offset of the matching `}`, skipping
+/// braces inside nested string and character literals.
+pub fn script_placeholder_end(input: &str) -> Option<usize> {
+    let mut cursor = Cursor::new(input, FrontmatterAllowed::No, ScriptMode::Enabled);
+    cursor.script_placeholder().then(|| cursor.pos_within_token() as usize - 1)
+}
+
 /// Creates an iterator that produces tokens from the input string.
 ///
 /// When parsing a full Rust document,
@@ -408,6 +416,16 @@ pub fn is_ident(string: &str) -> bool {
     }
 }
 
+/// Suffixes that make a numeric literal a specific integer or float type. In script mode
+/// every other suffix is a unit.
+pub fn is_numeric_type_suffix(suffix: &str) -> bool {
+    matches!(
+        suffix,
+        "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64" | "u128"
+            | "usize" | "f16" | "f32" | "f64" | "f128"
+    )
+}
+
 impl Cursor<'_> {
     /// Parses a token from the input string.
     pub fn advance_token(&mut self) -> Token {
@@ -512,7 +530,12 @@ pub fn advance_token(&mut self) -> Token {
             c @ '0'..='9' => {
                 let literal_kind = self.number(c);
                 let suffix_start = self.pos_within_token();
+                let rest = self.as_str();
                 self.eat_literal_suffix();
+                if matches!(self.script_mode, ScriptMode::Enabled) {
+                    let suffix = &rest[..(self.pos_within_token() - suffix_start) as usize];
+                    self.eat_unit_suffix_tail(suffix);
+                }
                 TokenKind::Literal { kind: literal_kind, suffix_start }
             }
 
@@ -555,7 +578,10 @@ pub fn advance_token(&mut self) -> Token {
 
             // String literal.
             '"' => {
-                let terminated = self.double_quoted_string();
+                let terminated = match self.script_mode {
+                    ScriptMode::Enabled => self.script_double_quoted_string(),
+                    ScriptMode::Disabled => self.double_quoted_string(),
+                };
                 let suffix_start = self.pos_within_token();
                 if terminated {
                     self.eat_literal_suffix();
@@ -1032,6 +1058,69 @@ fn double_quoted_string(&mut self) -> bool {
         false
     }
 
+    /// Eats a script-mode string literal and returns true if it is terminated.
+    /// Unlike [`Self::double_quoted_string`], quotes inside a `${ ... }`
+    /// placeholder belong to the placeholder's code, so `"${"abc".len()}"` is
+    /// one literal.
+    fn script_double_quoted_string(&mut self) -> bool {
+        debug_assert!(self.prev() == '"');
+        while let Some(c) = self.bump() {
+            match c {
+                '"' => {
+                    return true;
+                }
+                '\\' if self.first() == '\\' || self.first() == '"' => {
+                    // Bump again to skip escaped character.
+                    self.bump();
+                }
+                '$' if self.first() == '{' => {
+                    self.bump();
+                    if !self.script_placeholder() {
+                        return false;
+                    }
+                }
+                _ => (),
+            }
+        }
+        // End of file reached.
+        false
+    }
+
+    /// Eats the code of a `${ ... }` placeholder up to and including its
+    /// closing brace. Returns false if the input ends first.
+    fn script_placeholder(&mut self) -> bool {
+        let mut depth = 1usize;
+        while let Some(c) = self.bump() {
+            match c {
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return true;
+                    }
+                }
+                '"' if !self.script_double_quoted_string() => {
+                    return false;
+                }
+                // Character literals such as `'}'` or `'"'`; lifetimes fall through.
+                '\'' if self.first() == '\\' => {
+                    self.bump();
+                    self.bump();
+                    self.eat_while(|c| c != '\'' && c != '\n');
+                    if self.first() == '\'' {
+                        self.bump();
+                    }
+                }
+                '\'' if self.second() == '\'' => {
+                    self.bump();
+                    self.bump();
+                }
+                _ => (),
+            }
+        }
+        false
+    }
+
     /// Attempt to lex for a guarded string literal.
     ///
     /// Used by `rustc_parse::lexer` to lex for guarded strings
@@ -1217,6 +1306,27 @@ fn eat_literal_suffix(&mut self) {
         self.eat_while(|c| c.is_ascii_alphanumeric() || c == '_');
     }
 
+    /// Script mode: a unit suffix continues with `²`/`³` and further units joined
+    /// by `·` or `/`, so `5m/s²` lexes as one literal with suffix `m/s²`.
+    /// Type suffixes (`5u8/x`) keep their usual meaning.
+    fn eat_unit_suffix_tail(&mut self, suffix: &str) {
+        if suffix.is_empty() || is_numeric_type_suffix(suffix) {
+            return;
+        }
+        loop {
+            match self.first() {
+                '²' | '³' => {
+                    self.bump();
+                }
+                '·' | '/' if self.second().is_ascii_alphabetic() => {
+                    self.bump();
+                    self.eat_literal_suffix();
+                }
+                _ => break,
+            }
+        }
+    }
+
     // Eats the identifier. Note: succeeds on `_`, which isn't a valid
     // identifier.
     fn eat_identifier(&mut self) {
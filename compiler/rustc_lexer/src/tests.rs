@@ -125,8 +125,9 @@ fn test_valid_shebang() {
 }
 
 fn check_lexing(src: &str, frontmatter_allowed: FrontmatterAllowed, expect: Expect) {
-    let actual: String =
-        tokenize(src, frontmatter_allowed).map(|token| format!("{:?}\n", token)).collect();
+    let actual: String = tokenize(src, frontmatter_allowed, ScriptMode::Disabled)
+        .map(|token| format!("{:?}\n", token))
+        .collect();
     expect.assert_eq(&actual)
 }
 
@@ -247,6 +248,28 @@ fn raw_string() {
     )
 }
 
+#[test]
+fn script_string_placeholders() {
+    let lex = |src| {
+        tokenize(src, FrontmatterAllowed::No, ScriptMode::Enabled)
+            .map(|token| format!("{:?}\n", token))
+            .collect::<String>()
+    };
+    expect![[r#"
+        Token { kind: Literal { kind: Str { terminated: true }, suffix_start: 20 }, len: 20 }
+    "#]]
+    .assert_eq(&lex(r#""len ${"abc".len()}""#));
+    expect![[r#"
+        Token { kind: Literal { kind: Str { terminated: true }, suffix_start: 43 }, len: 43 }
+    "#]]
+    .assert_eq(&lex(r#""${if c { "even" } else { "odd}" }} ${'}'}""#));
+    expect![[r#"
+        Token { kind: Literal { kind: Str { terminated: false }, suffix_start: 10 }, len: 10 }
+    "#]]
+    .assert_eq(&lex(r#""${"abc"; "#));
+    assert_eq!(script_placeholder_end(r#"f("}") } rest"#), Some(7));
+}
+
 #[test]
 fn literal_suffixes() {
     check_lexing(
@@ -1,6 +1,6 @@
 use itertools::Itertools;
 use rustc_abi::{FIRST_VARIANT, FieldIdx, Size, VariantIdx};
-use rustc_ast::UnsafeBinderCastKind;
+use rustc_ast::{self as ast, UnsafeBinderCastKind};
 use rustc_data_structures::stack::ensure_sufficient_stack;
 use rustc_hir as hir;
 use rustc_hir::attrs::AttributeKind;
@@ -568,13 +568,12 @@ fn make_mirror_unadjusted(&mut self, expr: &'tcx hir::Expr<'tcx>) -> Expr<'tcx>
             hir::ExprKind::Lit(lit) => ExprKind::Literal { lit, neg: false },
 
             hir::ExprKind::Binary(op, lhs, rhs) => {
-                // Special handling for `in` operator: `a in b` -> `b.contains(&a)`
+                // Special handling for `in` operator: `a in b` -> `Contains::contains(&b, &a)`
                 if op.node == hir::BinOpKind::In {
-                    // The `in` operator is desugared to a method call on the RHS
-                    // This should have been recorded as a method call during type checking
+                    // Type checking recorded either `Contains::contains` or the
+                    // `iter_contains` lang item, both taking the RHS first
                     if self.typeck_results.is_method_call(expr) {
                         // lhs = what we're looking for, rhs = the collection
-                        // For `a in b` -> `b.contains(&a)`, the receiver is rhs
                         let rhs_mirror = self.mirror_expr(rhs);
                         let lhs_mirror = self.mirror_expr(lhs);
                         self.overloaded_operator(expr, Box::new([rhs_mirror, lhs_mirror]))
@@ -1118,6 +1117,21 @@ fn local(
                     ExprKind::Loop { body }
                 }
             }
+            hir::ExprKind::Field(source, ident) if self.typeck_results.is_method_call(expr) => {
+                // Script mode `m.key` on a map: `*MapField::__map_field(&m, "key")`.
+                let lhs = self.mirror_expr(source);
+                let lit = self.tcx.arena.alloc(hir::Lit {
+                    node: ast::LitKind::Str(ident.name, ast::StrStyle::Cooked),
+                    span: ident.span,
+                });
+                let key = self.thir.exprs.push(Expr {
+                    temp_scope_id: expr.hir_id.local_id,
+                    ty: Ty::new_static_str(self.tcx),
+                    span: ident.span,
+                    kind: ExprKind::Literal { lit, neg: false },
+                });
+                self.overloaded_place(expr, expr_ty, None, Box::new([lhs, key]), expr.span)
+            }
             hir::ExprKind::Field(source, ..) => ExprKind::Field {
                 lhs: self.mirror_expr(source),
                 variant_index: FIRST_VARIANT,
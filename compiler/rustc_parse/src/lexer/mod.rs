@@ -1,3 +1,5 @@
+use std::ops::Range;
+
 use diagnostics::make_errors_for_mismatched_closing_delims;
 use rustc_ast::ast::{self, AttrStyle};
 use rustc_ast::token::{self, CommentKind, Delimiter, IdentIsRaw, Token, TokenKind};
@@ -453,6 +455,7 @@ fn next_token_from_cursor(&mut self) -> (Token, bool) {
                         '≠' => Some(token::Ne),  // U+2260 NOT EQUAL TO
                         '≈' => Some(token::ApproxEq),  // U+2248 ALMOST EQUAL TO
                         '…' => Some(token::DotDotEq), // U+2026 HORIZONTAL ELLIPSIS (inclusive range)
+                        '·' => Some(token::Star),  // U+00B7 MIDDLE DOT (multiplication: `500·ms`)
                         _ => None,
                     } {
                         tok
@@ -1189,7 +1192,16 @@ fn cook_quoted(
         let content_start = start + BytePos(prefix_len);
         let content_end = end - BytePos(postfix_len);
         let lit_content = self.str_from_to(content_start, content_end);
+        // Script-mode `${ ... }` placeholders hold code, not string contents; the
+        // parser lexes them on their own when it interpolates the literal.
+        let placeholders = match (self.script_mode, mode) {
+            (ScriptMode::Enabled, Mode::Str) => script_placeholders(lit_content),
+            _ => Vec::new(),
+        };
         check_for_errors(lit_content, mode, |range, err| {
+            if placeholders.iter().any(|placeholder| placeholder.contains(&range.start)) {
+                return;
+            }
             let span_with_quotes = self.mk_sp(start, end);
             let (start, end) = (range.start as u32, range.end as u32);
             let lo = content_start + BytePos(start);
@@ -1272,6 +1284,20 @@ fn try_curly_quoted_literal(
     }
 }
 
+/// Byte ranges of the `${ ... }` placeholders in the contents of a script-mode
+/// string literal, matching how `rustc_lexer` skipped over them.
+fn script_placeholders(content: &str) -> Vec<Range<usize>> {
+    let mut placeholders = Vec::new();
+    let mut rest = 0;
+    while let Some(found) = content[rest..].find("${") {
+        let start = rest + found + 2;
+        let Some(end) = rustc_lexer::script_placeholder_end(&content[start..]) else { break };
+        placeholders.push(start..start + end);
+        rest = start + end + 1;
+    }
+    placeholders
+}
+
 pub fn nfc_normalize(string: &str) -> Symbol {
     use unicode_normalization::{IsNormalized, UnicodeNormalization, is_nfc_quick};
     match is_nfc_quick(string.chars()) {
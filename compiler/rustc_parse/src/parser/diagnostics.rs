@@ -1028,14 +1028,11 @@ pub(super) fn maybe_suggest_struct_literal(
 
                         // Helper to wrap value in Val::from(...)
                         let mk_val_from = |this: &Self, value: Box<Expr>, sp: Span| -> Box<Expr> {
-                            let val_from_path = Path {
-                                span: sp,
-                                segments: thin_vec![
-                                    PathSegment::from_ident(Ident::new(sym::Val, sp)),
-                                    PathSegment::from_ident(Ident::new(sym::from, sp)),
-                                ],
-                                tokens: None,
-                            };
+                            let mut val_from_path =
+                                crate::transformer::script_helper_path(sym::Val, sp);
+                            val_from_path
+                                .segments
+                                .push(PathSegment::from_ident(Ident::new(sym::from, sp)));
                             let path_expr = this.mk_expr(sp, ExprKind::Path(None, val_from_path));
                             this.mk_expr(sp, ExprKind::Call(path_expr, thin_vec![value]))
                         };
@@ -19,6 +19,7 @@
     StmtKind, Ty, TyKind, UnOp, UnsafeBinderCastKind, YieldKind,
 };
 use rustc_data_structures::stack::ensure_sufficient_stack;
+use rustc_data_structures::sync::Lock;
 use rustc_errors::{Applicability, Diag, PResult, StashKey, Subdiagnostic};
 use rustc_literal_escaper::unescape_char;
 use rustc_macros::Subdiagnostic;
@@ -38,6 +39,7 @@
     AttrWrapper, BlockMode, ClosureSpans, ExpTokenPair, ForceCollect, Parser, PathStyle,
     Restrictions, SemiColonMode, SeqSep, TokenType, Trailing, UsePreAttrPos,
 };
+use crate::transformer::{script_helper_path, script_helper_path_tokens, units};
 use crate::{errors, exp, maybe_recover_from_interpolated_ty_qpath};
 
 #[derive(Debug)]
@@ -52,6 +54,29 @@ pub(super) enum DestructuredFloat {
     Error,
 }
 
+/// The `map[K]V` header of a script-mode Go-style map literal.
+struct GoMapTy {
+    key: Box<Ty>,
+    value: GoMapValue,
+    span: Span,
+}
+
+enum GoMapValue {
+    /// `map[K]any`: values are wrapped in `Val`.
+    Any,
+    /// `map[K]map[K2]V2`
+    Map(Box<GoMapTy>),
+    Ty(Box<Ty>),
+}
+
+impl GoMapTy {
+    /// `string` is the script alias for `&str`.
+    fn key_is_string(&self) -> bool {
+        matches!(&self.key.kind, TyKind::Path(None, path)
+            if path.segments.len() == 1 && path.segments[0].ident.name == sym::string)
+    }
+}
+
 impl<'a> Parser<'a> {
     /// Parses an expression.
     #[inline]
@@ -195,10 +220,18 @@ pub(super) fn parse_expr_assoc_rest_with(
             if op.node == AssocOp::Binary(BinOpKind::Pow) {
                 self.bump();
             }
+            // Pipe operator `|>` consumes two tokens
+            if op.node == AssocOp::Pipe {
+                self.bump();
+            }
             // Null coalescing operator `??` consumes two tokens
             if op.node == AssocOp::NullCoalesce {
                 self.bump();
             }
+            // `not in` consumes two tokens
+            if op.node == AssocOp::NotIn {
+                self.bump();
+            }
             if op.node.is_comparison() {
                 if let Some(expr) = self.check_no_chained_comparison(&lhs, &op)? {
                     return Ok((expr, parsed_something));
@@ -282,6 +315,12 @@ pub(super) fn parse_expr_assoc_rest_with(
 
             let op_span = op.span;
             let op = op.node;
+            // Scripts have `in` and `not in`; other crates need `#![feature(in_operator)]`.
+            if matches!(op, AssocOp::Binary(BinOpKind::In) | AssocOp::NotIn)
+                && !self.is_script_mode()
+            {
+                self.psess.gated_spans.gate(sym::in_operator, op_span);
+            }
             // Special cases:
             if op == AssocOp::Cast {
                 lhs = self.parse_assoc_op_cast(lhs, lhs_span, op_span, ExprKind::Cast)?;
@@ -323,6 +362,19 @@ pub(super) fn parse_expr_assoc_rest_with(
                         // Transform `a and b` to `if (&a).is_truthy() { b } else { a }`
                         // Python-style truthy and: returns first falsy value or last value
                         self.mk_truthy_and_expr(lhs, rhs, span)
+                    } else if self.is_script_mode()
+                        && matches!(ast_op, BinOpKind::Eq | BinOpKind::Ne)
+                        && self.prev_token.is_ident_named(sym::nil)
+                        && rhs.span == self.prev_token.span
+                    {
+                        // Transform `x == nil` to `(&x).is_nil()`, so that Go's `err != nil`
+                        // works on the result of a function returning `error`, not just on options
+                        let is_nil = self.wrap_expr_with_method(lhs, sym::is_nil);
+                        if ast_op == BinOpKind::Ne {
+                            self.mk_expr(span, self.mk_unary(UnOp::Not, is_nil))
+                        } else {
+                            is_nil
+                        }
                     } else {
                         let binary = self.mk_binary(source_map::respan(cur_op_span, ast_op), lhs, rhs);
                         self.mk_expr(span, binary)
@@ -336,9 +388,20 @@ pub(super) fn parse_expr_assoc_rest_with(
                 AssocOp::NullCoalesce => {
                     self.mk_expr(span, ExprKind::NullCoalesce(lhs, rhs))
                 }
+                AssocOp::Pipe => {
+                    // Transform `x |> f` into `f(x)`; works for any callable, closures included
+                    self.mk_expr(span, ExprKind::Call(rhs, thin_vec![lhs]))
+                }
+                AssocOp::NotIn => {
+                    // Transform `a not in b` into `!(a in b)`
+                    let binary =
+                        self.mk_binary(source_map::respan(op_span, BinOpKind::In), lhs, rhs);
+                    let contains = self.mk_expr(span, binary);
+                    self.mk_expr(span, self.mk_unary(UnOp::Not, contains))
+                }
                 AssocOp::ApproxEq => {
                     // Transform `a ≈ b` into `approx_eq(a, b)` function call
-                    let fn_path = ast::Path::from_ident(Ident::new(sym::approx_eq, cur_op_span));
+                    let fn_path = script_helper_path(sym::approx_eq, cur_op_span);
                     let fn_expr = self.mk_expr(cur_op_span, ExprKind::Path(None, fn_path));
                     let args = thin_vec![lhs, rhs];
                     self.mk_expr(span, ExprKind::Call(fn_expr, args))
@@ -424,7 +487,8 @@ fn error_found_expr_would_be_stmt(&self, lhs: &Expr) {
     /// Possibly translate the current token to an associative operator.
     /// The method does not advance the current token.
     ///
-    /// Also accepts `and` / `or` as aliases for `&&` and `||` respectively (C++ style).
+    /// Also accepts `and` / `or` as aliases for `&&` and `||` respectively (C++ style),
+    /// and `not in` for negated containment (Python style).
     pub(super) fn check_assoc_op(&self) -> Option<Spanned<AssocOp>> {
         let (op, span) = match (AssocOp::from_token(&self.token), self.token.ident()) {
             // When parsing const expressions, stop parsing when encountering `>`.
@@ -456,6 +520,15 @@ pub(super) fn check_assoc_op(&self) -> Option<Spanned<AssocOp>> {
             {
                 (AssocOp::Binary(BinOpKind::Pow), self.token.span.to(self.look_ahead(1, |t| t.span)))
             }
+            // Pipe operator: `x |> f` (script mode), `|` and `>` written without a gap
+            (Some(AssocOp::Binary(BinOpKind::BitOr)), _)
+                if self.is_script_mode()
+                    && self.look_ahead(1, |t| {
+                        t.kind == token::Gt && t.span.lo() == self.token.span.hi()
+                    }) =>
+            {
+                (AssocOp::Pipe, self.token.span.to(self.look_ahead(1, |t| t.span)))
+            }
             // Null coalescing operator: `??`
             // Only match when followed by an expression (to distinguish from `foo??` = two try operators)
             (None, _)
@@ -478,6 +551,12 @@ pub(super) fn check_assoc_op(&self) -> Option<Spanned<AssocOp>> {
             (None, Some((Ident { name: sym::xor, span }, IdentIsRaw::No))) => {
                 (AssocOp::Binary(BinOpKind::BitXor), span)
             }
+            // Python style: `not in`
+            (None, Some((Ident { name: sym::not, span }, IdentIsRaw::No)))
+                if self.look_ahead(1, |t| t.is_keyword(kw::In)) =>
+            {
+                (AssocOp::NotIn, span.to(self.look_ahead(1, |t| t.span)))
+            }
             _ => return None,
         };
         Some(source_map::respan(span, op))
@@ -1506,8 +1585,49 @@ fn mk_expr_tuple_field_access(
         self.mk_expr(lo.to(ident_span), ExprKind::Field(base, Ident::new(field, ident_span)))
     }
 
+    /// Script mode: record a binding named like an `import fn`, so that calls of that name in
+    /// its scope stay plain calls of the local. Scopes end with a `ScriptScope`.
+    pub(super) fn note_script_binding(&self, ident: Ident) {
+        if self.is_script_mode() && self.psess.script_import_fns.borrow().contains(&ident.name) {
+            self.psess.script_import_fn_locals.borrow_mut().push(ident.name);
+        }
+    }
+
+    /// A scope for `note_script_binding`: bindings noted while it is alive are forgotten when
+    /// it is dropped.
+    pub(super) fn script_scope(&self) -> ScriptScope<'a> {
+        let psess = self.psess;
+        let locals = &psess.script_import_fn_locals;
+        ScriptScope { locals, len: locals.borrow().len() }
+    }
+
     /// Parse a function call expression, `expr(...)`.
     fn parse_expr_fn_call(&mut self, lo: Span, fun: Box<Expr>) -> Box<Expr> {
+        // Script mode: a call of an `import fn` with strings, variadics or callbacks is a call of
+        // the macro that converts its arguments, `name(..)` becoming `__import_name::call!(..)`.
+        // A local of the same name shadows the import, see `note_script_binding`.
+        if self.is_script_mode()
+            && let ExprKind::Path(None, path) = &fun.kind
+            && let [segment] = &path.segments[..]
+            && segment.args.is_none()
+            && self.psess.script_import_fns.borrow().contains(&segment.ident.name)
+            && !self.psess.script_import_fn_locals.borrow().contains(&segment.ident.name)
+        {
+            let ident = segment.ident;
+            let module = Ident::from_str_and_span(&format!("__import_{ident}"), ident.span);
+            let mut path = Path::from_ident(module);
+            path.segments.push(PathSegment::from_ident(Ident::new(sym::call, ident.span)));
+            return match self.parse_delim_args() {
+                Ok(args) => {
+                    let mac = Box::new(MacCall { path, args });
+                    self.mk_expr(lo.to(self.prev_token.span), ExprKind::MacCall(mac))
+                }
+                Err(err) => {
+                    self.recover_seq_parse_error(exp!(OpenParen), exp!(CloseParen), lo, err)
+                }
+            };
+        }
+
         let snapshot = if self.token == token::OpenParen {
             Some((self.create_snapshot_for_diagnostic(), fun.kind.clone()))
         } else {
@@ -1837,7 +1957,22 @@ fn parse_expr_bottom(&mut self) -> PResult<'a, Box<Expr>> {
                 // This match arm is a special-case of the `_` match arm below and
                 // could be removed without changing functionality, but it's faster
                 // to have it here, especially for programs with large constants.
-                this.parse_expr_lit()
+                let lit = this.parse_expr_lit()?;
+                if this.is_script_mode() && !lo.from_expansion() {
+                    // `500ms` -> 500f64 * crate::__script_helpers::units::MilliSecond
+                    let lit = this.maybe_unit_quantity(lit);
+                    let juxtaposed = this.is_juxtaposition_after(&lit);
+                    // `"hi {name}"` -> format!("hi {name}")
+                    let lit = this.maybe_interpolate_str_lit(lit)?;
+                    if juxtaposed {
+                        // `"value:" 42 "units"` -> "value: 42 units"
+                        this.parse_juxtaposed_concat(lit)
+                    } else {
+                        Ok(lit)
+                    }
+                } else {
+                    Ok(lit)
+                }
             } else if this.is_script_mode() && this.token == token::OpenParen && this.is_arrow_function() {
                 // JS-style arrow function with parens: (x, y) => expr
                 // Must check BEFORE tuple/paren expression parsing
@@ -1876,6 +2011,12 @@ fn parse_expr_bottom(&mut self) -> PResult<'a, Box<Expr>> {
                 // with match guards: `_ if x => result` looks like `x => expr`.
                 // No way to disambiguate without deep context tracking.
                 this.parse_arrow_function()
+            } else if this.is_script_mode()
+                && this.token.is_ident_named(sym::map)
+                && this.look_ahead(1, |t| *t == token::OpenBracket)
+            {
+                // Go-style `map[string]int{"a": 1}`
+                this.parse_expr_go_map_or_path()
             } else if this.check_path() {
                 this.parse_expr_path_start()
             } else if this.check_keyword(exp!(Move))
@@ -1918,6 +2059,10 @@ fn parse_expr_bottom(&mut self) -> PResult<'a, Box<Expr>> {
                 this.parse_const_block(lo)
             } else if this.may_recover() && this.is_do_catch_block() {
                 this.recover_do_catch()
+            } else if this.is_script_mode() && this.is_script_try_catch() {
+                // Script-mode `try { } catch err { } finally { }`
+                this.expect_keyword(exp!(Try))?;
+                this.parse_script_try_catch(lo)
             } else if this.is_try_block() {
                 this.expect_keyword(exp!(Try))?;
                 this.parse_try_block(lo)
@@ -1975,6 +2120,271 @@ fn parse_expr_lit(&mut self) -> PResult<'a, Box<Expr>> {
         }
     }
 
+    /// Script mode: a numeric literal with a unit suffix becomes a `units::Quantity`.
+    /// Compound suffixes multiply and divide unit constants left to right:
+    /// `5m/s²` -> `5f64 * units::Meter / units::Second / units::Second`, the units being
+    /// reached through `crate::__script_helpers`.
+    fn maybe_unit_quantity(&self, expr: Box<Expr>) -> Box<Expr> {
+        let ExprKind::Lit(lit) = &expr.kind else { return expr };
+        let lit = *lit;
+        let Some(suffix) = lit.suffix else { return expr };
+        if !matches!(lit.kind, token::Integer | token::Float) || !units::is_unit_suffix(suffix.as_str()) {
+            return expr;
+        }
+        let span = expr.span;
+        let factors = match units::parse_unit_suffix(suffix.as_str()) {
+            Ok(factors) => factors,
+            Err(unknown) => {
+                let guar = self
+                    .dcx()
+                    .struct_span_err(span, format!("unknown unit `{unknown}` in literal suffix `{suffix}`"))
+                    .emit();
+                return self.mk_expr_err(span, guar);
+            }
+        };
+        let value = token::Lit::new(lit.kind, lit.symbol, Some(sym::f64));
+        let mut quantity = self.mk_expr(span, ExprKind::Lit(value));
+        for factor in factors {
+            let mut path = script_helper_path(sym::units, span);
+            path.segments.push(PathSegment::from_ident(Ident::from_str_and_span(factor.name, span)));
+            let op = if factor.divide { BinOpKind::Div } else { BinOpKind::Mul };
+            for _ in 0..factor.power {
+                let unit = self.mk_expr(span, ExprKind::Path(None, path.clone()));
+                let binop = BinOp { node: op, span };
+                quantity = self.mk_expr(span, ExprKind::Binary(binop, quantity, unit));
+            }
+        }
+        quantity
+    }
+
+    fn is_str_lit_token(&self) -> bool {
+        matches!(
+            self.token.kind,
+            token::Literal(token::Lit { kind: token::Str | token::StrRaw(_), .. })
+        )
+    }
+
+    /// Script mode: a string literal directly followed (on the same line) by another
+    /// operand starts a juxtaposition concatenation: `"left" x "right"`.
+    fn is_juxtaposition_after(&self, lhs: &Expr) -> bool {
+        let is_str_lit = matches!(&lhs.kind, ExprKind::Lit(lit)
+            if matches!(lit.kind, token::LitKind::Str | token::LitKind::StrRaw(_)));
+        is_str_lit && self.can_continue_juxtaposition()
+    }
+
+    /// Whether the current token is a further juxtaposed operand on the same line.
+    fn can_continue_juxtaposition(&self) -> bool {
+        if self.can_infer_semi_from_newline() {
+            return false;
+        }
+        match self.token.kind {
+            token::Literal(_) | token::OpenParen => true,
+            // Plain identifiers, but not operator words: `"a" or b`, `"a" is string`
+            _ => match self.token.ident() {
+                Some((ident, IdentIsRaw::No)) => {
+                    !ident.is_reserved()
+                        && !matches!(ident.name, sym::or | sym::and | sym::xor | sym::not | sym::is)
+                }
+                Some((_, IdentIsRaw::Yes)) => true,
+                None => false,
+            },
+        }
+    }
+
+    /// Parses the rest of a juxtaposition `"value:" 42 "units"` after its first string literal.
+    ///
+    /// Operands are joined with a single space, unless the string literal on either side of
+    /// the gap already has whitespace there. Lowers through `String + &str`:
+    /// `String::from("value:") + " " + &42.to_string() + " " + "units"`.
+    /// String literals are appended as they are and interpolated literals (`format!`) are
+    /// borrowed; every other operand goes through `to_string()`, so `&str`, numbers and
+    /// anything `Display` work, and variables stay usable afterwards.
+    fn parse_juxtaposed_concat(&mut self, first: Box<Expr>) -> PResult<'a, Box<Expr>> {
+        let lit_str = |e: &Expr| match &e.kind {
+            ExprKind::Lit(lit) if matches!(lit.kind, token::LitKind::Str | token::LitKind::StrRaw(_)) => {
+                Some(lit.symbol)
+            }
+            _ => None,
+        };
+
+        let mut parts = vec![first];
+        loop {
+            let part = if self.is_str_lit_token() {
+                // Parse string operands directly so they don't start a nested juxtaposition
+                let lit = self.parse_expr_lit()?;
+                let lit = self.maybe_interpolate_str_lit(lit)?;
+                let span = lit.span;
+                self.parse_expr_dot_or_call_with(AttrVec::new(), lit, span)?
+            } else {
+                self.parse_expr_prefix(AttrWrapper::empty())?
+            };
+            parts.push(part);
+            if !self.can_continue_juxtaposition() {
+                break;
+            }
+        }
+
+        let mut parts = parts.into_iter();
+        let first = parts.next().unwrap();
+        let lo = first.span;
+        let mut prev_text = lit_str(&first);
+        let mut acc = self.wrap_in_string_from(first);
+        for part in parts {
+            let span = lo.to(part.span);
+            let text = lit_str(&part);
+            let left_has_space = prev_text.is_some_and(|s| s.as_str().ends_with(char::is_whitespace));
+            let right_has_space = text.is_some_and(|s| s.as_str().starts_with(char::is_whitespace));
+            if !left_has_space && !right_has_space {
+                let gap = part.span.shrink_to_lo();
+                let space_lit = token::Lit::new(token::Str, Symbol::intern(" "), None);
+                let space = self.mk_expr(gap, ExprKind::Lit(space_lit));
+                let binop = BinOp { node: BinOpKind::Add, span: gap };
+                acc = self.mk_expr(span, ExprKind::Binary(binop, acc, space));
+            }
+            let rhs = match &part.kind {
+                _ if text.is_some() => part,
+                ExprKind::MacCall(mac) if mac.path == sym::format => self.wrap_in_borrow(part),
+                _ => self.wrap_in_borrow(self.wrap_in_to_string(part)),
+            };
+            let binop = BinOp { node: BinOpKind::Add, span: rhs.span.shrink_to_lo() };
+            acc = self.mk_expr(span, ExprKind::Binary(binop, acc, rhs));
+            prev_text = text;
+        }
+        Ok(acc)
+    }
+
+    /// Script mode string interpolation: `"hi {name}, sum ${a + b}"` becomes
+    /// `format!("hi {name}, sum {}", a + b)`.
+    ///
+    /// `{name}` placeholders (with optional format spec, `{x:.2}`) are left to `format!`'s
+    /// implicit captures; each `${expr}` is replaced by `{}` and passed as a positional argument,
+    /// lexed in place so errors inside the placeholder point into the literal. `{{` and `}}`
+    /// escape braces. Literals that aren't valid format strings (`"{\"a\": 1}"`), or that
+    /// contain their own `{}` / `{0}` placeholders, are left untouched.
+    fn maybe_interpolate_str_lit(&mut self, expr: Box<Expr>) -> PResult<'a, Box<Expr>> {
+        use rustc_ast::tokenstream::{DelimSpan, TokenStream};
+        use rustc_parse_format as parse_format;
+
+        let ExprKind::Lit(lit) = &expr.kind else { return Ok(expr) };
+        let (raw_hashes, quote_len) = match lit.kind {
+            token::LitKind::Str => (None, 1),
+            token::LitKind::StrRaw(n) => (Some(n as usize), n as usize + 2),
+            _ => return Ok(expr),
+        };
+        if lit.suffix.is_some() || !lit.symbol.as_str().contains(['{', '}']) {
+            return Ok(expr);
+        }
+        let text = lit.symbol.as_str().to_string();
+        let lit_kind = lit.kind;
+        let span = expr.span;
+        // Source position of the first character inside the quotes
+        let content_start = span.lo() + BytePos(quote_len as u32);
+
+        // Replace each `${expr}` by `{}`, remembering where the expression source lives
+        let mut fmt = String::with_capacity(text.len());
+        let mut placeholders = Vec::new();
+        let mut rest = 0;
+        while let Some(found) = text[rest..].find("${") {
+            let start = rest + found;
+            let expr_start = start + 2;
+            // Same scan the lexer used, so `}` inside nested literals doesn't end the placeholder
+            let end = rustc_lexer::script_placeholder_end(&text[expr_start..]);
+            let Some(end) = end.map(|end| expr_start + end) else {
+                let lo = content_start + BytePos(start as u32);
+                return Err(self.dcx().struct_span_err(
+                    span.with_lo(lo).with_hi(lo + BytePos(2)),
+                    "unterminated `${` placeholder in string literal",
+                ));
+            };
+            fmt.push_str(&text[rest..start]);
+            fmt.push_str("{}");
+            placeholders.push(expr_start..end);
+            rest = end + 1;
+        }
+        fmt.push_str(&text[rest..]);
+
+        // Validate with the `format_args!` parser; anything it rejects stays a plain literal
+        let mut parser =
+            parse_format::Parser::new(&fmt, raw_hashes, None, false, parse_format::ParseMode::Format);
+        let mut implicit_args = 0;
+        let mut named_args = 0;
+        let mut other_args = 0;
+        for piece in &mut parser {
+            if let parse_format::Piece::NextArgument(arg) = piece {
+                match arg.position {
+                    parse_format::Position::ArgumentImplicitlyIs(_) => implicit_args += 1,
+                    parse_format::Position::ArgumentNamed(_) => named_args += 1,
+                    parse_format::Position::ArgumentIs(_) => other_args += 1,
+                }
+            }
+        }
+        if !parser.errors.is_empty() || other_args > 0 || implicit_args != placeholders.len() {
+            if placeholders.is_empty() {
+                return Ok(expr);
+            }
+            // The placeholders' code can't stay inside a plain literal
+            let msg = "string literal with `${}` placeholders is not a valid format string";
+            return Err(self.dcx().struct_span_err(span, msg).with_help(
+                "use `{{` and `}}` for literal braces, and `${expr}` or `{name}` for values",
+            ));
+        }
+        let new_lit = |symbol: &str| token::Lit::new(lit_kind, Symbol::intern(symbol), None);
+        if placeholders.is_empty() && named_args == 0 {
+            // Only `{{` / `}}` escapes: unescape in place, no formatting needed
+            let unescaped = fmt.replace("{{", "{").replace("}}", "}");
+            return Ok(self.mk_expr(span, ExprKind::Lit(new_lit(&unescaped))));
+        }
+
+        // format!(<fmt>, <expr>, ...)
+        let mut tokens = vec![TokenTree::token_alone(token::Literal(new_lit(&fmt)), span)];
+        for range in placeholders {
+            let src = &text[range.clone()];
+            let lo = content_start + BytePos(range.start as u32);
+            let stream = match crate::lexer::lex_token_trees(
+                self.psess,
+                src,
+                lo,
+                None,
+                crate::lexer::StripTokens::Nothing,
+            ) {
+                Ok(stream) => stream,
+                Err(errs) => {
+                    let mut errs = errs.into_iter();
+                    let first = errs.next().expect("lexer failure without a diagnostic");
+                    for err in errs {
+                        err.emit();
+                    }
+                    return Err(first);
+                }
+            };
+            let expr_span = span.with_lo(lo).with_hi(lo + BytePos(src.len() as u32));
+            if stream.is_empty() {
+                return Err(self
+                    .dcx()
+                    .struct_span_err(expr_span, "expected an expression inside `${}`"));
+            }
+            // Make sure the placeholder holds exactly one expression
+            let mut expr_parser = Parser::new(self.psess, stream.clone(), None);
+            expr_parser.parse_expr()?;
+            if expr_parser.token != token::Eof {
+                return Err(self.dcx().struct_span_err(
+                    expr_parser.token.span,
+                    "expected a single expression inside `${}`",
+                ));
+            }
+            tokens.push(TokenTree::token_alone(token::Comma, expr_span));
+            tokens.extend(stream.iter().cloned());
+        }
+
+        let args = Box::new(ast::DelimArgs {
+            dspan: DelimSpan::from_single(span),
+            delim: Delimiter::Parenthesis,
+            tokens: TokenStream::new(tokens),
+        });
+        let path = Path::from_ident(Ident::new(sym::format, span));
+        Ok(self.mk_expr(span, ExprKind::MacCall(Box::new(MacCall { path, args }))))
+    }
+
     fn parse_expr_tuple_parens(&mut self, restrictions: Restrictions) -> PResult<'a, Box<Expr>> {
         let lo = self.token.span;
         self.expect(exp!(OpenParen))?;
@@ -2132,6 +2542,230 @@ fn parse_expr_at_map(&mut self) -> PResult<'a, Box<Expr>> {
         Ok(call)
     }
 
+    /// Script mode: `map[...]` is either a Go-style map literal or plain indexing of a
+    /// variable called `map`. Only a literal header followed by `{` is parsed as a literal.
+    fn parse_expr_go_map_or_path(&mut self) -> PResult<'a, Box<Expr>> {
+        if self.is_go_map_literal_start() {
+            let map_ty = self.parse_go_map_type()?;
+            self.parse_go_map_body(&map_ty)
+        } else {
+            self.parse_expr_path_start()
+        }
+    }
+
+    /// Whether the tokens ahead read `map[K]V {`, where `V` is a type path or another
+    /// `map[K]V` header. Decided on tokens alone: parsing `map[i] * 2` or `map[*p]` as a
+    /// type would report errors that can't be taken back.
+    fn is_go_map_literal_start(&self) -> bool {
+        // Tokens that can appear inside a type, like `&'a [u8; 4]` or `Vec<(i64, String)>`
+        fn is_type_token(t: &Token) -> bool {
+            t.is_ident()
+                || t.is_lifetime()
+                || matches!(
+                    t.kind,
+                    token::PathSep
+                        | token::Lt
+                        | token::Gt
+                        | token::Shr
+                        | token::Comma
+                        | token::And
+                        | token::Semi
+                        | token::OpenParen
+                        | token::CloseParen
+                        | token::OpenBracket
+                        | token::CloseBracket
+                        | token::Literal(token::Lit { kind: token::Integer, .. })
+                )
+        }
+
+        let mut dist = 0;
+        loop {
+            if !self.look_ahead(dist, |t| t.is_ident_named(sym::map))
+                || !self.look_ahead(dist + 1, |t| *t == token::OpenBracket)
+                || self.look_ahead(dist + 2, |t| *t == token::CloseBracket)
+            {
+                return false;
+            }
+            dist += 2;
+
+            // The key type, up to the matching `]`
+            let mut depth = 0usize;
+            loop {
+                let (kind, is_type) = self.look_ahead(dist, |t| (t.kind, is_type_token(t)));
+                dist += 1;
+                match kind {
+                    token::CloseBracket if depth == 0 => break,
+                    token::OpenBracket | token::OpenParen => depth += 1,
+                    token::CloseBracket | token::CloseParen if depth > 0 => depth -= 1,
+                    token::CloseParen => return false,
+                    _ if is_type => {}
+                    _ => return false,
+                }
+            }
+
+            // A nested map type as the value
+            if self.look_ahead(dist, |t| t.is_ident_named(sym::map))
+                && self.look_ahead(dist + 1, |t| *t == token::OpenBracket)
+            {
+                continue;
+            }
+
+            // The value type, a path like `int`, `std::string::String` or `Vec<i64>`,
+            // and then the `{` of the literal
+            if !self.look_ahead(dist, |t| t.is_non_reserved_ident() || t.is_path_segment_keyword())
+            {
+                return false;
+            }
+            dist += 1;
+            let mut depth = 0usize;
+            loop {
+                let (kind, is_type) = self.look_ahead(dist, |t| (t.kind, is_type_token(t)));
+                dist += 1;
+                match kind {
+                    token::OpenBrace if depth == 0 => return true,
+                    token::Lt => depth += 1,
+                    token::Gt if depth > 0 => depth -= 1,
+                    token::Shr if depth > 1 => depth -= 2,
+                    token::PathSep if depth == 0 => {
+                        if self.look_ahead(dist, |t| t.is_ident()) {
+                            dist += 1;
+                        } else if !self.look_ahead(dist, |t| *t == token::Lt) {
+                            return false;
+                        }
+                    }
+                    _ if depth > 0 && is_type => {}
+                    _ => return false,
+                }
+            }
+        }
+    }
+
+    /// Parse the `map[K]V` header of a Go-style map literal. `V` may itself be a map type.
+    fn parse_go_map_type(&mut self) -> PResult<'a, GoMapTy> {
+        let lo = self.token.span;
+        self.bump(); // `map`
+        self.expect(exp!(OpenBracket))?;
+        let key = self.parse_ty()?;
+        self.expect(exp!(CloseBracket))?;
+        let value = if self.token.is_ident_named(sym::map)
+            && self.look_ahead(1, |t| *t == token::OpenBracket)
+        {
+            GoMapValue::Map(Box::new(self.parse_go_map_type()?))
+        } else {
+            let ty = self.parse_ty()?;
+            let is_any = matches!(&ty.kind, TyKind::Path(None, path)
+                if path.segments.len() == 1 && path.segments[0].ident.name == sym::any);
+            if is_any { GoMapValue::Any } else { GoMapValue::Ty(ty) }
+        };
+        Ok(GoMapTy { key, value, span: lo.to(self.prev_token.span) })
+    }
+
+    /// Parse `{k: v, ...}` after a `map[K]V` header and build
+    /// `::std::collections::HashMap::<K, V>::from([(k, v), ...])`.
+    /// Values of a `map[K]any` are wrapped in `Val::from` and its `string` keys become
+    /// `String`s. When `V` is a map type, values may drop the header: `{"db": {"host": "x"}}`.
+    fn parse_go_map_body(&mut self, map_ty: &GoMapTy) -> PResult<'a, Box<Expr>> {
+        let lo = self.token.span;
+        self.expect(exp!(OpenBrace))?;
+        let string_keys = matches!(map_ty.value, GoMapValue::Any) && map_ty.key_is_string();
+        let mut pairs = ThinVec::new();
+        while !self.eat(exp!(CloseBrace)) {
+            let mut key = self.parse_expr()?;
+            self.expect(exp!(Colon))?;
+            let mut value = match &map_ty.value {
+                GoMapValue::Map(inner) if self.check(exp!(OpenBrace)) => {
+                    self.parse_go_map_body(inner)?
+                }
+                _ => self.parse_expr()?,
+            };
+            if string_keys {
+                key = self.wrap_in_string_from(key);
+            }
+            if let GoMapValue::Any = map_ty.value {
+                value = self.wrap_in_val_from(value);
+            }
+            let span = key.span.to(value.span);
+            pairs.push(self.mk_expr(span, ExprKind::Tup(thin_vec![key, value])));
+            if !self.eat(exp!(Comma)) {
+                self.expect(exp!(CloseBrace))?;
+                break;
+            }
+        }
+        let span = lo.to(self.prev_token.span);
+
+        let (key_ty, value_ty) = self.go_map_rust_tys(map_ty);
+        let generic_args = ast::AngleBracketedArgs {
+            span: map_ty.span,
+            args: thin_vec![
+                ast::AngleBracketedArg::Arg(ast::GenericArg::Type(key_ty)),
+                ast::AngleBracketedArg::Arg(ast::GenericArg::Type(value_ty)),
+            ],
+        };
+        let hashmap_from_path = Path {
+            span: map_ty.span,
+            segments: thin_vec![
+                PathSegment::from_ident(Ident::new(kw::PathRoot, map_ty.span)),
+                PathSegment::from_ident(Ident::new(sym::std, map_ty.span)),
+                PathSegment::from_ident(Ident::new(sym::collections, map_ty.span)),
+                PathSegment {
+                    ident: Ident::new(sym::HashMap, map_ty.span),
+                    id: DUMMY_NODE_ID,
+                    args: Some(Box::new(ast::GenericArgs::AngleBracketed(generic_args))),
+                },
+                PathSegment::from_ident(Ident::new(sym::from, map_ty.span)),
+            ],
+            tokens: None,
+        };
+        let path_expr = self.mk_expr(map_ty.span, ExprKind::Path(None, hashmap_from_path));
+        let array_expr = self.mk_expr(span, ExprKind::Array(pairs));
+        Ok(self.mk_expr(map_ty.span.to(span), ExprKind::Call(path_expr, thin_vec![array_expr])))
+    }
+
+    /// The Rust key and value types of a Go-style map: `map[string]any` is
+    /// `HashMap<String, Val>`, nested maps become nested `HashMap`s.
+    fn go_map_rust_tys(&self, map_ty: &GoMapTy) -> (Box<Ty>, Box<Ty>) {
+        let span = map_ty.span;
+        let mk_path_ty = |name: Symbol, args: Option<Box<ast::GenericArgs>>| {
+            let segment = PathSegment { ident: Ident::new(name, span), id: DUMMY_NODE_ID, args };
+            let path = Path { span, segments: thin_vec![segment], tokens: None };
+            self.mk_ty(span, TyKind::Path(None, path))
+        };
+        match &map_ty.value {
+            GoMapValue::Ty(ty) => (map_ty.key.clone(), ty.clone()),
+            GoMapValue::Any => {
+                let key =
+                    if map_ty.key_is_string() { mk_path_ty(sym::String, None) } else { map_ty.key.clone() };
+                (key, self.mk_ty(span, TyKind::Path(None, script_helper_path(sym::Val, span))))
+            }
+            GoMapValue::Map(inner) => {
+                let (inner_key, inner_value) = self.go_map_rust_tys(inner);
+                let args = ast::AngleBracketedArgs {
+                    span,
+                    args: thin_vec![
+                        ast::AngleBracketedArg::Arg(ast::GenericArg::Type(inner_key)),
+                        ast::AngleBracketedArg::Arg(ast::GenericArg::Type(inner_value)),
+                    ],
+                };
+                let hashmap = PathSegment {
+                    ident: Ident::new(sym::HashMap, span),
+                    id: DUMMY_NODE_ID,
+                    args: Some(Box::new(ast::GenericArgs::AngleBracketed(args))),
+                };
+                let path = Path {
+                    span,
+                    segments: thin_vec![
+                        PathSegment::from_ident(Ident::new(kw::PathRoot, span)),
+                        PathSegment::from_ident(Ident::new(sym::std, span)),
+                        PathSegment::from_ident(Ident::new(sym::collections, span)),
+                        hashmap,
+                    ],
+                    tokens: None,
+                };
+                (map_ty.key.clone(), self.mk_ty(span, TyKind::Path(None, path)))
+            }
+        }
+    }
+
     /// Detect if expressions have mixed literal types
     fn detect_mixed_literals(&self, exprs: &[Box<Expr>]) -> bool {
         use rustc_ast::token::LitKind;
@@ -2211,7 +2845,7 @@ fn build_val_wrapped_tokens(&self, exprs: &[Box<Expr>], span: Span) -> rustc_ast
             }
             // Build Val::from(expr)
             // Val
-            tokens.push(TokenTree::token_alone(TokenKind::Ident(sym::Val, IdentIsRaw::No), span));
+            tokens.extend(script_helper_path_tokens(sym::Val, span));
             // ::
             tokens.push(TokenTree::token_alone(TokenKind::PathSep, span));
             // from
@@ -2572,7 +3206,21 @@ fn parse_expr_opt(&mut self) -> PResult<'a, Option<Box<Expr>>> {
     /// Parse `"return" expr?`.
     fn parse_expr_return(&mut self) -> PResult<'a, Box<Expr>> {
         let lo = self.prev_token.span;
-        let kind = ExprKind::Ret(self.parse_expr_opt()?);
+        let nil_span = self.token.span;
+        let nil = self.script_error_fn && self.token.is_ident_named(sym::nil);
+        let mut value = self.parse_expr_opt()?;
+        // In a script function returning `error`, `return nil` is `return Ok(())`.
+        if nil
+            && let Some(expr) = &mut value
+            && expr.span == nil_span
+        {
+            let span = nil_span;
+            let ok = Path::from_ident(Ident::new(sym::Ok, span));
+            let ok = self.mk_expr(span, ExprKind::Path(None, ok));
+            let unit = self.mk_expr(span, ExprKind::Tup(ThinVec::new()));
+            *expr = self.mk_expr(span, ExprKind::Call(ok, thin_vec![unit]));
+        }
+        let kind = ExprKind::Ret(value);
         let expr = self.mk_expr(lo.to(self.prev_token.span), kind);
         self.maybe_recover_from_bad_qpath(expr)
     }
@@ -3105,6 +3753,7 @@ fn parse_simple_block(&mut self) -> PResult<'a, Box<Expr>> {
 
     /// Parses a closure expression (e.g., `move |args| expr`).
     fn parse_expr_closure(&mut self) -> PResult<'a, Box<Expr>> {
+        let _scope = self.script_scope();
         let lo = self.token.span;
 
         let before = self.prev_token;
@@ -3146,22 +3795,27 @@ fn parse_expr_closure(&mut self) -> PResult<'a, Box<Expr>> {
         let capture_clause = self.parse_capture_clause()?;
         let (fn_decl, fn_arg_span) = self.parse_fn_block_decl()?;
         let decl_hi = self.prev_token.span;
-        let mut body = match &fn_decl.output {
-            // No return type.
-            FnRetTy::Default(_) => {
-                let restrictions =
-                    self.restrictions - Restrictions::STMT_EXPR - Restrictions::ALLOW_LET;
-                let prev = self.prev_token;
-                let token = self.token;
-                let attrs = self.parse_outer_attributes()?;
-                match self.parse_expr_res(restrictions, attrs) {
-                    Ok((expr, _)) => expr,
-                    Err(err) => self.recover_closure_body(err, before, prev, token, lo, decl_hi)?,
+        // A `return nil` in the closure doesn't return from the enclosing `error` function.
+        let mut body = self.with_script_error_fn(false, |this| -> PResult<'a, _> {
+            Ok(match &fn_decl.output {
+                // No return type.
+                FnRetTy::Default(_) => {
+                    let restrictions =
+                        this.restrictions - Restrictions::STMT_EXPR - Restrictions::ALLOW_LET;
+                    let prev = this.prev_token;
+                    let token = this.token;
+                    let attrs = this.parse_outer_attributes()?;
+                    match this.parse_expr_res(restrictions, attrs) {
+                        Ok((expr, _)) => expr,
+                        Err(err) => {
+                            this.recover_closure_body(err, before, prev, token, lo, decl_hi)?
+                        }
+                    }
                 }
-            }
-            // Explicit return type (`->`) needs block `-> T { }`.
-            FnRetTy::Ty(ty) => self.parse_closure_block_body(ty.span)?,
-        };
+                // Explicit return type (`->`) needs block `-> T { }`.
+                FnRetTy::Ty(ty) => this.parse_closure_block_body(ty.span)?,
+            })
+        })?;
 
         match coroutine_kind {
             Some(CoroutineKind::Async { .. }) => {}
@@ -3358,8 +4012,12 @@ fn parse_expr_if(&mut self) -> PResult<'a, Box<Expr>> {
     /// Wrap an expression with `.is_truthy()` for script mode truthy semantics.
     /// Creates: `(&expr).is_truthy()`
     fn wrap_expr_with_is_truthy(&mut self, expr: Box<Expr>) -> Box<Expr> {
-        use rustc_span::symbol::Ident;
+        self.wrap_expr_with_method(expr, sym::is_truthy)
+    }
 
+    /// Call a `&self` method of a script extension trait on an expression.
+    /// Creates: `(&expr).method()`
+    fn wrap_expr_with_method(&mut self, expr: Box<Expr>, method: Symbol) -> Box<Expr> {
         let span = expr.span;
 
         // Create a reference to the expression: &expr
@@ -3371,8 +4029,8 @@ fn wrap_expr_with_is_truthy(&mut self, expr: Box<Expr>) -> Box<Expr> {
             tokens: None,
         });
 
-        // Create the method call: (&expr).is_truthy()
-        let method_name = Ident::from_str_and_span("is_truthy", span);
+        // Create the method call: (&expr).method()
+        let method_name = Ident::new(method, span);
         Box::new(Expr {
             id: ast::DUMMY_NODE_ID,
             kind: ExprKind::MethodCall(Box::new(ast::MethodCall {
@@ -3844,6 +4502,7 @@ pub fn parse_for_head(&mut self) -> PResult<'a, (Pat, Box<Expr>)> {
 
     /// Parses `for await? <src_pat> in <src_expr> <src_loop_block>` (`for` token already eaten).
     fn parse_expr_for(&mut self, opt_label: Option<Label>, lo: Span) -> PResult<'a, Box<Expr>> {
+        let _scope = self.script_scope();
         let is_await =
             self.token_uninterpolated_span().at_least_rust_2018() && self.eat_keyword(exp!(Await));
 
@@ -4123,6 +4782,7 @@ fn parse_arm_body_missing_braces(
     }
 
     pub(super) fn parse_arm(&mut self) -> PResult<'a, Arm> {
+        let _scope = self.script_scope();
         let attrs = self.parse_outer_attributes()?;
         self.collect_tokens(None, attrs, ForceCollect::No, |this, attrs| {
             let lo = this.token.span;
@@ -4564,6 +5224,173 @@ fn parse_try_block(&mut self, span_lo: Span) -> PResult<'a, Box<Expr>> {
         }
     }
 
+    /// Script mode: `try {` always starts a try/catch, regardless of edition.
+    fn is_script_try_catch(&self) -> bool {
+        self.token.is_keyword(kw::Try) && self.look_ahead(1, |t| *t == token::OpenBrace)
+    }
+
+    /// Parses a script-mode `try { body } catch err { handler } finally { cleanup }`
+    /// (`try` token already eaten). Both `catch` and `finally` are optional; without
+    /// either this is an ordinary (feature-gated) `try` block.
+    ///
+    /// Lowers to:
+    /// ```ignore (illustrative)
+    /// {
+    ///     let __try_value = match __try_catch(|| Ok({ body })) {
+    ///         Ok(__try_ok) => __try_ok,
+    ///         Err(err) => { handler },
+    ///     };
+    ///     { cleanup }
+    ///     __try_value
+    /// }
+    /// ```
+    /// `__try_catch` (extensions/src/catch.rs) runs the body under `catch_unwind`, so `err`
+    /// receives either the panic message or an `Err` propagated with `?`.
+    /// Without `catch`, the error arm runs `cleanup` and re-raises via `__try_rethrow(err)`.
+    /// Because of the closure, `return`, `break` and `continue` can't leave the body and are
+    /// rejected; `?` does, as the error handed to `catch`.
+    fn parse_script_try_catch(&mut self, lo: Span) -> PResult<'a, Box<Expr>> {
+        let (attrs, body) = self.parse_inner_attrs_and_block(None)?;
+
+        let handler = if self.eat_keyword(exp!(Catch)) {
+            // `catch err { }`, `catch _ { }` or bare `catch { }`
+            let err_ident = if self.check(exp!(OpenBrace)) {
+                Ident::new(kw::Underscore, self.prev_token.span)
+            } else if self.eat_keyword(exp!(Underscore)) {
+                Ident::new(kw::Underscore, self.prev_token.span)
+            } else {
+                self.parse_ident()?
+            };
+            Some((err_ident, self.parse_block()?))
+        } else {
+            None
+        };
+
+        let cleanup = if self.token.is_ident_named(sym::finally)
+            && self.look_ahead(1, |t| *t == token::OpenBrace)
+        {
+            self.bump(); // consume `finally`
+            Some(self.parse_block()?)
+        } else {
+            None
+        };
+
+        if handler.is_none() && cleanup.is_none() {
+            let span = lo.to(body.span);
+            self.psess.gated_spans.gate(sym::try_blocks, span);
+            return Ok(self.mk_expr_with_attrs(span, ExprKind::TryBlock(body, None), attrs));
+        }
+
+        let span = lo.to(self.prev_token.span);
+        let mut escapes = TryBodyEscapes::default();
+        escapes.visit_block(&body);
+        let mut guar = None;
+        for (escape_span, keyword) in escapes.escapes {
+            let err = self
+                .dcx()
+                .struct_span_err(escape_span, format!("`{keyword}` can't leave a `try` body"))
+                .with_note("the body runs in a closure, so this would only leave the closure")
+                .with_help("set a variable and act on it after the `try`");
+            guar = Some(err.emit());
+        }
+        if let Some(guar) = guar {
+            return Ok(self.mk_expr_err(span, guar));
+        }
+
+        let path_expr = |this: &Self, name: Symbol| {
+            this.mk_expr(span, ExprKind::Path(None, Path::from_ident(Ident::new(name, span))))
+        };
+        let helper_expr = |this: &Self, name: Symbol| {
+            this.mk_expr(span, ExprKind::Path(None, script_helper_path(name, span)))
+        };
+        let block_expr = |this: &Self, block: Box<ast::Block>| {
+            let block_span = block.span;
+            this.mk_expr(block_span, ExprKind::Block(block, None))
+        };
+        let variant_pat = |this: &Self, variant: Symbol, binding: Ident| {
+            let inner = if binding.name == kw::Underscore {
+                this.mk_pat(binding.span, ast::PatKind::Wild)
+            } else {
+                this.mk_pat_ident(binding.span, ast::BindingMode::NONE, binding)
+            };
+            Box::new(this.mk_pat(
+                span,
+                ast::PatKind::TupleStruct(
+                    None,
+                    Path::from_ident(Ident::new(variant, span)),
+                    thin_vec![Box::new(inner)],
+                ),
+            ))
+        };
+        let arm = |pat: Box<Pat>, body: Box<Expr>| Arm {
+            attrs: AttrVec::new(),
+            pat,
+            guard: None,
+            body: Some(body),
+            span,
+            id: DUMMY_NODE_ID,
+            is_placeholder: false,
+        };
+
+        // __try_catch(|| Ok({ body }))
+        let body_expr = block_expr(self, body);
+        let ok_body = self.mk_expr(span, ExprKind::Call(path_expr(self, sym::Ok), thin_vec![body_expr]));
+        let body_closure = self.mk_closure_expr(span, ok_body);
+        let try_call = self.mk_expr(
+            span,
+            ExprKind::Call(helper_expr(self, sym::__try_catch), thin_vec![body_closure]),
+        );
+
+        // Err(err) => { handler }, or without catch: Err(__try_ok) => { cleanup; __try_rethrow(__try_ok) }
+        let (err_ident, err_body) = match handler {
+            Some((err_ident, handler)) => (err_ident, block_expr(self, handler)),
+            None => {
+                let err_ident = Ident::new(sym::__try_ok, span);
+                let rethrow = self.mk_expr(
+                    span,
+                    ExprKind::Call(helper_expr(self, sym::__try_rethrow), thin_vec![path_expr(self, sym::__try_ok)]),
+                );
+                let mut stmts = ThinVec::new();
+                if let Some(cleanup) = &cleanup {
+                    stmts.push(self.mk_stmt(span, StmtKind::Semi(block_expr(self, cleanup.clone()))));
+                }
+                stmts.push(self.mk_stmt(span, StmtKind::Expr(rethrow)));
+                (err_ident, block_expr(self, self.mk_block(stmts, BlockCheckMode::Default, span)))
+            }
+        };
+
+        let arms = thin_vec![
+            arm(variant_pat(self, sym::Ok, Ident::new(sym::__try_ok, span)), path_expr(self, sym::__try_ok)),
+            arm(variant_pat(self, sym::Err, err_ident), err_body),
+        ];
+        let match_expr = self.mk_expr(span, ExprKind::Match(try_call, arms, MatchKind::Prefix));
+
+        let Some(cleanup) = cleanup else {
+            return Ok(self.mk_expr_with_attrs(span, match_expr.kind, attrs));
+        };
+
+        // { let __try_value = match ..; { cleanup } __try_value }
+        let value_ident = Ident::new(sym::__try_value, span);
+        let local = Box::new(ast::Local {
+            id: DUMMY_NODE_ID,
+            super_: None,
+            pat: Box::new(self.mk_pat_ident(span, ast::BindingMode::NONE, value_ident)),
+            ty: None,
+            kind: ast::LocalKind::Init(match_expr),
+            span,
+            colon_sp: None,
+            attrs: AttrVec::new(),
+            tokens: None,
+        });
+        let stmts = thin_vec![
+            self.mk_stmt(span, StmtKind::Let(local)),
+            self.mk_stmt(span, StmtKind::Semi(block_expr(self, cleanup))),
+            self.mk_stmt(span, StmtKind::Expr(path_expr(self, sym::__try_value))),
+        ];
+        let block = self.mk_block(stmts, BlockCheckMode::Default, span);
+        Ok(self.mk_expr_with_attrs(span, ExprKind::Block(block, None), attrs))
+    }
+
     fn is_do_catch_block(&self) -> bool {
         self.token.is_keyword(kw::Do)
             && self.is_keyword_ahead(1, &[kw::Catch])
@@ -5144,6 +5971,15 @@ fn wrap_in_string_from(&self, expr: Box<Expr>) -> Box<Expr> {
         self.mk_expr(span, ExprKind::Call(path_expr, thin_vec![expr]))
     }
 
+    /// Wrap an expression in Val::from(expr)
+    fn wrap_in_val_from(&self, expr: Box<Expr>) -> Box<Expr> {
+        let span = expr.span;
+        let mut val_from_path = script_helper_path(sym::Val, span);
+        val_from_path.segments.push(PathSegment::from_ident(Ident::new(sym::from, span)));
+        let path_expr = self.mk_expr(span, ExprKind::Path(None, val_from_path));
+        self.mk_expr(span, ExprKind::Call(path_expr, thin_vec![expr]))
+    }
+
     /// Wrap an expression in expr.to_string()
     pub(super) fn wrap_in_to_string(&self, expr: Box<Expr>) -> Box<Expr> {
         let span = expr.span;
@@ -5364,7 +6200,7 @@ fn mk_debug_string_call(&self, expr: Box<Expr>, span: Span) -> Box<Expr> {
         // Create __debug_string(&expr) call
         let fn_path = self.mk_expr(span, ExprKind::Path(
             None,
-            Path::from_ident(Ident::new(sym::__debug_string, span))
+            script_helper_path(sym::__debug_string, span)
         ));
 
         Box::new(Expr {
@@ -5570,6 +6406,18 @@ fn mk_char_to_digit(&self, char_expr: Box<Expr>, span: Span) -> Box<Expr> {
 
 /// Could this lifetime/label be an unclosed char literal? For example, `'a`
 /// could be, but `'abc` could not.
+/// See `Parser::script_scope`.
+pub(super) struct ScriptScope<'a> {
+    locals: &'a Lock<Vec<Symbol>>,
+    len: usize,
+}
+
+impl Drop for ScriptScope<'_> {
+    fn drop(&mut self) {
+        self.locals.borrow_mut().truncate(self.len);
+    }
+}
+
 pub(crate) fn could_be_unclosed_char_literal(ident: Ident) -> bool {
     ident.name.as_str().starts_with('\'')
         && unescape_char(ident.without_first_quote().name.as_str()).is_ok()
@@ -5768,6 +6616,72 @@ fn visit_expr(&mut self, e: &mut Expr) {
     }
 }
 
+/// Finds the `return`, `break` and `continue` expressions that would leave a script `try`
+/// body. The body runs as a closure under `catch_unwind`, so they can't reach the enclosing
+/// function or loop.
+#[derive(Default)]
+struct TryBodyEscapes {
+    /// Labels of the loops entered inside the body, `None` for unlabeled ones.
+    loops: Vec<Option<Symbol>>,
+    /// Labels of the labeled blocks entered inside the body.
+    blocks: Vec<Symbol>,
+    escapes: Vec<(Span, &'static str)>,
+}
+
+impl TryBodyEscapes {
+    fn in_loop(&mut self, label: &Option<Label>, ex: &Expr) {
+        self.loops.push(label.as_ref().map(|label| label.ident.name));
+        walk_expr(self, ex);
+        self.loops.pop();
+    }
+}
+
+impl<'ast> Visitor<'ast> for TryBodyEscapes {
+    fn visit_expr(&mut self, ex: &'ast Expr) {
+        match &ex.kind {
+            // Closures, async and gen blocks have their own control flow.
+            ExprKind::Closure(..) | ExprKind::Gen(..) => {}
+            ExprKind::While(_, _, label) | ExprKind::Loop(_, label, _) => self.in_loop(label, ex),
+            ExprKind::ForLoop { label, .. } => self.in_loop(label, ex),
+            ExprKind::Block(_, Some(label)) => {
+                self.blocks.push(label.ident.name);
+                walk_expr(self, ex);
+                self.blocks.pop();
+            }
+            ExprKind::Ret(_) => {
+                self.escapes.push((ex.span, "return"));
+                walk_expr(self, ex);
+            }
+            ExprKind::Break(label, _) => {
+                let inside = match label {
+                    Some(label) => {
+                        self.blocks.contains(&label.ident.name)
+                            || self.loops.contains(&Some(label.ident.name))
+                    }
+                    None => !self.loops.is_empty(),
+                };
+                if !inside {
+                    self.escapes.push((ex.span, "break"));
+                }
+                walk_expr(self, ex);
+            }
+            ExprKind::Continue(label) => {
+                let inside = match label {
+                    Some(label) => self.loops.contains(&Some(label.ident.name)),
+                    None => !self.loops.is_empty(),
+                };
+                if !inside {
+                    self.escapes.push((ex.span, "continue"));
+                }
+            }
+            _ => walk_expr(self, ex),
+        }
+    }
+
+    // Nested items are checked on their own.
+    fn visit_item(&mut self, _item: &'ast ast::Item) {}
+}
+
 /// Convert a binary operator to its corresponding token kind.
 /// Note: Pow is handled specially in expr_to_token_stream since ** is two tokens.
 fn binop_to_token(op: BinOpKind) -> token::TokenKind {
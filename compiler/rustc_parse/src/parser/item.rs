@@ -26,7 +26,10 @@
     Recovered, Trailing, UsePreAttrPos,
 };
 use crate::errors::{self, FnPointerCannotBeAsync, FnPointerCannotBeConst, MacroExpandsToAdtField};
-use crate::transformer::{create_derive_attr, create_no_mangle_attr};
+use crate::transformer::{
+    create_allow_attr, create_derive_attr, create_no_mangle_attr, script_helper_path,
+    script_helper_path_tokens,
+};
 use crate::{exp, fluent_generated as fluent};
 
 impl<'a> Parser<'a> {
@@ -187,7 +190,10 @@ fn parse_script_statement_as_item(&mut self) -> PResult<'a, Option<Item>> {
 
             // Check for new statement on next line
             let token_pos = self.psess.source_map().lookup_char_pos(self.token.span.lo());
-            if token_pos.line > stmt_line && self.token_could_start_statement() {
+            if token_pos.line > stmt_line
+                && self.token_could_start_statement()
+                && !self.is_try_catch_continuation(&stmt_tokens)
+            {
                 break;
             }
 
@@ -208,11 +214,7 @@ fn parse_script_statement_as_item(&mut self) -> PResult<'a, Option<Item>> {
         let hi = self.prev_token.span;
 
         // Build __stmt!(tokens) macro call
-        let stmt_path = ast::Path {
-            span: lo,
-            segments: thin_vec![ast::PathSegment::from_ident(Ident::new(sym::__stmt, lo))],
-            tokens: None,
-        };
+        let stmt_path = script_helper_path(sym::__stmt, lo);
 
         let args = ast::DelimArgs {
             dspan: DelimSpan::from_pair(lo, hi),
@@ -298,11 +300,7 @@ fn parse_script_if_statement_as_item(&mut self) -> PResult<'a, Option<Item>> {
             all.extend(cond_tokens);
             all.extend(body_tokens);
 
-            let path = ast::Path {
-                span: lo,
-                segments: thin_vec![ast::PathSegment::from_ident(Ident::new(sym::__stmt, lo))],
-                tokens: None,
-            };
+            let path = script_helper_path(sym::__stmt, lo);
             let args = ast::DelimArgs {
                 dspan: DelimSpan::from_pair(lo, hi),
                 delim: Delimiter::Parenthesis,
@@ -315,11 +313,7 @@ fn parse_script_if_statement_as_item(&mut self) -> PResult<'a, Option<Item>> {
             all.push(TokenTree::token_alone(token::TokenKind::Semi, lo));
             all.extend(body_tokens);
 
-            let path = ast::Path {
-                span: lo,
-                segments: thin_vec![ast::PathSegment::from_ident(Ident::new(sym::__if, lo))],
-                tokens: None,
-            };
+            let path = script_helper_path(sym::__if, lo);
             let args = ast::DelimArgs {
                 dspan: DelimSpan::from_pair(lo, hi),
                 delim: Delimiter::Parenthesis,
@@ -451,6 +445,7 @@ fn wrap_with_is_truthy(tokens: Vec<TokenTree>, span: Span) -> Vec<TokenTree> {
     fn token_could_start_statement(&self) -> bool {
         self.token.is_non_reserved_ident()
             || self.token.is_keyword(kw::Let)
+            || self.token.is_keyword(kw::Try)
             || self.token.is_keyword(kw::Fn)
             || self.token.is_keyword(kw::If)
             || self.token.is_keyword(kw::For)
@@ -466,6 +461,15 @@ fn token_could_start_statement(&self) -> bool {
             || self.token.is_keyword(kw::Type)
             || self.token == token::Pound
     }
+
+    /// `catch` / `finally` on a new line continue a `try { }` statement instead of starting one.
+    fn is_try_catch_continuation(&self, stmt_tokens: &[TokenTree]) -> bool {
+        let starts_with_try = matches!(
+            stmt_tokens.first(),
+            Some(TokenTree::Token(tok, _)) if tok.is_keyword(kw::Try)
+        );
+        starts_with_try && (self.token.is_keyword(kw::Catch) || self.token.is_ident_named(sym::finally))
+    }
 }
 
 enum ReuseKind {
@@ -603,6 +607,13 @@ fn parse_item_kind(
 
         let info = if !self.is_use_closure() && self.eat_keyword_case(exp!(Use), case) {
             self.parse_use_item()?
+        } else if self.is_script_mode()
+            && self.token.is_keyword(kw::Fn)
+            && self.look_ahead(1, |t| t.is_ident())
+            && self.look_ahead(2, |t| *t == token::Bang)
+        {
+            // fn put!(x int) { .. }: a macro defined like a function
+            self.parse_script_macro_fn()?
         } else if self.check_fn_front_matter(check_pub, case) {
             // FUNCTION ITEM
             let (ident, sig, generics, contract, body) =
@@ -692,12 +703,26 @@ fn parse_item_kind(
             // Go-style short variable declaration: x := expr -> __walrus!(x = expr)
             // Only at module level (Free context), not inside impl/trait/blocks/macros
             return self.parse_walrus_assignment(lo, attrs);
+        } else if self.is_script_mode()
+            && self.token.is_ident_named(sym::import)
+            && self.look_ahead(1, |t| t.is_ident_named(sym::lib))
+            && self.look_ahead(2, |t| matches!(t.kind, token::Literal(token::Lit { kind: token::Str, .. })))
+        {
+            // import lib "libfoo.so" { fn ... }: checked dynamic import of #[dynexport] functions
+            self.bump(); // consume `import`
+            self.bump(); // consume `lib`
+            return self.parse_import_lib(lo);
         } else if self.token.is_ident_named(sym::import) && self.look_ahead(1, |t| t.is_keyword(kw::Fn)) {
             // import fn: creates extern "C" function declarations
             self.bump(); // consume `import`
             return self.parse_import_fn(lo, attrs);
-        } else if self.token.is_ident_named(sym::include) && self.look_ahead(1, |t| t.is_ident()) {
+        } else if self.token.is_ident_named(sym::include)
+            && self.look_ahead(1, |t| {
+                t.is_ident() || matches!(t.kind, token::Literal(token::Lit { kind: token::Str, .. }))
+            })
+        {
             // include library_name; -> generates #[link(name = "library_name")] extern "C" {}
+            // include "foo.h" / include foo from "foo.h" -> mod foo { <declarations from foo.h> }
             self.bump(); // consume `include`
             return self.parse_include_library(lo, attrs);
         } else if self.isnt_macro_invocation()
@@ -738,6 +763,13 @@ fn parse_item_kind(
     fn recover_import_as_use(&mut self) -> PResult<'a, Option<ItemKind>> {
         let span = self.token.span;
         let token_name = super::token_descr(&self.token);
+        if self.is_script_mode()
+            && self.token.is_ident_named(sym::import)
+            && self.look_ahead(1, |t| matches!(t.kind, token::Literal(token::Lit { kind: token::Str, .. })))
+        {
+            self.bump(); // consume `import`
+            return self.parse_script_import(span);
+        }
         let snapshot = self.create_snapshot_for_diagnostic();
         self.bump();
         match self.parse_use_item() {
@@ -753,6 +785,179 @@ fn recover_import_as_use(&mut self) -> PResult<'a, Option<ItemKind>> {
         }
     }
 
+    /// Script mode: `import "./helper"` loads `helper.rust`/`helper.rs` next to the
+    /// current file, or every script file in the `helper/` folder (one Go-style package),
+    /// as `mod helper`. Capitalized items are exported, and `helper.Hello()` resolves as
+    /// `helper::Hello()` unless a value called `helper` is in scope.
+    fn parse_script_import(&mut self, lo: Span) -> PResult<'a, Option<ItemKind>> {
+        let (lit, lit_span) = self.parse_token_lit()?;
+        self.expect_semi()?;
+
+        let base_dir = self
+            .psess
+            .source_map()
+            .span_to_filename(lit_span)
+            .into_local_path()
+            .and_then(|file| file.parent().map(|dir| dir.to_path_buf()))
+            .unwrap_or_default();
+        let target = base_dir.join(lit.symbol.as_str());
+        let name = target.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default();
+        if !rustc_lexer::is_ident(name) {
+            let msg = format!("cannot import `{}`: `{name}` is not a valid module name", lit.symbol);
+            return Err(self.dcx().struct_span_err(lit_span, msg));
+        }
+        let ident = Ident::from_str_and_span(name, lit_span);
+
+        let mut files = match script_import_files(lit.symbol.as_str(), &target) {
+            Ok(files) => files,
+            Err(msg) => return Err(self.dcx().struct_span_err(lit_span, msg)),
+        };
+        for file in &mut files {
+            if let Ok(canonical) = file.canonicalize() {
+                *file = canonical;
+            }
+        }
+        let cycle = files.iter().find(|file| self.psess.script_import_stack.borrow().contains(file));
+        if let Some(file) = cycle {
+            let msg = format!("import cycle: `{}` is already being imported", file.display());
+            return Err(self.dcx().struct_span_err(lit_span, msg));
+        }
+
+        // Imported files see the script prelude (type aliases, helpers) like the main script.
+        let mut items = thin_vec![self.mk_glob_use_crate(lit_span)];
+        for file in files {
+            let mut parser = crate::unwrap_or_emit_fatal(crate::new_parser_from_file(
+                self.psess,
+                &file,
+                crate::lexer::StripTokens::ShebangAndFrontmatter,
+                Some(lit_span),
+            ));
+            self.psess.script_import_stack.borrow_mut().push(file);
+            let parsed = parser.parse_mod(exp!(Eof));
+            self.psess.script_import_stack.borrow_mut().pop();
+            let (_, file_items, _) = parsed?;
+            for mut item in file_items {
+                // Go-style exports: capitalized names are public.
+                let exported = item.kind.ident().is_some_and(|i| i.as_str().starts_with(char::is_uppercase));
+                if exported && matches!(item.vis.kind, VisibilityKind::Inherited) {
+                    item.vis.kind = VisibilityKind::Public;
+                }
+                items.push(item);
+            }
+        }
+
+        let spans = ModSpans { inner_span: lit_span, inject_use_span: lo.shrink_to_lo() };
+        Ok(Some(ItemKind::Mod(Safety::Default, ident, ModKind::Loaded(items, Inline::Yes, spans))))
+    }
+
+    /// Script mode: `import lib "libfoo.so" { fn add(a: i32, b: i32) -> i32 }` becomes
+    /// `mod foo` (or the name given with `as name`) with wrappers per declared function.
+    /// The library is opened on first call, and each symbol is checked against its
+    /// `dynexport_meta_<name>` record; `foo.add` panics if that fails, `foo.try_add` returns
+    /// the `DynloadError`, and `foo.load()` checks every function up front. `#[dynexport(version = N)]` on a declaration
+    /// rejects items exported with a lower version. See transformer/dynload.rs.
+    fn parse_import_lib(&mut self, lo: Span) -> PResult<'a, Option<ItemKind>> {
+        let (lit, lit_span) = self.parse_token_lit()?;
+        let library = lit.symbol.as_str();
+        let ident = if self.eat_keyword(exp!(As)) {
+            self.parse_ident()?
+        } else {
+            // libfoo.so, libfoo.dylib, foo.dll -> foo
+            let file = std::path::Path::new(library).file_name().and_then(|f| f.to_str());
+            let stem = file.unwrap_or_default().split('.').next().unwrap_or_default();
+            let name = stem.strip_prefix("lib").filter(|n| !n.is_empty()).unwrap_or(stem);
+            if !rustc_lexer::is_ident(name) {
+                let msg = format!("cannot derive a module name from `{library}`");
+                let mut err = self.dcx().struct_span_err(lit_span, msg);
+                err.help("name the module explicitly: `import lib \"...\" as name { ... }`");
+                return Err(err);
+            }
+            Ident::from_str_and_span(name, lit_span)
+        };
+
+        self.expect(exp!(OpenBrace))?;
+        let mode = FnParseMode {
+            req_name: |_, _| true,
+            context: FnContext::Free,
+            req_body: false,
+            in_block: false,
+        };
+        let mut fns = Vec::new();
+        while !self.eat(exp!(CloseBrace)) {
+            let mut version = 0;
+            while self.check(exp!(Pound)) {
+                let attr = self.parse_attribute(super::attr::InnerAttrPolicy::Forbidden(None))?;
+                version = crate::transformer::dynload::required_version(&attr).map_err(|span| {
+                    let msg = "expected `#[dynexport(version = N)]` with `N` between 0 and 65535";
+                    self.dcx().struct_span_err(span, msg)
+                })?;
+            }
+            self.expect_keyword(exp!(Fn))?;
+            let fn_ident = self.parse_ident()?;
+            let decl = self.parse_fn_decl(&mode, AllowPlus::Yes, RecoverReturnSign::Yes)?;
+            if let Err(span) = crate::transformer::dynload::fn_type_hash(&decl) {
+                let msg = format!("cannot check `{fn_ident}` against the library's #[dynexport] metadata");
+                let mut err = self.dcx().struct_span_err(span, msg);
+                err.note(
+                    "imported signatures may only use primitives, references, pointers, slices, \
+                     arrays, tuples and standard library types like `String`, `Vec<T>` and `Option<T>`",
+                );
+                err.help("name other standard library types by their path, like `std::rc::Rc<T>`");
+                return Err(err);
+            }
+            let _ = self.eat(exp!(Semi)) || self.eat(exp!(Comma));
+            fns.push((fn_ident, decl, version));
+        }
+
+        // A relative path is looked up next to the script first, then by the system loader.
+        // The local file is passed on canonicalized: a bare `libfoo.so` from a script in the
+        // current directory would otherwise still go through the loader's search path.
+        let base_dir = self
+            .psess
+            .source_map()
+            .span_to_filename(lit_span)
+            .into_local_path()
+            .and_then(|file| file.parent().map(|dir| dir.to_path_buf()))
+            .unwrap_or_default();
+        let local = base_dir.join(library).canonicalize().ok().filter(|local| local.is_file());
+        let library = match local {
+            Some(local) => local.to_string_lossy().into_owned(),
+            None => library.to_string(),
+        };
+
+        let source = crate::transformer::dynload::import_lib_source(&library, &fns);
+        let filename = rustc_span::FileName::Custom(format!("import lib {}", ident.name));
+        let stream = match crate::source_str_to_stream(self.psess, filename, source, Some(lit_span)) {
+            Ok(stream) => stream,
+            Err(errs) => {
+                let mut errs = errs.into_iter();
+                let first = errs.next().expect("lexer failed without an error");
+                errs.for_each(|err| _ = err.emit());
+                return Err(first);
+            }
+        };
+        let mut parser = Parser::new(self.psess, stream, None);
+        let mut items = ThinVec::new();
+        while let Some(item) = parser.parse_item(ForceCollect::No)? {
+            items.push(item);
+        }
+
+        let spans = ModSpans { inner_span: lo.to(self.prev_token.span), inject_use_span: lo.shrink_to_lo() };
+        Ok(Some(ItemKind::Mod(Safety::Default, ident, ModKind::Loaded(items, Inline::Yes, spans))))
+    }
+
+    /// `use crate::*;`
+    fn mk_glob_use_crate(&self, span: Span) -> Box<Item> {
+        let tree = UseTree {
+            prefix: ast::Path::from_ident(Ident::new(kw::Crate, span)),
+            kind: UseTreeKind::Glob,
+            span,
+        };
+        let vis = Visibility { span, kind: VisibilityKind::Inherited, tokens: None };
+        let attrs = thin_vec![create_allow_attr(span, sym::unused_imports)];
+        Box::new(Item { attrs, id: DUMMY_NODE_ID, kind: ItemKind::Use(tree), vis, span, tokens: None })
+    }
+
     fn parse_use_item(&mut self) -> PResult<'a, ItemKind> {
         let tree = self.parse_use_tree()?;
         if let Err(mut e) = self.expect_semi() {
@@ -908,11 +1113,7 @@ fn parse_walrus_assignment(
 
         // Build __stmt!(let mut ident = expr_tokens) macro call
         // This produces `let mut ident = expr;` after expansion
-        let stmt_path = ast::Path {
-            span: lo,
-            segments: thin_vec![ast::PathSegment::from_ident(Ident::new(sym::__stmt, lo))],
-            tokens: None,
-        };
+        let stmt_path = script_helper_path(sym::__stmt, lo);
 
         // Build the macro arguments: let mut ident = expr_tokens
         let mut args_tokens = vec![
@@ -2874,6 +3075,105 @@ fn parse_item_macro_rules(
         ))
     }
 
+    /// Script mode: `fn put!(x int) { .. }` defines a macro the way a function is defined, so
+    /// a script can replace a built-in macro with its own. Each call runs the body as a local
+    /// function:
+    ///
+    /// `macro_rules! put { ($__arg0:expr $(,)?) => {{ fn put(x int) { .. } put($__arg0) }} }`
+    ///
+    /// Inside the body, `put!` is still the built-in macro, reached through
+    /// `crate::__script_helpers`.
+    fn parse_script_macro_fn(&mut self) -> PResult<'a, ItemKind> {
+        use rustc_ast::tokenstream::{DelimSpacing, Spacing};
+
+        self.expect_keyword(exp!(Fn))?;
+        let ident = self.parse_ident()?;
+        self.expect(exp!(Bang))?;
+
+        // The signature as written, which the local function repeats, and then the body.
+        let mut signature = Vec::new();
+        while self.token != token::OpenBrace && self.token != token::Eof {
+            signature.extend(self.collect_token_tree());
+        }
+        if self.token != token::OpenBrace {
+            self.expect(exp!(OpenBrace))?;
+        }
+        let Some(TokenTree::Delimited(dspan, spacing, delim, body)) = self.collect_token_tree()
+        else {
+            unreachable!("`{{` starts a delimited token tree")
+        };
+        let body = builtin_macro_calls(ident.name, &body);
+
+        // Parse the signature on its own for the parameter count, reporting errors in it here
+        // rather than at every call.
+        let mut parser = Parser::new(self.psess, TokenStream::new(signature.clone()), None);
+        let mode = FnParseMode {
+            req_name: |_, _| true,
+            context: FnContext::Free,
+            req_body: false,
+            in_block: false,
+        };
+        let decl = parser.parse_fn_decl(&mode, AllowPlus::Yes, RecoverReturnSign::Yes)?;
+        if parser.token != token::Eof {
+            let msg = format!("expected the body of `fn {ident}!` after its signature");
+            return Err(self.dcx().struct_span_err(parser.token.span, msg));
+        }
+
+        let span = ident.span;
+        let tok = |kind: TokenKind| TokenTree::token_alone(kind, span);
+        let ident_tok = |name: Symbol| tok(token::Ident(name, IdentIsRaw::No));
+        let group = |delim: Delimiter, trees: Vec<TokenTree>| {
+            TokenTree::Delimited(
+                DelimSpan::from_single(span),
+                DelimSpacing::new(Spacing::Alone, Spacing::Alone),
+                delim,
+                TokenStream::new(trees),
+            )
+        };
+        let args: Vec<Symbol> =
+            (0..decl.inputs.len()).map(|i| Symbol::intern(&format!("__arg{i}"))).collect();
+
+        // ($__arg0:expr, $__arg1:expr $(,)?)
+        let mut matcher = Vec::new();
+        let mut call_args = Vec::new();
+        for (i, &arg) in args.iter().enumerate() {
+            if i > 0 {
+                matcher.push(tok(token::Comma));
+                call_args.push(tok(token::Comma));
+            }
+            matcher.extend([tok(token::Dollar), ident_tok(arg), tok(token::Colon)]);
+            matcher.push(ident_tok(sym::expr));
+            call_args.extend([tok(token::Dollar), ident_tok(arg)]);
+        }
+        matcher.push(tok(token::Dollar));
+        matcher.push(group(Delimiter::Parenthesis, vec![tok(token::Comma)]));
+        matcher.push(tok(token::Question));
+
+        // { fn put(x int) { .. } put($__arg0) }
+        let mut expansion = vec![
+            tok(token::Ident(kw::Fn, IdentIsRaw::No)),
+            TokenTree::token_alone(token::Ident(ident.name, IdentIsRaw::No), ident.span),
+        ];
+        expansion.extend(signature);
+        expansion.push(TokenTree::Delimited(dspan, spacing, delim, body));
+        expansion.push(ident_tok(ident.name));
+        expansion.push(group(Delimiter::Parenthesis, call_args));
+
+        let rules = vec![
+            group(Delimiter::Parenthesis, matcher),
+            tok(token::FatArrow),
+            group(Delimiter::Brace, vec![group(Delimiter::Brace, expansion)]),
+            tok(token::Semi),
+        ];
+        let body = Box::new(DelimArgs {
+            dspan: DelimSpan::from_single(span),
+            delim: Delimiter::Brace,
+            tokens: TokenStream::new(rules),
+        });
+        let def = ast::MacroDef { body, macro_rules: true, eii_declaration: None };
+        Ok(ItemKind::MacroDef(ident, def))
+    }
+
     /// Item macro invocations or `macro_rules!` definitions need inherited visibility.
     /// If that's not the case, emit an error.
     fn complain_if_pub_macro(&self, vis: &Visibility, macro_rules: bool) {
@@ -3052,8 +3352,15 @@ fn parse_include_library(
         lo: Span,
         attrs: &mut AttrVec,
     ) -> PResult<'a, Option<ItemKind>> {
+        if let token::Literal(token::Lit { kind: token::Str, .. }) = self.token.kind {
+            return self.parse_include_header(lo, None, attrs);
+        }
         // Parse library name
         let lib_name = self.parse_ident()?;
+        if self.token.is_ident_named(sym::from) {
+            self.bump(); // consume `from`
+            return self.parse_include_header(lo, Some(lib_name), attrs);
+        }
         self.expect_semi()?;
 
         // Create #[link(name = "library_name")] attribute
@@ -3082,6 +3389,100 @@ fn parse_include_library(
         Ok(Some(foreign_mod))
     }
 
+    /// Parse the rest of `include "foo.h"` or `include foo from "foo.h"` into a module `foo`
+    /// linking `libfoo`, with the functions, types and constants of the header (see
+    /// transformer/c_header.rs). A relative header is looked up next to the script, then in the
+    /// include directories of `c_header::include_dirs`.
+    fn parse_include_header(
+        &mut self,
+        lo: Span,
+        name: Option<Ident>,
+        attrs: &mut AttrVec,
+    ) -> PResult<'a, Option<ItemKind>> {
+        let (lit, lit_span) = self.parse_token_lit()?;
+        let _ = self.eat(exp!(Semi));
+        let header = lit.symbol.as_str();
+        let (ident, library) = match name {
+            Some(ident) => (ident, Some(ident.name)),
+            None => {
+                let stem = std::path::Path::new(header).file_stem().and_then(|f| f.to_str());
+                let stem = stem.unwrap_or_default();
+                if !rustc_lexer::is_ident(stem) {
+                    let msg = format!("cannot derive a library name from `{header}`");
+                    let mut err = self.dcx().struct_span_err(lit_span, msg);
+                    err.help("name the library explicitly: `include name from \"...\"`");
+                    return Err(err);
+                }
+                let library = crate::transformer::c_header::header_library(header, stem);
+                (Ident::from_str_and_span(stem, lit_span), library.map(Symbol::intern))
+            }
+        };
+
+        let base_dir = self
+            .psess
+            .source_map()
+            .span_to_filename(lit_span)
+            .into_local_path()
+            .and_then(|file| file.parent().map(|dir| dir.to_path_buf()))
+            .unwrap_or_default();
+        // Recorded like `env!` reads, so a changed search path invalidates the script cache.
+        let mut env = |var: &str| {
+            let value = std::env::var(var).ok();
+            let entry = (Symbol::intern(var), value.as_deref().map(Symbol::intern));
+            self.psess.env_depinfo.borrow_mut().insert(entry);
+            value
+        };
+        let mut dirs = vec![base_dir];
+        dirs.extend(crate::transformer::c_header::include_dirs(&self.psess.config, &mut env));
+        let path = dirs.iter().map(|dir| dir.join(header)).find(|path| path.is_file());
+        // Loaded through the source map so the script cache and dep-info track the headers.
+        let source_map = self.psess.source_map();
+        let mut load = |path: &std::path::Path| -> std::io::Result<String> {
+            let file = source_map.load_file(path)?;
+            Ok(file.src.as_deref().cloned().unwrap_or_default())
+        };
+        let text = match path {
+            Some(path) => crate::transformer::c_header::read_header(&path, &mut load),
+            None => {
+                let dirs: Vec<_> = dirs.iter().map(|dir| dir.display().to_string()).collect();
+                Err(format!("no such file in {}", dirs.join(", ")))
+            }
+        };
+
+        // Errors are emitted here rather than returned so a script doesn't retry the line as a
+        // statement; the module is then empty.
+        let mut items = ThinVec::new();
+        match text {
+            Err(note) => {
+                let msg = format!("cannot read header `{header}`");
+                let mut err = self.dcx().struct_span_err(lit_span, msg);
+                err.note(note);
+                err.emit();
+            }
+            Ok(text) => {
+                let source = crate::transformer::c_header::header_module_source(
+                    library.as_ref().map(Symbol::as_str),
+                    &text,
+                    &self.psess.config,
+                );
+                let filename = rustc_span::FileName::Custom(format!("include {header}"));
+                match crate::source_str_to_stream(self.psess, filename, source, Some(lit_span)) {
+                    Ok(stream) => {
+                        let mut parser = Parser::new(self.psess, stream, None);
+                        attrs.extend(parser.parse_inner_attributes()?);
+                        while let Some(item) = parser.parse_item(ForceCollect::No)? {
+                            items.push(item);
+                        }
+                    }
+                    Err(errs) => errs.into_iter().for_each(|err| _ = err.emit()),
+                }
+            }
+        }
+
+        let spans = ModSpans { inner_span: lo.to(self.prev_token.span), inject_use_span: lo.shrink_to_lo() };
+        Ok(Some(ItemKind::Mod(Safety::Default, ident, ModKind::Loaded(items, Inline::Yes, spans))))
+    }
+
     /// Create #[link(name = "library_name")] attribute
     fn create_link_attr(&self, span: Span, library_name: Symbol) -> ast::Attribute {
         use rustc_ast::token::{IdentIsRaw, TokenKind};
@@ -3125,10 +3526,14 @@ fn create_link_attr(&self, span: Span, library_name: Symbol) -> ast::Attribute {
     }
 
     /// Parse `import fn name(args) -> ret;` and convert to extern "C" { fn name(args) -> ret; }
+    ///
+    /// A signature with strings, a C-variadic `...` or callbacks instead becomes the module
+    /// `__import_<name>` described in transformer/import_fn.rs, and later calls of `name(..)`
+    /// parse as calls of its `call!` macro.
     fn parse_import_fn(
         &mut self,
         lo: Span,
-        _attrs: &mut AttrVec,
+        attrs: &mut AttrVec,
     ) -> PResult<'a, Option<ItemKind>> {
         self.expect_keyword(exp!(Fn))?; // consume `fn`
 
@@ -3136,7 +3541,7 @@ fn parse_import_fn(
         let ident = self.parse_ident()?;
         let mut generics = self.parse_generics()?;
         let mode = FnParseMode {
-            req_name: |_, _| true,
+            req_name: |_, is_dot_dot_dot| is_dot_dot_dot == IsDotDotDot::No,
             context: FnContext::Free,
             req_body: false,
             in_block: false,
@@ -3147,6 +3552,17 @@ fn parse_import_fn(
 
         let fn_span = lo.to(self.prev_token.span);
 
+        if crate::transformer::import_fn::needs_wrapper(&decl) {
+            match crate::transformer::import_fn::import_fn_source(ident, &decl) {
+                Ok(source) => {
+                    return self.parse_import_fn_module(lo, ident, source, attrs).map(Some);
+                }
+                // Emitted rather than returned so a script doesn't retry the line as a
+                // statement; the function is then declared as written.
+                Err((span, msg)) => _ = self.dcx().struct_span_err(span, msg).emit(),
+            }
+        }
+
         // Build function signature (no extern needed in signature for foreign fns)
         let header = ast::FnHeader::default();
         let sig = ast::FnSig {
@@ -3198,6 +3614,40 @@ fn parse_import_fn(
         })))
     }
 
+    /// The `__import_<name>` module of an `import fn` from its generated source.
+    fn parse_import_fn_module(
+        &mut self,
+        lo: Span,
+        ident: Ident,
+        source: String,
+        attrs: &mut AttrVec,
+    ) -> PResult<'a, ItemKind> {
+        // `#[link]` on the declaration belongs on the generated foreign block.
+        let (link, rest): (Vec<_>, AttrVec) =
+            mem::take(attrs).into_iter().partition(|attr| attr.has_name(sym::link));
+        *attrs = rest;
+        let mut items = ThinVec::new();
+        let filename = rustc_span::FileName::Custom(format!("import fn {ident}"));
+        match crate::source_str_to_stream(self.psess, filename, source, Some(ident.span)) {
+            Ok(stream) => {
+                let mut parser = Parser::new(self.psess, stream, None);
+                attrs.extend(parser.parse_inner_attributes()?);
+                while let Some(mut item) = parser.parse_item(ForceCollect::No)? {
+                    if let ItemKind::ForeignMod(_) = item.kind {
+                        item.attrs.extend(link.iter().cloned());
+                    }
+                    items.push(item);
+                }
+            }
+            Err(errs) => errs.into_iter().for_each(|err| _ = err.emit()),
+        }
+
+        self.psess.script_import_fns.borrow_mut().insert(ident.name);
+        let module = Ident::from_str_and_span(&format!("__import_{ident}"), ident.span);
+        let spans = ModSpans { inner_span: lo.to(self.prev_token.span), inject_use_span: lo.shrink_to_lo() };
+        Ok(ItemKind::Mod(Safety::Default, module, ModKind::Loaded(items, Inline::Yes, spans)))
+    }
+
     /// Parse a function starting from the front matter (`const ...`) to the body `{ ... }` or `;`.
     fn parse_fn(
         &mut self,
@@ -3208,22 +3658,23 @@ fn parse_fn(
         case: Case,
     ) -> PResult<'a, (Ident, FnSig, Generics, Option<Box<FnContract>>, Option<Box<Block>>)> {
         let fn_span = self.token.span;
+        let _scope = self.script_scope();
         let header = self.parse_fn_front_matter(vis, case, FrontMatterParsingMode::Function)?; // `const ... fn`
         let ident = self.parse_ident()?; // `foo`
         let mut generics = self.parse_generics()?; // `<'a, T, ...>`
-        let decl = match self.parse_fn_decl(&fn_parse_mode, AllowPlus::Yes, RecoverReturnSign::Yes)
-        {
-            Ok(decl) => decl,
-            Err(old_err) => {
-                // If we see `for Ty ...` then user probably meant `impl` item.
-                if self.token.is_keyword(kw::For) {
-                    old_err.cancel();
-                    return Err(self.dcx().create_err(errors::FnTypoWithImpl { fn_span }));
-                } else {
-                    return Err(old_err);
+        let mut decl =
+            match self.parse_fn_decl(&fn_parse_mode, AllowPlus::Yes, RecoverReturnSign::Yes) {
+                Ok(decl) => decl,
+                Err(old_err) => {
+                    // If we see `for Ty ...` then user probably meant `impl` item.
+                    if self.token.is_keyword(kw::For) {
+                        old_err.cancel();
+                        return Err(self.dcx().create_err(errors::FnTypoWithImpl { fn_span }));
+                    } else {
+                        return Err(old_err);
+                    }
                 }
-            }
-        };
+            };
 
         // Store the end of function parameters to give better diagnostics
         // inside `parse_fn_body()`.
@@ -3237,14 +3688,39 @@ fn parse_fn(
         let fn_params_end =
             if generics.where_clause.has_where_token { Some(fn_params_end) } else { None };
 
+        let error_fn = self.is_script_mode() && self.lower_script_error_ret_ty(&mut decl);
+
         let mut sig_hi = self.prev_token.span;
         // Either `;` or `{ ... }`.
-        let body =
-            self.parse_fn_body(attrs, &ident, &mut sig_hi, fn_parse_mode.req_body, fn_params_end)?;
+        let body = self.with_script_error_fn(error_fn, |this| {
+            this.parse_fn_body(attrs, &ident, &mut sig_hi, fn_parse_mode.req_body, fn_params_end)
+        })?;
         let fn_sig_span = sig_lo.to(sig_hi);
         Ok((ident, FnSig { header, decl, span: fn_sig_span }, generics, contract, body))
     }
 
+    /// Script mode: the Go-style return type `error` is `Result<(), Box<dyn Error>>`, so
+    /// `def parse() error { ... }` can use `try` and `return errors.New("...")`.
+    /// Returns whether `decl` returned `error`.
+    fn lower_script_error_ret_ty(&self, decl: &mut FnDecl) -> bool {
+        let FnRetTy::Ty(ty) = &mut decl.output else { return false };
+        let TyKind::Path(None, path) = &ty.kind else { return false };
+        let [segment] = &path.segments[..] else { return false };
+        if segment.ident.name != sym::error || segment.args.is_some() {
+            return false;
+        }
+        let source = "::std::result::Result<(), ::std::boxed::Box<dyn ::std::error::Error>>";
+        let filename = rustc_span::FileName::Custom("script error type".into());
+        match crate::source_str_to_stream(self.psess, filename, source.into(), Some(ty.span)) {
+            Ok(stream) => match Parser::new(self.psess, stream, None).parse_ty() {
+                Ok(result) => *ty = result,
+                Err(err) => _ = err.emit(),
+            },
+            Err(errs) => errs.into_iter().for_each(|err| _ = err.emit()),
+        }
+        true
+    }
+
     /// Provide diagnostics when function body is not found
     fn error_fn_body_not_found(
         &mut self,
@@ -4136,3 +4612,74 @@ pub(super) enum FrontMatterParsingMode {
     /// For function pointer types, the `const` and `async` keywords are not permitted.
     FunctionPtrType,
 }
+
+/// Script files an `import` path refers to: the file itself, the file with a
+/// `.rust`/`.rs` extension, or every script file in the folder (sorted by name).
+/// A path naming both a folder of scripts and a script file is ambiguous; a trailing
+/// `/` picks the folder, an explicit extension picks the file.
+fn script_import_files(
+    path: &str,
+    target: &std::path::Path,
+) -> Result<Vec<std::path::PathBuf>, String> {
+    let is_script = |path: &std::path::Path| {
+        path.is_file() && matches!(path.extension().and_then(|e| e.to_str()), Some("rust" | "rs"))
+    };
+    let mut folder: Vec<_> = std::fs::read_dir(target)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| is_script(path))
+        .collect();
+    folder.sort();
+    if path.ends_with('/') {
+        return if folder.is_empty() {
+            Err(format!("cannot import `{path}`: the folder contains no script files"))
+        } else {
+            Ok(folder)
+        };
+    }
+    let file = if target.is_file() {
+        Some(target.to_path_buf())
+    } else {
+        ["rust", "rs"].iter().map(|ext| target.with_extension(ext)).find(|path| path.is_file())
+    };
+    match (file, folder.is_empty()) {
+        (Some(file), false) => {
+            let ext = file.extension().and_then(|e| e.to_str()).unwrap_or("rust");
+            Err(format!(
+                "cannot import `{path}`: it names both a folder and a script file; \
+                 use `{path}/` for the folder or `{path}.{ext}` for the file"
+            ))
+        }
+        (Some(file), true) => Ok(vec![file]),
+        (None, false) => Ok(folder),
+        (None, true) => Err(format!("cannot find script file or folder `{path}` to import")),
+    }
+}
+
+/// The body of `fn name!`, with its own `name!` calls going to the built-in macro:
+/// `crate::__script_helpers::name!`.
+fn builtin_macro_calls(name: Symbol, stream: &TokenStream) -> TokenStream {
+    let trees: Vec<&TokenTree> = stream.iter().collect();
+    let mut out = Vec::with_capacity(trees.len());
+    for (i, tree) in trees.iter().enumerate() {
+        let is_token = |tree: Option<&&TokenTree>, kind: TokenKind| {
+            matches!(tree, Some(TokenTree::Token(token, _)) if token.kind == kind)
+        };
+        match tree {
+            TokenTree::Token(token, _)
+                if token.is_ident_named(name)
+                    && is_token(trees.get(i + 1), token::Bang)
+                    && !is_token(i.checked_sub(1).and_then(|j| trees.get(j)), token::PathSep) =>
+            {
+                out.extend(script_helper_path_tokens(name, token.span));
+            }
+            TokenTree::Delimited(dspan, spacing, delim, inner) => {
+                let inner = builtin_macro_calls(name, inner);
+                out.push(TokenTree::Delimited(*dspan, *spacing, *delim, inner));
+            }
+            tree => out.push((*tree).clone()),
+        }
+    }
+    TokenStream::new(out)
+}
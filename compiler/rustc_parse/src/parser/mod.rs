@@ -235,6 +235,9 @@ pub struct Parser<'a> {
     /// Whether the parser is in script mode (shebang file or -Z script).
     /// Enables Python-like string behavior where "foo" becomes String.
     script_mode: bool,
+    /// Whether this is the body of a script function returning `error`, where `return nil`
+    /// means `return Ok(())`.
+    script_error_fn: bool = false,
 }
 
 // This type is used a lot, e.g. it's cloned when matching many declarative macro rules with
@@ -1502,6 +1505,14 @@ fn with_res<T>(&mut self, res: Restrictions, f: impl FnOnce(&mut Self) -> T) ->
         res
     }
 
+    /// Parses with `return nil` meaning `return Ok(())` or, if `!error_fn`, `return None`.
+    fn with_script_error_fn<T>(&mut self, error_fn: bool, f: impl FnOnce(&mut Self) -> T) -> T {
+        let old = mem::replace(&mut self.script_error_fn, error_fn);
+        let res = f(self);
+        self.script_error_fn = old;
+        res
+    }
+
     /// Parses `pub` and `pub(in path)` plus shortcuts `pub(crate)` for `pub(in crate)`, `pub(self)`
     /// for `pub(in self)` and `pub(super)` for `pub(in super)`.
     /// If the following element can't be a tuple (i.e., it's a function definition), then
@@ -1389,6 +1389,7 @@ fn parse_pat_ident(
         {
             PatKind::Err(guar)
         } else {
+            self.note_script_binding(ident);
             PatKind::Ident(binding_annotation, ident, sub)
         };
         Ok(pat)
@@ -1769,6 +1770,7 @@ fn parse_pat_field(&mut self, lo: Span, attrs: AttrVec) -> PResult<'a, PatField>
     }
 
     pub(super) fn mk_pat_ident(&self, span: Span, ann: BindingMode, ident: Ident) -> Pat {
+        self.note_script_binding(ident);
         self.mk_pat(span, PatKind::Ident(ann, ident, None))
     }
 
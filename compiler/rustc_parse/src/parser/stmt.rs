@@ -140,6 +140,17 @@ pub fn parse_stmt_without_recovery(
                     sub: errors::InvalidVariableDeclarationSub::UseLetNotVar(lo),
                 }));
             }
+        } else if self.is_script_try_stmt() {
+            // Script mode: `try f()` and `try x := f()` return early on an `Err`, like `f()?`.
+            self.bump(); // `try`
+            self.error_outer_attrs(attrs);
+            match self.parse_stmt_without_recovery(capture_semi, force_collect, force_full_expr)? {
+                Some(stmt) => self.mk_script_try_stmt(lo, stmt)?,
+                None => {
+                    let msg = "expected an expression or `:=` after `try`";
+                    return Err(self.dcx().struct_span_err(self.token.span, msg));
+                }
+            }
         } else if self.check_path()
             && !self.token.is_qpath_start()
             && !self.is_path_start_item()
@@ -211,6 +222,42 @@ pub fn parse_stmt_without_recovery(
         Ok(Some(stmt))
     }
 
+    /// Whether this is a script-mode `try` statement rather than a `try { .. }` block.
+    fn is_script_try_stmt(&self) -> bool {
+        self.is_script_mode()
+            && self.token.is_keyword(kw::Try)
+            && self.look_ahead(1, |t| t.can_begin_expr() && *t != token::OpenBrace)
+    }
+
+    /// Applies `?` to the value computed by the statement after a script-mode `try`:
+    /// `try f()` is `f()?`, `try x := f()` is `let mut x = f()?` and `try x = f()` is
+    /// `x = f()?`.
+    fn mk_script_try_stmt(&self, lo: Span, mut stmt: Stmt) -> PResult<'a, Stmt> {
+        let mk_try = |this: &Self, value: &mut Box<Expr>| {
+            let inner = mem::replace(value, Box::new(Expr::dummy()));
+            *value = this.mk_expr(inner.span, ExprKind::Try(inner));
+        };
+        match &mut stmt.kind {
+            StmtKind::Let(local) => match &mut local.kind {
+                LocalKind::Init(init) => mk_try(self, init),
+                _ => return Err(self.dcx().struct_span_err(stmt.span, "`try` needs a value")),
+            },
+            StmtKind::Expr(expr) | StmtKind::Semi(expr) => {
+                if let ExprKind::Assign(_, rhs, _) = &mut expr.kind {
+                    mk_try(self, rhs)
+                } else {
+                    mk_try(self, expr)
+                }
+            }
+            _ => {
+                let msg = "expected an expression or `:=` after `try`";
+                return Err(self.dcx().struct_span_err(stmt.span, msg));
+            }
+        }
+        stmt.span = lo.to(stmt.span);
+        Ok(stmt)
+    }
+
     fn parse_stmt_path_start(&mut self, lo: Span, attrs: AttrWrapper) -> PResult<'a, Stmt> {
         let stmt = self.collect_tokens(None, attrs, ForceCollect::No, |this, attrs| {
             let path = this.parse_path(PathStyle::Expr)?;
@@ -793,6 +840,7 @@ pub fn parse_block_tail(
         s: BlockCheckMode,
         recover: AttemptLocalParseRecovery,
     ) -> PResult<'a, Box<Block>> {
+        let _scope = self.script_scope();
         let mut stmts = ThinVec::new();
         let mut snapshot = None;
         while !self.eat(exp!(CloseBrace)) {
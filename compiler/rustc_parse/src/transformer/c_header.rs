@@ -0,0 +1,1466 @@
+//! `include "foo.h"` and `include foo from "foo.h"`.
+//!
+//! The include becomes a module declaring what a script needs from a C header, so a C library
+//! can be called without a bindgen step. The reader understands a subset of C:
+//!
+//! * function prototypes, including variadic ones, which go into one `unsafe extern "C"` block
+//!   with `#[link(name = "foo")]`. They are all unsafe to call: a prototype doesn't show whether
+//!   a function is sound for every argument (think `close` or `kill`). A header without
+//!   functions, or a C library header such as `stdio.h`, doesn't link anything, and `math.h`
+//!   links `libm` (see `header_library`);
+//! * structs, as `#[repr(C)]` structs when every field can be represented, and as opaque types
+//!   that are only usable behind pointers otherwise (forward declarations, bitfields, unions);
+//! * enums, as `#[repr(transparent)]` integer newtypes with a constant per enumerator, named
+//!   like in C. A C function may return any value of the underlying integer (flags OR-ed
+//!   together, values added in a newer version of the library), which a Rust enum can't hold;
+//! * `typedef`s of the above, including function pointer types;
+//! * `#define NAME <integer expression>` as constants.
+//!
+//! The preprocessor evaluates conditionals as a C compiler for the target would, with only the
+//! standard and platform macros predefined, so compiler-specific branches (`__GNUC__`,
+//! `__cplusplus`, `_MSC_VER`) are left out. Macros are expanded, with `##` only pasting
+//! identifiers and numbers. Quoted `#include`s are read relative to the including header;
+//! `<system>` headers are not, so their types are only usable behind pointers (as `c_void`).
+//! Inline functions and global variables are skipped, and of several declarations of a name
+//! the first one wins.
+
+use std::path::{Path, PathBuf};
+
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_session::config::Cfg;
+
+/// Reads `path` with `load`, splicing in the quoted headers it includes, each one at most once.
+pub(crate) fn read_header(
+    path: &Path,
+    load: &mut dyn FnMut(&Path) -> std::io::Result<String>,
+) -> Result<String, String> {
+    let mut out = String::new();
+    read_into(path, load, &mut FxHashSet::default(), &mut out)?;
+    Ok(out)
+}
+
+fn read_into(
+    path: &Path,
+    load: &mut dyn FnMut(&Path) -> std::io::Result<String>,
+    seen: &mut FxHashSet<PathBuf>,
+    out: &mut String,
+) -> Result<(), String> {
+    if !seen.insert(path.canonicalize().unwrap_or_else(|_| path.to_path_buf())) {
+        return Ok(());
+    }
+    let text = load(path).map_err(|err| format!("{}: {err}", path.display()))?;
+    for line in text.lines() {
+        let directive = line.trim_start().strip_prefix('#').map(str::trim_start);
+        let quoted = directive
+            .and_then(|d| d.strip_prefix("include"))
+            .and_then(|rest| rest.trim().strip_prefix('"'))
+            .and_then(|rest| rest.split('"').next());
+        if let Some(name) = quoted {
+            let included = path.parent().unwrap_or(Path::new(".")).join(name);
+            if included.is_file() {
+                read_into(&included, load, seen, out)?;
+            }
+            continue;
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    Ok(())
+}
+
+/// Headers of the C library, which every program links already.
+const LIBC_HEADERS: &[&str] = &[
+    "assert.h",
+    "ctype.h",
+    "errno.h",
+    "fcntl.h",
+    "inttypes.h",
+    "limits.h",
+    "locale.h",
+    "setjmp.h",
+    "signal.h",
+    "stdarg.h",
+    "stddef.h",
+    "stdint.h",
+    "stdio.h",
+    "stdlib.h",
+    "string.h",
+    "strings.h",
+    "time.h",
+    "unistd.h",
+    "wchar.h",
+    "wctype.h",
+];
+
+/// Headers whose library isn't named after the header.
+const HEADER_LIBRARIES: &[(&str, &str)] = &[
+    ("complex.h", "m"),
+    ("fenv.h", "m"),
+    ("math.h", "m"),
+    ("tgmath.h", "m"),
+    ("pthread.h", "pthread"),
+    ("dlfcn.h", "dl"),
+];
+
+/// The library to link for `include "header"` without an explicit name: none for the C
+/// library's own headers, `m` for `math.h` and the like, and otherwise the file stem, so
+/// `include "foo.h"` links `libfoo`.
+pub(crate) fn header_library<'h>(header: &'h str, stem: &'h str) -> Option<&'h str> {
+    let file = Path::new(header).file_name().and_then(|f| f.to_str()).unwrap_or(header);
+    if LIBC_HEADERS.contains(&file) {
+        return None;
+    }
+    let library = HEADER_LIBRARIES.iter().find(|(h, _)| *h == file).map(|(_, library)| *library);
+    Some(library.unwrap_or(stem))
+}
+
+/// Directories searched for a header that isn't next to the script: those in `CPATH` and
+/// `C_INCLUDE_PATH`, as a C compiler would, then the usual system directories when the target
+/// is a Unix. `env` reads (and records) an environment variable.
+pub(crate) fn include_dirs(
+    target: &Cfg,
+    env: &mut dyn FnMut(&str) -> Option<String>,
+) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = ["CPATH", "C_INCLUDE_PATH"]
+        .into_iter()
+        .filter_map(|var| env(var))
+        .flat_map(|paths| std::env::split_paths(&paths).collect::<Vec<_>>())
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .collect();
+    if has_cfg(target, "unix", None) {
+        dirs.extend(["/usr/local/include".into(), "/usr/include".into()]);
+    }
+    dirs
+}
+
+/// Source of the module an `include` expands to, from the header text, the library to link (if
+/// any) and the target's `cfg`.
+pub(crate) fn header_module_source(library: Option<&str>, header: &str, target: &Cfg) -> String {
+    let mut decls = Decls::default();
+    let code = decls.preprocess(&strip_comments(header), target);
+    decls.toks = decls.expand(&tokenize(&code), 0);
+    decls.end = decls.toks.len();
+    decls.read();
+    decls.module_source(library)
+}
+
+/// Whether `cfg(name)` or `cfg(name = "value")` holds for the target.
+fn has_cfg(target: &Cfg, name: &str, value: Option<&str>) -> bool {
+    target.iter().any(|(n, v)| n.as_str() == name && v.map(|v| v.as_str()) == value)
+}
+
+/// Macros a C compiler for the target defines before reading the header.
+fn predefined_macros(target: &Cfg) -> Vec<&'static str> {
+    let mut macros = vec!["__STDC__ 1", "__STDC_VERSION__ 201710L", "__STDC_HOSTED__ 1"];
+    let unix = has_cfg(target, "unix", None);
+    if unix {
+        macros.push("__unix__ 1");
+    }
+    if has_cfg(target, "target_os", Some("linux")) {
+        macros.push("__linux__ 1");
+    }
+    if has_cfg(target, "target_vendor", Some("apple")) {
+        macros.push("__APPLE__ 1");
+    }
+    if has_cfg(target, "windows", None) {
+        macros.push("_WIN32 1");
+    }
+    if unix && has_cfg(target, "target_pointer_width", Some("64")) {
+        macros.push("__LP64__ 1");
+    }
+    if has_cfg(target, "target_arch", Some("x86_64")) {
+        macros.push("__x86_64__ 1");
+    }
+    if has_cfg(target, "target_arch", Some("aarch64")) {
+        macros.push("__aarch64__ 1");
+    }
+    macros
+}
+
+/// Joins continued lines and replaces comments with a space, keeping line breaks.
+fn strip_comments(text: &str) -> String {
+    let text = text.replace("\\\r\n", " ").replace("\\\n", " ");
+    let mut stripped = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '/' if chars.peek() == Some(&'/') => {
+                while chars.peek().is_some_and(|&c| c != '\n') {
+                    chars.next();
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    if c == '\n' {
+                        stripped.push('\n');
+                    }
+                    prev = c;
+                }
+                stripped.push(' ');
+            }
+            '"' | '\'' => {
+                stripped.push(c);
+                while let Some(next) = chars.next() {
+                    stripped.push(next);
+                    if next == '\\' {
+                        stripped.extend(chars.next());
+                    } else if next == c || next == '\n' {
+                        break;
+                    }
+                }
+            }
+            _ => stripped.push(c),
+        }
+    }
+    stripped
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Tok {
+    Ident(String),
+    Num(String),
+    Str,
+    Punct(String),
+}
+
+fn tokenize(code: &str) -> Vec<Tok> {
+    let chars: Vec<char> = code.chars().collect();
+    let mut toks = Vec::new();
+    let mut i = 0;
+    'outer: while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            toks.push(Tok::Ident(chars[start..i].iter().collect()));
+        } else if c.is_ascii_digit()
+            || (c == '.' && chars.get(i + 1).is_some_and(char::is_ascii_digit))
+        {
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '.') {
+                i += 1;
+            }
+            toks.push(Tok::Num(chars[start..i].iter().collect()));
+        } else if c == '"' || c == '\'' {
+            i += 1;
+            while i < chars.len() && chars[i] != c {
+                i += if chars[i] == '\\' { 2 } else { 1 };
+            }
+            i += 1;
+            toks.push(Tok::Str);
+        } else {
+            for punct in ["...", "<<", ">>", "&&", "||", "==", "!=", "<=", ">=", "##"] {
+                if chars[i..].starts_with(&punct.chars().collect::<Vec<_>>()) {
+                    toks.push(Tok::Punct(punct.to_string()));
+                    i += punct.len();
+                    continue 'outer;
+                }
+            }
+            toks.push(Tok::Punct(c.to_string()));
+            i += 1;
+        }
+    }
+    toks
+}
+
+#[derive(Clone, Debug)]
+enum CType {
+    Void,
+    /// A Rust type for a C arithmetic type, e.g. `::std::ffi::c_int` or `usize`.
+    Prim(&'static str),
+    /// A struct, enum or typedef name, or a type declared somewhere the reader didn't look.
+    Named(String),
+    /// Pointee and whether it is `const`.
+    Ptr(Box<CType>, bool),
+    Array(Box<CType>, Option<u64>),
+    /// Return type, named parameters and whether the function is variadic.
+    Func(Box<CType>, Params, bool),
+}
+
+/// Parameter names, if given, and types.
+type Params = Vec<(Option<String>, CType)>;
+
+/// The Rust type for a combination of C type keywords, e.g. `unsigned long int`.
+fn arithmetic_type(words: &[&str]) -> Option<&'static str> {
+    let count = |word: &str| words.iter().filter(|w| **w == word).count();
+    let unsigned = count("unsigned") > 0;
+    let ty = match () {
+        _ if count("_Bool") + count("bool") > 0 => "bool",
+        _ if count("float") > 0 => "f32",
+        _ if count("double") > 0 && count("long") > 0 => return None,
+        _ if count("double") > 0 => "f64",
+        _ if count("char") > 0 && unsigned => "::std::ffi::c_uchar",
+        _ if count("char") > 0 && count("signed") > 0 => "::std::ffi::c_schar",
+        _ if count("char") > 0 => "::std::ffi::c_char",
+        _ if count("short") > 0 && unsigned => "::std::ffi::c_ushort",
+        _ if count("short") > 0 => "::std::ffi::c_short",
+        _ if count("long") > 1 && unsigned => "::std::ffi::c_ulonglong",
+        _ if count("long") > 1 => "::std::ffi::c_longlong",
+        _ if count("long") > 0 && unsigned => "::std::ffi::c_ulong",
+        _ if count("long") > 0 => "::std::ffi::c_long",
+        _ if unsigned => "::std::ffi::c_uint",
+        _ => "::std::ffi::c_int",
+    };
+    Some(ty)
+}
+
+/// Standard typedefs with a fixed Rust equivalent.
+const STD_TYPEDEFS: &[(&str, &str)] = &[
+    ("size_t", "usize"),
+    ("ssize_t", "isize"),
+    ("ptrdiff_t", "isize"),
+    ("intptr_t", "isize"),
+    ("uintptr_t", "usize"),
+    ("int8_t", "i8"),
+    ("int16_t", "i16"),
+    ("int32_t", "i32"),
+    ("int64_t", "i64"),
+    ("uint8_t", "u8"),
+    ("uint16_t", "u16"),
+    ("uint32_t", "u32"),
+    ("uint64_t", "u64"),
+];
+
+const TYPE_WORDS: &[&str] = &[
+    "void", "char", "short", "int", "long", "float", "double", "signed", "unsigned", "_Bool",
+    "bool",
+];
+
+/// Keywords that qualify a declaration without changing what Rust needs to know about it.
+const QUALIFIERS: &[&str] = &[
+    "extern",
+    "register",
+    "auto",
+    "volatile",
+    "__volatile__",
+    "restrict",
+    "__restrict",
+    "__restrict__",
+    "__extension__",
+    "_Noreturn",
+    "_Thread_local",
+    "_Nullable",
+    "_Nonnull",
+    "_Null_unspecified",
+    "__nullable",
+    "__nonnull",
+];
+
+/// Keywords followed by a parenthesized argument that is dropped.
+const ATTRIBUTES: &[&str] =
+    &["__attribute__", "__attribute", "__declspec", "__asm__", "__asm", "asm"];
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "crate", "do", "dyn", "else",
+    "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let", "loop",
+    "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "self",
+    "Self", "static", "struct", "super", "trait", "true", "try", "type", "typeof", "unsafe",
+    "unsized", "use", "virtual", "where", "while", "yield", "abstract", "become",
+];
+
+/// `name`, with a trailing `_` if it is a Rust keyword.
+fn rust_name(name: &str) -> String {
+    if RUST_KEYWORDS.contains(&name) { format!("{name}_") } else { name.to_string() }
+}
+
+struct StructDef {
+    name: String,
+    /// `None` for a struct that is opaque to Rust.
+    fields: Option<Vec<(String, CType)>>,
+}
+
+struct EnumDef {
+    name: String,
+    variants: Vec<(String, i128)>,
+}
+
+struct FnDef {
+    name: String,
+    ret: CType,
+    params: Params,
+    variadic: bool,
+}
+
+/// Declarations read so far, and the reader's position in the header's tokens.
+#[derive(Default)]
+struct Decls {
+    toks: Vec<Tok>,
+    pos: usize,
+    /// The reader doesn't look past here; moved in while reading a nested declarator.
+    end: usize,
+    /// Object-like macros that aren't integer constants.
+    macros: FxHashMap<String, Vec<Tok>>,
+    fn_macros: FxHashMap<String, FnMacro>,
+    /// Integer `#define`s and enumerators, for evaluating constant expressions.
+    values: FxHashMap<String, i128>,
+    consts: Vec<(String, i128)>,
+    structs: Vec<StructDef>,
+    enums: Vec<EnumDef>,
+    typedefs: Vec<(String, CType)>,
+    fns: Vec<FnDef>,
+    /// Every name declared so far; the first declaration wins.
+    declared: FxHashSet<String>,
+    anonymous: usize,
+}
+
+struct Conditional {
+    outer_active: bool,
+    /// Whether lines are read in the current branch.
+    active: bool,
+    /// Whether any branch so far was taken.
+    taken: bool,
+}
+
+struct FnMacro {
+    /// Parameter names, with `...` last for a variadic macro.
+    params: Vec<String>,
+    body: Vec<Tok>,
+}
+
+impl FnMacro {
+    fn substitute(&self, args: &[Vec<Tok>]) -> Vec<Tok> {
+        let arg = |name: &str| -> Option<Vec<Tok>> {
+            if name == "__VA_ARGS__" {
+                let start = self.params.len() - 1;
+                let rest = args.get(start..).unwrap_or_default();
+                return Some(rest.join(&Tok::Punct(",".into())));
+            }
+            let index = self.params.iter().position(|param| param == name)?;
+            Some(args.get(index).cloned().unwrap_or_default())
+        };
+        let mut out: Vec<Tok> = Vec::with_capacity(self.body.len());
+        let mut paste = false;
+        let mut toks = self.body.iter().peekable();
+        while let Some(tok) = toks.next() {
+            let replacement = match tok {
+                Tok::Punct(p) if p == "##" => {
+                    paste = true;
+                    continue;
+                }
+                Tok::Punct(p) if p == "#" => match toks.peek() {
+                    Some(Tok::Ident(name)) if arg(name).is_some() => {
+                        toks.next();
+                        vec![Tok::Str]
+                    }
+                    _ => vec![tok.clone()],
+                },
+                Tok::Ident(name) => arg(name).unwrap_or_else(|| vec![tok.clone()]),
+                _ => vec![tok.clone()],
+            };
+            let mut replacement = replacement.into_iter();
+            if std::mem::take(&mut paste)
+                && let Some(last) = out.pop()
+            {
+                match (last, replacement.next()) {
+                    (Tok::Ident(a) | Tok::Num(a), Some(Tok::Ident(b) | Tok::Num(b))) => {
+                        out.push(Tok::Ident(a + &b))
+                    }
+                    (last, next) => out.extend([last].into_iter().chain(next)),
+                }
+            }
+            out.extend(replacement);
+        }
+        out
+    }
+}
+
+/// The parameters of a function-like macro, removing them from the front of `body`.
+fn body_params(has_params: bool, body: &mut Vec<Tok>) -> Option<Vec<String>> {
+    if !has_params {
+        return None;
+    }
+    let end = body.iter().position(|tok| *tok == Tok::Punct(")".into()))?;
+    let params = body[1..end]
+        .iter()
+        .filter_map(|tok| match tok {
+            Tok::Ident(name) => Some(name.clone()),
+            Tok::Punct(p) if p == "..." => Some("...".into()),
+            _ => None,
+        })
+        .collect();
+    body.drain(..=end);
+    Some(params)
+}
+
+/// The comma-separated arguments of a macro call starting at `(`, and the number of tokens up
+/// to and including the closing `)`.
+fn macro_args(toks: &[Tok]) -> Option<(Vec<Vec<Tok>>, usize)> {
+    if toks.first() != Some(&Tok::Punct("(".into())) {
+        return None;
+    }
+    let mut args = vec![Vec::new()];
+    let mut depth = 0;
+    for (i, tok) in toks.iter().enumerate().skip(1) {
+        match tok {
+            Tok::Punct(p) if p == ")" && depth == 0 => {
+                if args.len() == 1 && args[0].is_empty() {
+                    args.clear();
+                }
+                return Some((args, i + 1));
+            }
+            Tok::Punct(p) if p == "," && depth == 0 => {
+                args.push(Vec::new());
+                continue;
+            }
+            Tok::Punct(p) if p == "(" => depth += 1,
+            Tok::Punct(p) if p == ")" => depth -= 1,
+            _ => {}
+        }
+        args.last_mut().unwrap().push(tok.clone());
+    }
+    None
+}
+
+/// What precedes the declarators of a declaration.
+struct Specifiers {
+    ty: CType,
+    is_const: bool,
+    is_typedef: bool,
+    /// `static` or `inline`, so there's nothing to link against.
+    is_local: bool,
+}
+
+impl Decls {
+    /// Evaluates the conditionals and `#define`s of the header, returning the code in the
+    /// branches that are taken.
+    fn preprocess(&mut self, text: &str, target: &Cfg) -> String {
+        for define in predefined_macros(target) {
+            self.define(define);
+        }
+        self.consts.clear();
+        let mut code = String::new();
+        let mut conditionals: Vec<Conditional> = Vec::new();
+        for line in text.lines() {
+            let active = conditionals.last().is_none_or(|cond| cond.active);
+            let Some(directive) = line.trim_start().strip_prefix('#') else {
+                if active {
+                    code.push_str(line);
+                    code.push('\n');
+                }
+                continue;
+            };
+            let directive = directive.trim_start();
+            let keyword_len = directive
+                .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                .unwrap_or(directive.len());
+            let (keyword, rest) = directive.split_at(keyword_len);
+            let rest = rest.trim();
+            let defined = |decls: &Self| decls.is_defined(rest);
+            match keyword {
+                "if" | "ifdef" | "ifndef" => {
+                    let taken = active
+                        && match keyword {
+                            "ifdef" => defined(self),
+                            "ifndef" => !defined(self),
+                            _ => self.condition(rest),
+                        };
+                    conditionals.push(Conditional { outer_active: active, active: taken, taken });
+                }
+                "elif" | "elifdef" | "elifndef" | "else" => {
+                    let Some(cond) = conditionals.last() else { continue };
+                    let taken = cond.outer_active
+                        && !cond.taken
+                        && match keyword {
+                            "elifdef" => defined(self),
+                            "elifndef" => !defined(self),
+                            "elif" => self.condition(rest),
+                            _ => true,
+                        };
+                    let cond = conditionals.last_mut().unwrap();
+                    cond.active = taken;
+                    cond.taken |= taken;
+                }
+                "endif" => _ = conditionals.pop(),
+                "define" if active => self.define(rest),
+                "undef" if active => {
+                    self.macros.remove(rest);
+                    self.fn_macros.remove(rest);
+                    self.values.remove(rest);
+                }
+                _ => {}
+            }
+        }
+        code
+    }
+
+    fn is_defined(&self, name: &str) -> bool {
+        self.macros.contains_key(name)
+            || self.fn_macros.contains_key(name)
+            || self.values.contains_key(name)
+    }
+
+    /// Evaluates the expression of an `#if`.
+    fn condition(&self, expr: &str) -> bool {
+        let toks = tokenize(expr);
+        let mut resolved = Vec::with_capacity(toks.len());
+        let mut i = 0;
+        while i < toks.len() {
+            if toks[i] == Tok::Ident("defined".into()) {
+                let name = match (toks.get(i + 1), toks.get(i + 2)) {
+                    (Some(Tok::Ident(name)), _) => {
+                        i += 2;
+                        name
+                    }
+                    (Some(Tok::Punct(p)), Some(Tok::Ident(name))) if p == "(" => {
+                        i += 4;
+                        name
+                    }
+                    _ => return false,
+                };
+                resolved.push(Tok::Num(if self.is_defined(name) { "1" } else { "0" }.into()));
+                continue;
+            }
+            resolved.push(toks[i].clone());
+            i += 1;
+        }
+
+        // Identifiers left after expansion are 0, and so are calls like `__has_include(<x.h>)`.
+        let expanded = self.expand(&resolved, 0);
+        let mut toks = Vec::with_capacity(expanded.len());
+        let mut i = 0;
+        while i < expanded.len() {
+            if let Tok::Ident(name) = &expanded[i]
+                && !self.values.contains_key(name)
+            {
+                i += 1;
+                if expanded.get(i) == Some(&Tok::Punct("(".into())) {
+                    i += macro_args(&expanded[i..]).map_or(expanded.len(), |(_, len)| len);
+                }
+                toks.push(Tok::Num("0".into()));
+                continue;
+            }
+            toks.push(expanded[i].clone());
+            i += 1;
+        }
+        self.eval(&toks).is_some_and(|value| value != 0)
+    }
+
+    fn define(&mut self, definition: &str) {
+        let name_len = definition
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .unwrap_or(definition.len());
+        let (name, body) = definition.split_at(name_len);
+        if name.is_empty() {
+            return;
+        }
+        self.macros.remove(name);
+        self.fn_macros.remove(name);
+        self.values.remove(name);
+
+        let mut body = tokenize(body);
+        // A function-like macro has its parameter list right after the name.
+        if let Some(params) = body_params(definition[name_len..].starts_with('('), &mut body) {
+            self.fn_macros.insert(name.to_string(), FnMacro { params, body });
+        } else if let Some(value) = self.eval(&self.expand(&body, 0)) {
+            self.values.insert(name.to_string(), value);
+            match self.consts.iter_mut().find(|(defined, _)| defined == name) {
+                Some((_, defined)) => *defined = value,
+                None => self.consts.push((name.to_string(), value)),
+            }
+        } else {
+            self.macros.insert(name.to_string(), body);
+        }
+    }
+
+    /// Expands macros in `toks`, recursively up to a small depth.
+    fn expand(&self, toks: &[Tok], depth: usize) -> Vec<Tok> {
+        let mut out = Vec::with_capacity(toks.len());
+        let mut i = 0;
+        while i < toks.len() {
+            let tok = &toks[i];
+            i += 1;
+            let Tok::Ident(name) = tok else {
+                out.push(tok.clone());
+                continue;
+            };
+            if depth < 16
+                && let Some(body) = self.macros.get(name)
+            {
+                out.extend(self.expand(body, depth + 1));
+            } else if depth < 16
+                && let Some(def) = self.fn_macros.get(name)
+                && let Some((args, len)) = macro_args(&toks[i..])
+            {
+                i += len;
+                out.extend(self.expand(&def.substitute(&args), depth + 1));
+            } else {
+                out.push(tok.clone());
+            }
+        }
+        out
+    }
+
+    fn peek(&self) -> Option<&Tok> {
+        if self.pos < self.end { self.toks.get(self.pos) } else { None }
+    }
+
+    fn peek_ident(&self) -> Option<&str> {
+        match self.peek() {
+            Some(Tok::Ident(ident)) => Some(ident),
+            _ => None,
+        }
+    }
+
+    fn is_punct(&self, punct: &str) -> bool {
+        matches!(self.peek(), Some(Tok::Punct(p)) if p == punct)
+    }
+
+    fn eat(&mut self, punct: &str) -> bool {
+        let found = self.is_punct(punct);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, punct: &str) -> Option<()> {
+        self.eat(punct).then_some(())
+    }
+
+    /// Skips a bracketed group starting at the current token.
+    fn skip_group(&mut self) -> Option<()> {
+        let mut depth = 0usize;
+        while let Some(tok) = self.peek().cloned() {
+            self.pos += 1;
+            if let Tok::Punct(p) = tok {
+                match p.as_str() {
+                    "(" | "[" | "{" => depth += 1,
+                    ")" | "]" | "}" => {
+                        depth = depth.checked_sub(1)?;
+                        if depth == 0 {
+                            return Some(());
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        None
+    }
+
+    /// Skips qualifiers and attributes.
+    fn skip_noise(&mut self) {
+        while let Some(ident) = self.peek_ident() {
+            if ATTRIBUTES.contains(&ident) {
+                self.pos += 1;
+                if self.is_punct("(") && self.skip_group().is_none() {
+                    return;
+                }
+            } else if QUALIFIERS.contains(&ident) {
+                self.pos += 1;
+            } else {
+                return;
+            }
+        }
+    }
+
+    /// Skips to the end of the current declaration, or past a function body.
+    fn skip_declaration(&mut self) {
+        let start = self.pos;
+        while let Some(tok) = self.peek() {
+            match tok {
+                Tok::Punct(p) if p == ";" => {
+                    self.pos += 1;
+                    return;
+                }
+                Tok::Punct(p) if p == "{" => {
+                    let is_body = self.pos > start
+                        && matches!(&self.toks[self.pos - 1], Tok::Punct(p) if p == ")");
+                    if self.skip_group().is_none() {
+                        self.pos = self.end;
+                    }
+                    if is_body {
+                        return;
+                    }
+                }
+                Tok::Punct(p) if p == "(" || p == "[" => {
+                    if self.skip_group().is_none() {
+                        self.pos = self.end;
+                    }
+                }
+                Tok::Punct(p) if p == "}" && self.pos == start => {
+                    self.pos += 1;
+                    return;
+                }
+                _ => self.pos += 1,
+            }
+        }
+    }
+
+    fn read(&mut self) {
+        while let Some(tok) = self.peek() {
+            // extern "C" { ... } around the whole header: read what is inside, drop the `}`.
+            if *tok == Tok::Ident("extern".into())
+                && self.toks.get(self.pos + 1) == Some(&Tok::Str)
+                && self.toks.get(self.pos + 2) == Some(&Tok::Punct("{".into()))
+            {
+                self.pos += 3;
+                continue;
+            }
+            if *tok == Tok::Punct(";".into()) || *tok == Tok::Punct("}".into()) {
+                self.pos += 1;
+                continue;
+            }
+            let start = self.pos;
+            if self.declaration().is_none() {
+                self.pos = start;
+                self.skip_declaration();
+            }
+        }
+    }
+
+    fn declaration(&mut self) -> Option<()> {
+        let specs = self.specifiers()?;
+        if specs.is_typedef {
+            loop {
+                let (name, ty) = self.declarator(specs.ty.clone(), specs.is_const)?;
+                self.typedef(name?, ty);
+                if !self.eat(",") {
+                    break;
+                }
+            }
+            return self.expect(";");
+        }
+        // A struct or enum declared on its own has already been recorded.
+        if self.eat(";") {
+            return Some(());
+        }
+        let mut fns = Vec::new();
+        loop {
+            let (name, ty) = self.declarator(specs.ty.clone(), specs.is_const)?;
+            // __THROW, __wur, __asm__("name") and similar after the declarator.
+            while self.peek_ident().is_some() {
+                self.pos += 1;
+                if self.is_punct("(") {
+                    self.skip_group()?;
+                }
+            }
+            if let (Some(name), CType::Func(ret, params, variadic)) = (name, ty) {
+                fns.push(FnDef { name, ret: *ret, params, variadic });
+            }
+            if !self.eat(",") {
+                break;
+            }
+        }
+        // Function definitions, variables and their initializers are skipped.
+        if self.is_punct("{") {
+            return self.skip_group();
+        }
+        self.expect(";")?;
+        if !specs.is_local {
+            for def in fns {
+                if self.declared.insert(def.name.clone()) {
+                    self.fns.push(def);
+                }
+            }
+        }
+        Some(())
+    }
+
+    fn typedef(&mut self, name: String, ty: CType) {
+        if let CType::Named(target) = &ty {
+            // typedef struct foo foo;
+            if *target == name {
+                return;
+            }
+            // typedef struct { ... } foo;
+            if target.starts_with("__anonymous") {
+                let structs = self.structs.iter_mut().map(|def| &mut def.name);
+                let enums = self.enums.iter_mut().map(|def| &mut def.name);
+                if let Some(anonymous) = structs.chain(enums).find(|def| def.as_str() == target) {
+                    *anonymous = name;
+                    return;
+                }
+            }
+        }
+        if self.declared.insert(name.clone()) {
+            self.typedefs.push((name, ty));
+        }
+    }
+
+    fn specifiers(&mut self) -> Option<Specifiers> {
+        let mut specs =
+            Specifiers { ty: CType::Void, is_const: false, is_typedef: false, is_local: false };
+        let mut words = Vec::new();
+        let mut ty = None;
+        loop {
+            self.skip_noise();
+            let Some(ident) = self.peek_ident().map(str::to_string) else { break };
+            let word = TYPE_WORDS.iter().find(|w| **w == ident);
+            match ident.as_str() {
+                "typedef" => specs.is_typedef = true,
+                "static" | "inline" | "__inline" | "__inline__" => specs.is_local = true,
+                "const" | "__const" => specs.is_const = true,
+                "struct" | "union" | "enum" if ty.is_none() && words.is_empty() => {
+                    ty = Some(self.tagged_type()?);
+                    continue;
+                }
+                _ if let Some(word) = word
+                    && ty.is_none() =>
+                {
+                    words.push(*word)
+                }
+                _ if ty.is_none() && words.is_empty() => {
+                    ty = Some(match STD_TYPEDEFS.iter().find(|(c, _)| *c == ident) {
+                        Some((_, rust)) => CType::Prim(rust),
+                        None => CType::Named(ident),
+                    });
+                }
+                _ => break,
+            }
+            self.pos += 1;
+        }
+        specs.ty = match ty {
+            Some(ty) => ty,
+            None if words == ["void"] => CType::Void,
+            None if words.is_empty() || words.contains(&"void") => return None,
+            None => CType::Prim(arithmetic_type(&words)?),
+        };
+        Some(specs)
+    }
+
+    /// `struct Tag`, `struct [Tag] { ... }`, `enum [Tag] { ... }` and so on.
+    fn tagged_type(&mut self) -> Option<CType> {
+        let Some(Tok::Ident(kind)) = self.peek().cloned() else { return None };
+        self.pos += 1;
+        self.skip_noise();
+        let tag = self.peek_ident().map(str::to_string);
+        if tag.is_some() {
+            self.pos += 1;
+        }
+        self.skip_noise();
+        if !self.is_punct("{") {
+            let tag = tag?;
+            if kind != "enum" && !self.structs.iter().any(|s| s.name == tag) {
+                self.structs.push(StructDef { name: tag.clone(), fields: None });
+            }
+            return Some(CType::Named(tag));
+        }
+
+        let name = tag.unwrap_or_else(|| {
+            self.anonymous += 1;
+            format!("__anonymous{}", self.anonymous)
+        });
+        match kind.as_str() {
+            "enum" => {
+                let variants = self.enum_body()?;
+                if !self.enums.iter().any(|e| e.name == name) {
+                    self.enums.push(EnumDef { name: name.clone(), variants });
+                }
+            }
+            "struct" => {
+                let fields = self.struct_body();
+                match self.structs.iter_mut().find(|s| s.name == name) {
+                    Some(def) if def.fields.is_none() => def.fields = fields,
+                    Some(_) => {}
+                    None => self.structs.push(StructDef { name: name.clone(), fields }),
+                }
+            }
+            _ => {
+                self.skip_group()?;
+                if !self.structs.iter().any(|s| s.name == name) {
+                    self.structs.push(StructDef { name: name.clone(), fields: None });
+                }
+            }
+        }
+        Some(CType::Named(name))
+    }
+
+    fn enum_body(&mut self) -> Option<Vec<(String, i128)>> {
+        self.expect("{")?;
+        let mut variants = Vec::new();
+        let mut next = 0;
+        while !self.eat("}") {
+            let name = self.peek_ident()?.to_string();
+            self.pos += 1;
+            self.skip_noise();
+            if self.eat("=") {
+                let start = self.pos;
+                while self.peek().is_some() && !self.is_punct(",") && !self.is_punct("}") {
+                    if self.is_punct("(") {
+                        self.skip_group()?;
+                    } else {
+                        self.pos += 1;
+                    }
+                }
+                next = self.eval(&self.toks[start..self.pos])?;
+            }
+            self.values.entry(name.clone()).or_insert(next);
+            variants.push((name, next));
+            next += 1;
+            self.eat(",");
+        }
+        Some(variants)
+    }
+
+    /// Fields of a struct, or `None` (with the body skipped) if Rust can't lay it out the same.
+    fn struct_body(&mut self) -> Option<Vec<(String, CType)>> {
+        let end = {
+            let start = self.pos;
+            self.skip_group()?;
+            std::mem::replace(&mut self.pos, start + 1)
+        };
+        let outer_end = std::mem::replace(&mut self.end, end - 1);
+        let fields = self.fields();
+        self.end = outer_end;
+        self.pos = end;
+        fields
+    }
+
+    fn fields(&mut self) -> Option<Vec<(String, CType)>> {
+        let mut fields = Vec::new();
+        while self.peek().is_some() {
+            let specs = self.specifiers()?;
+            // Nested definitions and anonymous members don't have a Rust equivalent here.
+            if specs.is_typedef || self.is_punct(";") {
+                return None;
+            }
+            loop {
+                let (name, ty) = self.declarator(specs.ty.clone(), specs.is_const)?;
+                self.skip_noise();
+                // Bitfields
+                if self.is_punct(":") {
+                    return None;
+                }
+                fields.push((name?, ty));
+                if !self.eat(",") {
+                    break;
+                }
+            }
+            self.expect(";")?;
+        }
+        Some(fields)
+    }
+
+    /// A declarator applied to `base`, returning the declared name if there is one.
+    fn declarator(&mut self, base: CType, base_const: bool) -> Option<(Option<String>, CType)> {
+        let mut ty = base;
+        let mut pointee_const = base_const;
+        self.skip_noise();
+        while self.eat("*") {
+            ty = CType::Ptr(Box::new(ty), pointee_const);
+            pointee_const = false;
+            loop {
+                self.skip_noise();
+                match self.peek_ident() {
+                    Some("const" | "__const") => pointee_const = true,
+                    _ => break,
+                }
+                self.pos += 1;
+            }
+        }
+
+        // In `int (*name)(int)`, the parenthesized part applies to what the suffixes make.
+        let mut nested = None;
+        let mut name = None;
+        let next = self.toks.get(self.pos + 1);
+        if self.is_punct("(")
+            && matches!(next, Some(Tok::Punct(p)) if p == "*" || p == "(" || p == "^")
+        {
+            let start = self.pos + 1;
+            self.skip_group()?;
+            nested = Some((start, self.pos - 1));
+        } else if let Some(ident) = self.peek_ident()
+            && !QUALIFIERS.contains(&ident)
+            && !ATTRIBUTES.contains(&ident)
+        {
+            name = Some(ident.to_string());
+            self.pos += 1;
+        }
+
+        let mut suffixes = Vec::new();
+        loop {
+            self.skip_noise();
+            if self.is_punct("[") {
+                let start = self.pos + 1;
+                self.skip_group()?;
+                let len = &self.toks[start..self.pos - 1];
+                let len =
+                    if len.is_empty() { None } else { Some(u64::try_from(self.eval(len)?).ok()?) };
+                suffixes.push(Err(len));
+            } else if self.is_punct("(") {
+                suffixes.push(Ok(self.params()?));
+            } else {
+                break;
+            }
+        }
+        for suffix in suffixes.into_iter().rev() {
+            ty = match suffix {
+                Err(len) => CType::Array(Box::new(ty), len),
+                Ok((params, variadic)) => CType::Func(Box::new(ty), params, variadic),
+            };
+        }
+
+        let Some((start, end)) = nested else { return Some((name, ty)) };
+        let (pos, outer_end) = (self.pos, self.end);
+        (self.pos, self.end) = (start, end);
+        let declarator = self.declarator(ty, false);
+        let complete = self.pos == end;
+        (self.pos, self.end) = (pos, outer_end);
+        if complete { declarator } else { None }
+    }
+
+    fn params(&mut self) -> Option<(Params, bool)> {
+        self.expect("(")?;
+        let mut params = Vec::new();
+        let mut variadic = false;
+        if self.peek_ident() == Some("void")
+            && self.toks.get(self.pos + 1) == Some(&Tok::Punct(")".into()))
+        {
+            self.pos += 1;
+        }
+        while !self.eat(")") {
+            if self.eat("...") {
+                variadic = true;
+                continue;
+            }
+            let specs = self.specifiers()?;
+            let (name, ty) = self.declarator(specs.ty, specs.is_const)?;
+            // Array and function parameters are pointers.
+            let ty = match ty {
+                CType::Array(elem, _) => CType::Ptr(elem, false),
+                ty @ CType::Func(..) => CType::Ptr(Box::new(ty), false),
+                ty => ty,
+            };
+            params.push((name, ty));
+            if !self.eat(",") {
+                self.expect(")")?;
+                break;
+            }
+        }
+        Some((params, variadic))
+    }
+
+    /// Evaluates an integer constant expression.
+    fn eval(&self, toks: &[Tok]) -> Option<i128> {
+        let mut eval = Eval { toks, pos: 0, values: &self.values };
+        let value = eval.ternary()?;
+        (eval.pos == toks.len()).then_some(value)
+    }
+
+    fn module_source(&self, library: Option<&str>) -> String {
+        // C names keep their case.
+        let mut out = String::from(
+            "#![allow(non_camel_case_types, non_snake_case, non_upper_case_globals, dead_code)]\n",
+        );
+        let mut types: FxHashSet<&str> = FxHashSet::default();
+        let mut names: FxHashSet<String> = FxHashSet::default();
+
+        for (name, value) in &self.consts {
+            let ty = match *value {
+                v if i32::try_from(v).is_ok() => "i32",
+                v if u32::try_from(v).is_ok() => "u32",
+                v if i64::try_from(v).is_ok() => "i64",
+                _ => "u64",
+            };
+            let name = rust_name(name);
+            out.push_str(&format!("pub const {name}: {ty} = {value};\n"));
+            names.insert(name);
+        }
+
+        for def in self.enums.iter().filter(|e| !e.name.starts_with("__anonymous")) {
+            types.insert(&def.name);
+        }
+        let structs = self.structs.iter().filter(|s| !s.name.starts_with("__anonymous"));
+        for def in structs.clone() {
+            types.insert(&def.name);
+        }
+        // Typedefs whose target has a Rust equivalent, which struct fields may refer to.
+        let mut aliases = FxHashSet::default();
+        loop {
+            let resolved = self.typedefs.iter().filter(|(name, ty)| {
+                !types.contains(name.as_str()) && self.rust_ty(ty, &types).is_some()
+            });
+            let resolved: Vec<&str> = resolved.map(|(name, _)| name.as_str()).collect();
+            if resolved.is_empty() {
+                break;
+            }
+            types.extend(&resolved);
+            aliases.extend(resolved);
+        }
+        for def in &self.enums {
+            // The integer type C gives the enum: `int` when every value fits, as is usual.
+            let fits =
+                |min: i128, max: i128| def.variants.iter().all(|(_, v)| (min..=max).contains(v));
+            let repr = if fits(i32::MIN.into(), i32::MAX.into()) {
+                "::std::ffi::c_int"
+            } else if fits(0, u32::MAX.into()) {
+                "::std::ffi::c_uint"
+            } else if fits(i64::MIN.into(), i64::MAX.into()) {
+                "i64"
+            } else {
+                "u64"
+            };
+            let anonymous = def.name.starts_with("__anonymous");
+            let ty = if anonymous { repr.to_string() } else { rust_name(&def.name) };
+            if !anonymous {
+                out.push_str(
+                    "#[repr(transparent)]\n#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]\n",
+                );
+                out.push_str(&format!("pub struct {ty}(pub {repr});\n"));
+            }
+            for (variant, value) in &def.variants {
+                let variant = rust_name(variant);
+                if names.insert(variant.clone()) {
+                    let value = if anonymous { value.to_string() } else { format!("{ty}({value})") };
+                    out.push_str(&format!("pub const {variant}: {ty} = {value};\n"));
+                }
+            }
+        }
+
+        for def in structs {
+            let name = rust_name(&def.name);
+            let fields = def.fields.as_ref().and_then(|fields| {
+                fields
+                    .iter()
+                    .map(|(field, ty)| {
+                        Some(format!(
+                            "    pub {}: {},\n",
+                            rust_name(field),
+                            self.rust_ty(ty, &types)?
+                        ))
+                    })
+                    .collect::<Option<String>>()
+            });
+            match fields {
+                Some(fields) => {
+                    out.push_str("#[repr(C)]\n#[derive(Debug, Clone, Copy)]\n");
+                    out.push_str(&format!("pub struct {name} {{\n{fields}}}\n"));
+                }
+                None => out.push_str(&format!(
+                    "#[repr(C)]\npub struct {name} {{\n    _private: [u8; 0],\n}}\n"
+                )),
+            }
+        }
+
+        for (name, ty) in &self.typedefs {
+            if aliases.contains(name.as_str()) {
+                out.push_str(&format!(
+                    "pub type {} = {};\n",
+                    rust_name(name),
+                    self.rust_ty(ty, &types).unwrap()
+                ));
+            }
+        }
+
+        // A header of only types and constants doesn't need its library.
+        let mut fns = String::new();
+        for def in &self.fns {
+            let Some(sig) = self.rust_fn(def, &types) else { continue };
+            let name = rust_name(&def.name);
+            if name != def.name {
+                fns.push_str(&format!("    #[link_name = \"{}\"]\n", def.name));
+            }
+            fns.push_str(&format!("    pub fn {name}{sig};\n"));
+        }
+        if !fns.is_empty() {
+            if let Some(library) = library {
+                out.push_str(&format!("#[link(name = {library:?})]\n"));
+            }
+            out.push_str(&format!("unsafe extern \"C\" {{\n{fns}}}\n"));
+        }
+        out
+    }
+
+    /// `(a: T, ...) -> R` for a function, if every type has a Rust equivalent.
+    fn rust_fn(&self, def: &FnDef, types: &FxHashSet<&str>) -> Option<String> {
+        let mut params = Vec::new();
+        for (i, (name, ty)) in def.params.iter().enumerate() {
+            let name = name.as_deref().map_or_else(|| format!("arg{i}"), rust_name);
+            params.push(format!("{name}: {}", self.rust_ty(ty, types)?));
+        }
+        if def.variadic {
+            params.push("...".to_string());
+        }
+        let ret = match &def.ret {
+            CType::Void => String::new(),
+            ty => format!(" -> {}", self.rust_ty(ty, types)?),
+        };
+        Some(format!("({}){ret}", params.join(", ")))
+    }
+
+    fn rust_ty(&self, ty: &CType, types: &FxHashSet<&str>) -> Option<String> {
+        match ty {
+            CType::Void | CType::Func(..) | CType::Array(_, None) => None,
+            CType::Prim(rust) => Some(rust.to_string()),
+            CType::Named(name) => types.contains(name.as_str()).then(|| rust_name(name)),
+            CType::Array(elem, Some(len)) => {
+                Some(format!("[{}; {len}]", self.rust_ty(elem, types)?))
+            }
+            CType::Ptr(pointee, _) if let CType::Func(ret, params, variadic) = &**pointee => {
+                let def = FnDef {
+                    name: String::new(),
+                    ret: (**ret).clone(),
+                    params: params.clone(),
+                    variadic: *variadic,
+                };
+                let sig = self.rust_fn(&def, types)?;
+                Some(format!("Option<unsafe extern \"C\" fn{sig}>"))
+            }
+            CType::Ptr(pointee, is_const) => {
+                let pointee = self
+                    .rust_ty(pointee, types)
+                    .unwrap_or_else(|| "::std::ffi::c_void".to_string());
+                Some(format!("*{} {pointee}", if *is_const { "const" } else { "mut" }))
+            }
+        }
+    }
+}
+
+/// Integer constant expressions, for `#define`s, enumerators and array lengths.
+struct Eval<'a> {
+    toks: &'a [Tok],
+    pos: usize,
+    values: &'a FxHashMap<String, i128>,
+}
+
+const BINARY_OPS: &[&[&str]] = &[
+    &["||"],
+    &["&&"],
+    &["|"],
+    &["^"],
+    &["&"],
+    &["==", "!="],
+    &["<", ">", "<=", ">="],
+    &["<<", ">>"],
+    &["+", "-"],
+    &["*", "/", "%"],
+];
+
+impl Eval<'_> {
+    fn peek_punct(&self) -> Option<&str> {
+        match self.toks.get(self.pos) {
+            Some(Tok::Punct(p)) => Some(p),
+            _ => None,
+        }
+    }
+
+    fn ternary(&mut self) -> Option<i128> {
+        let condition = self.binary(0)?;
+        if self.peek_punct() != Some("?") {
+            return Some(condition);
+        }
+        self.pos += 1;
+        let then = self.ternary()?;
+        if self.peek_punct() != Some(":") {
+            return None;
+        }
+        self.pos += 1;
+        let otherwise = self.ternary()?;
+        Some(if condition != 0 { then } else { otherwise })
+    }
+
+    fn binary(&mut self, level: usize) -> Option<i128> {
+        if level == BINARY_OPS.len() {
+            return self.unary();
+        }
+        let mut lhs = self.binary(level + 1)?;
+        while let Some(op) = self.peek_punct().filter(|op| BINARY_OPS[level].contains(op)) {
+            let op = op.to_string();
+            self.pos += 1;
+            let rhs = self.binary(level + 1)?;
+            lhs = match op.as_str() {
+                "||" => i128::from(lhs != 0 || rhs != 0),
+                "&&" => i128::from(lhs != 0 && rhs != 0),
+                "==" => i128::from(lhs == rhs),
+                "!=" => i128::from(lhs != rhs),
+                "<" => i128::from(lhs < rhs),
+                ">" => i128::from(lhs > rhs),
+                "<=" => i128::from(lhs <= rhs),
+                ">=" => i128::from(lhs >= rhs),
+                "|" => lhs | rhs,
+                "^" => lhs ^ rhs,
+                "&" => lhs & rhs,
+                "<<" => lhs.checked_shl(u32::try_from(rhs).ok()?)?,
+                ">>" => lhs.checked_shr(u32::try_from(rhs).ok()?)?,
+                "+" => lhs.checked_add(rhs)?,
+                "-" => lhs.checked_sub(rhs)?,
+                "*" => lhs.checked_mul(rhs)?,
+                "/" => lhs.checked_div(rhs)?,
+                _ => lhs.checked_rem(rhs)?,
+            };
+        }
+        Some(lhs)
+    }
+
+    fn unary(&mut self) -> Option<i128> {
+        match self.peek_punct() {
+            Some("-") => {
+                self.pos += 1;
+                Some(-self.unary()?)
+            }
+            Some("+") => {
+                self.pos += 1;
+                self.unary()
+            }
+            Some("~") => {
+                self.pos += 1;
+                Some(!self.unary()?)
+            }
+            Some("!") => {
+                self.pos += 1;
+                Some(i128::from(self.unary()? == 0))
+            }
+            Some("(") => {
+                self.pos += 1;
+                // A cast like `(unsigned int)1` is dropped.
+                let cast_end =
+                    self.toks[self.pos..].iter().position(|tok| *tok == Tok::Punct(")".into()));
+                if let Some(len) = cast_end
+                    && len > 0
+                    && self.toks[self.pos..self.pos + len].iter().all(|tok| {
+                        matches!(tok, Tok::Ident(w) if TYPE_WORDS.contains(&w.as_str())
+                            || STD_TYPEDEFS.iter().any(|(c, _)| c == w))
+                    })
+                {
+                    self.pos += len + 1;
+                    return self.unary();
+                }
+                let value = self.ternary()?;
+                if self.peek_punct() != Some(")") {
+                    return None;
+                }
+                self.pos += 1;
+                Some(value)
+            }
+            _ => {
+                let value = match self.toks.get(self.pos)? {
+                    Tok::Num(num) => parse_int(num)?,
+                    Tok::Ident(name) => *self.values.get(name)?,
+                    _ => return None,
+                };
+                self.pos += 1;
+                Some(value)
+            }
+        }
+    }
+}
+
+/// An integer literal with an optional `u`/`l` suffix.
+fn parse_int(num: &str) -> Option<i128> {
+    let num = num.trim_end_matches(['u', 'U', 'l', 'L']);
+    let (digits, radix) =
+        if let Some(hex) = num.strip_prefix("0x").or_else(|| num.strip_prefix("0X")) {
+            (hex, 16)
+        } else if let Some(bin) = num.strip_prefix("0b").or_else(|| num.strip_prefix("0B")) {
+            (bin, 2)
+        } else if num.len() > 1
+            && let Some(oct) = num.strip_prefix('0')
+        {
+            (oct, 8)
+        } else {
+            (num, 10)
+        };
+    i128::from_str_radix(digits, radix).ok()
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,228 @@
+use rustc_span::{Symbol, create_default_session_globals_then};
+
+use super::*;
+
+fn module(header: &str) -> String {
+    header_module_source(Some("foo"), header, &Cfg::default())
+}
+
+/// Whether `lines` appear one after the other in `source`.
+fn has_lines(source: &str, lines: &[&str]) -> bool {
+    source.contains(&format!("{}\n", lines.join("\n")))
+}
+
+#[test]
+fn defines() {
+    let source = module(
+        "#define FOO_H\n\
+         #define SIZE 16\n\
+         #define MASK (0xff << 8) /* high byte */\n\
+         #define BIG 0x80000000u\n\
+         #define TWICE (SIZE * 2)\n\
+         #define NAME \"foo\"\n\
+         #define MAX(a, b) ((a) > (b) ? (a) : (b))\n",
+    );
+    assert!(source.contains("pub const SIZE: i32 = 16;\n"));
+    assert!(source.contains("pub const MASK: i32 = 65280;\n"));
+    assert!(source.contains("pub const BIG: u32 = 2147483648;\n"));
+    assert!(source.contains("pub const TWICE: i32 = 32;\n"));
+    assert!(!source.contains("NAME"));
+    assert!(!source.contains("MAX"));
+}
+
+#[test]
+fn functions() {
+    let source = module(
+        "#define API extern\n\
+         #ifdef __cplusplus\n\
+         extern \"C\" {\n\
+         #endif\n\
+         API double scale(double x, int by);\n\
+         API int printf(const char *restrict fmt, ...);\n\
+         API void fill(char buf[], size_t len) __attribute__((nonnull));\n\
+         API void *alloc(unsigned long);\n\
+         static inline int helper(void) { return 0; }\n\
+         int counter;\n\
+         #ifdef __cplusplus\n\
+         }\n\
+         #endif\n",
+    );
+    assert!(source.contains("#[link(name = \"foo\")]\nunsafe extern \"C\" {\n"));
+    assert!(source.contains("    pub fn scale(x: f64, by: ::std::ffi::c_int) -> f64;\n"));
+    assert!(source.contains(
+        "    pub fn printf(fmt: *const ::std::ffi::c_char, ...) -> ::std::ffi::c_int;\n"
+    ));
+    assert!(source.contains("    pub fn fill(buf: *mut ::std::ffi::c_char, len: usize);\n"));
+    assert!(
+        source
+            .contains("    pub fn alloc(arg0: ::std::ffi::c_ulong) -> *mut ::std::ffi::c_void;\n")
+    );
+    assert!(!source.contains("helper"));
+    assert!(!source.contains("counter"));
+}
+
+#[test]
+fn structs() {
+    let source = module(
+        "typedef struct point { double x, y; } point;\n\
+         typedef struct { int len; char name[32]; struct node *next; } entry;\n\
+         struct node { struct node *next; const entry *value; };\n\
+         typedef struct handle handle;\n\
+         struct flags { unsigned a : 1; };\n\
+         typedef int (*callback)(void *data, int code);\n\
+         handle *open_handle(const char *path, callback cb);\n\
+         double norm(point p);\n",
+    );
+    assert!(has_lines(
+        &source,
+        &[
+            "#[repr(C)]",
+            "#[derive(Debug, Clone, Copy)]",
+            "pub struct point {",
+            "    pub x: f64,",
+            "    pub y: f64,",
+            "}",
+        ]
+    ));
+    assert!(has_lines(
+        &source,
+        &[
+            "pub struct entry {",
+            "    pub len: ::std::ffi::c_int,",
+            "    pub name: [::std::ffi::c_char; 32],",
+            "    pub next: *mut node,",
+            "}",
+        ]
+    ));
+    assert!(has_lines(
+        &source,
+        &["pub struct node {", "    pub next: *mut node,", "    pub value: *const entry,", "}"]
+    ));
+    assert!(has_lines(&source, &["#[repr(C)]", "pub struct handle {", "    _private: [u8; 0],"]));
+    assert!(has_lines(&source, &["#[repr(C)]", "pub struct flags {", "    _private: [u8; 0],"]));
+    assert!(source.contains(
+        "pub type callback = Option<unsafe extern \"C\" fn(data: *mut ::std::ffi::c_void, \
+         code: ::std::ffi::c_int) -> ::std::ffi::c_int>;\n"
+    ));
+    assert!(source.contains(
+        "    pub fn open_handle(path: *const ::std::ffi::c_char, cb: callback) -> *mut handle;\n"
+    ));
+    assert!(source.contains("    pub fn norm(p: point) -> f64;\n"));
+}
+
+#[test]
+fn enums() {
+    let source = module(
+        "typedef enum { RED, GREEN = 4, BLUE } color;\n\
+         enum mode { MODE_READ = 1 << 0, MODE_WRITE = 1 << 1, MODE_DEFAULT = MODE_READ };\n\
+         enum { LIMIT = 10 };\n\
+         void paint(color c);\n",
+    );
+    assert!(has_lines(
+        &source,
+        &[
+            "#[repr(transparent)]",
+            "#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]",
+            "pub struct color(pub ::std::ffi::c_int);",
+            "pub const RED: color = color(0);",
+            "pub const GREEN: color = color(4);",
+            "pub const BLUE: color = color(5);",
+        ]
+    ));
+    assert!(source.contains("pub struct mode(pub ::std::ffi::c_int);\n"));
+    assert!(source.contains("pub const MODE_READ: mode = mode(1);\n"));
+    assert!(source.contains("pub const MODE_DEFAULT: mode = mode(1);\n"));
+    assert!(source.contains("pub const LIMIT: ::std::ffi::c_int = 10;\n"));
+    assert!(source.contains("    pub fn paint(c: color);\n"));
+}
+
+#[test]
+fn keywords_are_renamed() {
+    let source = module("int match(const char *type);\n");
+    assert!(has_lines(
+        &source,
+        &[
+            "    #[link_name = \"match\"]",
+            "    pub fn match_(type_: *const ::std::ffi::c_char) -> ::std::ffi::c_int;",
+        ]
+    ));
+}
+
+#[test]
+fn conditionals() {
+    let source = module(
+        "#ifndef FOO_H\n\
+         #define FOO_H\n\
+         #if defined(_MSC_VER) || defined(__GNUC__)\n\
+         #  define FOO_API __declspec(dllexport)\n\
+         #elif __STDC_VERSION__ >= 199901L && !defined(FOO_NO_API)\n\
+         #  define FOO_API extern\n\
+         #else\n\
+         #  define FOO_API unknown\n\
+         #endif\n\
+         #ifdef FOO_PREFIX\n\
+         #  define foo_open prefixed_foo_open\n\
+         #endif\n\
+         #define FOO_VERSION 3\n\
+         #if FOO_VERSION > 2\n\
+         FOO_API int foo_open(const char *path);\n\
+         #else\n\
+         FOO_API int foo_open_v1(const char *path);\n\
+         #endif\n\
+         #endif\n",
+    );
+    assert!(
+        source.contains(
+            "    pub fn foo_open(path: *const ::std::ffi::c_char) -> ::std::ffi::c_int;\n"
+        )
+    );
+    assert!(!source.contains("foo_open_v1"));
+    assert!(!source.contains("__STDC_VERSION__"));
+}
+
+#[test]
+fn function_like_macros() {
+    let source = module(
+        "#define OF(args) args\n\
+         #define DECLARE(name, type) type foo_##name(type value)\n\
+         #define FLAG(n) (1 << (n))\n\
+         #define BOTH (FLAG(0) | FLAG(3))\n\
+         extern int deflate OF((void *strm, int flush));\n\
+         DECLARE(twice, double);\n",
+    );
+    assert!(source.contains("pub const BOTH: i32 = 9;\n"));
+    assert!(source.contains(
+        "    pub fn deflate(strm: *mut ::std::ffi::c_void, flush: ::std::ffi::c_int) \
+         -> ::std::ffi::c_int;\n"
+    ));
+    assert!(source.contains("    pub fn foo_twice(value: f64) -> f64;\n"));
+}
+
+#[test]
+fn enum_repr_fits_values() {
+    let source = module("enum big { SMALL = 1, LARGE = 0x80000000u };\n");
+    assert!(source.contains("pub struct big(pub ::std::ffi::c_uint);\n"));
+    assert!(source.contains("pub const LARGE: big = big(2147483648);\n"));
+}
+
+#[test]
+fn platform_macros_follow_the_target() {
+    let header = "#ifdef _WIN32\nint on_windows(void);\n#else\nint elsewhere(void);\n#endif\n";
+    create_default_session_globals_then(|| {
+        let windows = Cfg::from_iter([(Symbol::intern("windows"), None)]);
+        let source = header_module_source(Some("foo"), header, &windows);
+        assert!(source.contains("on_windows"));
+        assert!(!source.contains("elsewhere"));
+    });
+    assert!(module(header).contains("elsewhere"));
+}
+
+#[test]
+fn libraries() {
+    assert_eq!(header_library("math.h", "math"), Some("m"));
+    assert_eq!(header_library("sys/../stdio.h", "stdio"), None);
+    assert_eq!(header_library("vendor/zlib.h", "zlib"), Some("zlib"));
+    let source = header_module_source(None, "int puts(const char *s);\n", &Cfg::default());
+    assert!(source.contains("unsafe extern \"C\" {\n    pub fn puts("));
+    assert!(!source.contains("#[link"));
+}
@@ -0,0 +1,368 @@
+//! C-ABI companions of `#[dynexport]` items.
+//!
+//! A `#[dynexport]` fn with the Rust ABI also gets a `dynexport_c_<name>` shim with the C ABI,
+//! and a `#[dynexport]` struct or enum gets a `#[repr(C)]` mirror plus an `FfiRepr` impl so it
+//! can appear in such signatures. The representation and the conversions live in
+//! compiler/extensions/src/ffi.rs, which is injected into the exporting crate as
+//! `__dynexport_ffi`. Signatures are checked syntactically: a fn using any type outside the
+//! representation keeps its Rust-ABI export only.
+
+use rustc_ast as ast;
+use rustc_ast_pretty::pprust;
+use rustc_session::parse::ParseSess;
+use rustc_span::{FileName, Span, Symbol, sym};
+use thin_vec::ThinVec;
+
+use super::dynload::SCRIPT_ALIASES;
+use crate::parser::{ForceCollect, Parser};
+use crate::source_str_to_stream;
+
+const FFI_SOURCE: &str = include_str!("../../../extensions/src/ffi.rs");
+
+/// Module the FFI support is injected as in crates with `#[dynexport]` items. Scripts already
+/// have it as `dynexport` (see extensions.rs).
+pub const FFI_MODULE: &str = "__dynexport_ffi";
+
+/// What the generated code may refer to.
+pub struct ShimCx<'a> {
+    /// Names of the crate's `#[dynexport]` structs and enums, which may appear in signatures.
+    pub adts: &'a [Symbol],
+    /// Path of the module holding the FFI support, e.g. `crate::__dynexport_ffi`.
+    pub ffi: String,
+}
+
+/// Types that are passed as themselves.
+const SCALARS: &[&str] = &[
+    "bool", "i8", "i16", "i32", "i64", "isize", "u8", "u16", "u32", "u64", "usize", "f32", "f64",
+];
+
+/// Source of `mod <name>` holding the FFI representation types and conversions.
+pub fn ffi_module_source(name: &str) -> String {
+    format!("#[doc(hidden)]\n#[allow(dead_code)]\npub mod {name} {{\n{FFI_SOURCE}\n}}")
+}
+
+/// Source of the items to add next to a `#[dynexport]` item, if it needs any.
+pub fn companion_source(
+    item: &ast::Item,
+    cx: &ShimCx<'_>,
+) -> Result<Option<String>, (Span, &'static str)> {
+    let cfgs: String = item
+        .attrs
+        .iter()
+        .filter(|attr| attr.has_name(sym::cfg))
+        .map(|attr| pprust::attribute_to_string(attr) + "\n")
+        .collect();
+    let items = match &item.kind {
+        ast::ItemKind::Fn(f) => fn_shim(f, cx).into_iter().collect(),
+        ast::ItemKind::Struct(ident, generics, data) => {
+            check_generics(generics)?;
+            struct_mirror(item, *ident, data, cx)?
+        }
+        ast::ItemKind::Enum(ident, generics, def) => {
+            check_generics(generics)?;
+            if def.variants.is_empty() {
+                return Err((item.span, "an enum without variants has no C representation"));
+            }
+            enum_mirror(item, *ident, def, cx)?
+        }
+        _ => Vec::new(),
+    };
+    if items.is_empty() {
+        return Ok(None);
+    }
+    // Every generated item carries the original's `#[cfg]`s so they disappear together.
+    Ok(Some(items.iter().map(|source| format!("{cfgs}{source}")).collect()))
+}
+
+/// Parse generated items, giving their tokens `span`.
+pub fn parse_generated_items(
+    psess: &ParseSess,
+    source: String,
+    span: Span,
+) -> ThinVec<Box<ast::Item>> {
+    let filename = FileName::Custom("dynexport_shims".into());
+    let stream = match source_str_to_stream(psess, filename, source, Some(span)) {
+        Ok(stream) => stream,
+        Err(errs) => {
+            for err in errs {
+                err.emit();
+            }
+            return ThinVec::new();
+        }
+    };
+    let mut parser = Parser::new(psess, stream, None);
+    let mut items = ThinVec::new();
+    loop {
+        match parser.parse_item(ForceCollect::No) {
+            Ok(Some(item)) => items.push(item),
+            Ok(None) => break,
+            Err(err) => {
+                err.emit();
+                break;
+            }
+        }
+    }
+    items
+}
+
+fn check_generics(generics: &ast::Generics) -> Result<(), (Span, &'static str)> {
+    match generics.params.first() {
+        Some(param) => Err((param.ident.span, "generic types can't be `#[dynexport]`ed")),
+        None => Ok(()),
+    }
+}
+
+/// The representation of `ty` as written in the shim, or `None` if it has none.
+pub fn repr_ty(ty: &ast::Ty, cx: &ShimCx<'_>) -> Option<String> {
+    let ffi = &cx.ffi;
+    match &ty.kind {
+        ast::TyKind::Paren(inner) => repr_ty(inner, cx),
+        ast::TyKind::Tup(tys) if tys.is_empty() => Some("()".to_string()),
+        ast::TyKind::Ref(_, mt) if !mt.mutbl.is_mut() && is_plain(&mt.ty, "str") => {
+            Some(format!("{ffi}::FfiStr"))
+        }
+        ast::TyKind::Path(None, path) if path.segments.len() == 1 => {
+            let segment = &path.segments[0];
+            let name = segment.ident.as_str();
+            let Some(args) = &segment.args else {
+                let alias = SCRIPT_ALIASES.iter().find(|(alias, _)| *alias == name);
+                if let Some(&(_, target)) = alias {
+                    return match target {
+                        "&str" => Some(format!("{ffi}::FfiStr")),
+                        "char" => Some("u32".to_string()),
+                        target if SCALARS.contains(&target) => Some(target.to_string()),
+                        _ => None,
+                    };
+                }
+                return match name {
+                    "char" => Some("u32".to_string()),
+                    "String" => Some(format!("{ffi}::FfiString")),
+                    _ if SCALARS.contains(&name) => Some(name.to_string()),
+                    _ if cx.adts.contains(&segment.ident.name) => {
+                        Some(format!("<{name} as {ffi}::FfiRepr>::Repr"))
+                    }
+                    _ => None,
+                };
+            };
+            let ast::GenericArgs::AngleBracketed(args) = &**args else { return None };
+            let [ast::AngleBracketedArg::Arg(ast::GenericArg::Type(inner))] = &args.args[..] else {
+                return None;
+            };
+            let wrapper = match name {
+                "Vec" => "FfiVec",
+                "Option" => "FfiOption",
+                _ => return None,
+            };
+            Some(format!("{ffi}::{wrapper}<{}>", repr_ty(inner, cx)?))
+        }
+        _ => None,
+    }
+}
+
+fn is_plain(ty: &ast::Ty, name: &str) -> bool {
+    matches!(&ty.kind, ast::TyKind::Path(None, path)
+        if path.segments.len() == 1
+            && path.segments[0].args.is_none()
+            && path.segments[0].ident.as_str() == name)
+}
+
+/// `dynexport_c_<name>` for a non-generic Rust-ABI fn whose signature has a representation.
+fn fn_shim(f: &ast::Fn, cx: &ShimCx<'_>) -> Option<String> {
+    let rust_abi = match &f.sig.header.ext {
+        ast::Extern::None => true,
+        ast::Extern::Explicit(abi, _) => abi.symbol_unescaped.as_str() == "Rust",
+        ast::Extern::Implicit(_) => false,
+    };
+    let generic = f
+        .generics
+        .params
+        .iter()
+        .any(|param| !matches!(param.kind, ast::GenericParamKind::Lifetime));
+    if !rust_abi || generic || f.sig.header.coroutine_kind.is_some() || f.sig.decl.c_variadic() {
+        return None;
+    }
+
+    let ffi = &cx.ffi;
+    let ffi_str = format!("{ffi}::FfiStr");
+    let ret = match &f.sig.decl.output {
+        ast::FnRetTy::Default(_) => String::new(),
+        ast::FnRetTy::Ty(ty) => format!(" -> {}", repr_ty(ty, cx)?),
+    };
+    let mut params = Vec::new();
+    let mut args = Vec::new();
+    for (i, param) in f.sig.decl.inputs.iter().enumerate() {
+        if param.is_self() {
+            return None;
+        }
+        let repr = repr_ty(&param.ty, cx)?;
+        // A `&str` argument is decoded lossily into a temporary, unless a `&str` in the
+        // result may borrow from it.
+        if repr == ffi_str && !ret.contains(&ffi_str) {
+            args.push(format!("&*{ffi_str}::to_str_lossy(__arg{i})"));
+        } else {
+            args.push(format!("{ffi}::FfiRepr::from_repr(__arg{i})"));
+        }
+        params.push(format!("__arg{i}: {repr}"));
+    }
+    let name = f.ident.as_str();
+    Some(format!(
+        "\n#[unsafe(no_mangle)]\n\
+         #[doc(hidden)]\n\
+         #[allow(improper_ctypes_definitions, unused_unsafe)]\n\
+         pub unsafe extern \"C\" fn dynexport_c_{name}({params}){ret} {{\n\
+         \x20   unsafe {{ {ffi}::FfiRepr::into_repr({name}({args})) }}\n\
+         }}\n",
+        params = params.join(", "),
+        args = args.join(", "),
+    ))
+}
+
+/// The fields of a struct or variant as `(name, representation)`. Tuple fields are named by
+/// index, which brace syntax accepts for tuple structs and variants alike.
+fn field_reprs(
+    data: &ast::VariantData,
+    cx: &ShimCx<'_>,
+) -> Result<Vec<(String, String)>, (Span, &'static str)> {
+    data.fields()
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let name = field.ident.map_or_else(|| i.to_string(), |ident| ident.to_string());
+            match repr_ty(&field.ty, cx) {
+                Some(repr) => Ok((name, repr)),
+                None => Err((field.ty.span, "this type has no `#[dynexport]` C representation")),
+            }
+        })
+        .collect()
+}
+
+/// The body of a struct or variant definition with the given field types.
+fn fields_def(data: &ast::VariantData, fields: &[(String, String)], vis: &str) -> String {
+    match data {
+        ast::VariantData::Struct { .. } => {
+            let fields: Vec<String> =
+                fields.iter().map(|(name, repr)| format!("{vis}{name}: {repr}")).collect();
+            format!(" {{ {} }}", fields.join(", "))
+        }
+        ast::VariantData::Tuple(..) => {
+            let fields: Vec<String> =
+                fields.iter().map(|(_, repr)| format!("{vis}{repr}")).collect();
+            format!("({})", fields.join(", "))
+        }
+        ast::VariantData::Unit(_) => String::new(),
+    }
+}
+
+/// `{ a: conv(a_expr), ... }` for brace-syntax construction.
+fn convert_fields(
+    fields: &[(String, String)],
+    conv: &str,
+    value: impl Fn(&str) -> String,
+) -> String {
+    let fields: Vec<String> =
+        fields.iter().map(|(name, _)| format!("{name}: {conv}({})", value(name))).collect();
+    format!("{{ {} }}", fields.join(", "))
+}
+
+fn mirror_impl(
+    cx: &ShimCx<'_>,
+    ident: ast::Ident,
+    mirror: &str,
+    into: String,
+    from: String,
+) -> String {
+    let ffi = &cx.ffi;
+    format!(
+        "\n#[doc(hidden)]\n\
+         #[allow(unused_unsafe)]\n\
+         impl {ffi}::FfiRepr for {ident} {{\n\
+         \x20   type Repr = {mirror};\n\
+         \x20   fn into_repr(self) -> {mirror} {{\n{into}\n    }}\n\
+         \x20   unsafe fn from_repr(repr: {mirror}) -> Self {{\n{from}\n    }}\n\
+         }}\n"
+    )
+}
+
+fn struct_mirror(
+    item: &ast::Item,
+    ident: ast::Ident,
+    data: &ast::VariantData,
+    cx: &ShimCx<'_>,
+) -> Result<Vec<String>, (Span, &'static str)> {
+    let ffi = &cx.ffi;
+    let vis = pprust::vis_to_string(&item.vis);
+    let mirror = format!("__dynexport_{ident}");
+    let fields = field_reprs(data, cx)?;
+    let semi = if matches!(data, ast::VariantData::Struct { .. }) { "" } else { ";" };
+    let into = format!(
+        "        {mirror} {}",
+        convert_fields(&fields, &format!("{ffi}::FfiRepr::into_repr"), |name| format!(
+            "self.{name}"
+        ))
+    );
+    let from = format!(
+        "        unsafe {{ {ident} {} }}",
+        convert_fields(&fields, &format!("{ffi}::FfiRepr::from_repr"), |name| format!(
+            "repr.{name}"
+        ))
+    );
+    let def = format!(
+        "\n#[repr(C)]\n\
+         #[doc(hidden)]\n\
+         #[allow(non_camel_case_types)]\n\
+         {vis}struct {mirror}{}{semi}\n",
+        fields_def(data, &fields, &vis),
+    );
+    Ok(vec![def, mirror_impl(cx, ident, &mirror, into, from)])
+}
+
+fn enum_mirror(
+    item: &ast::Item,
+    ident: ast::Ident,
+    def: &ast::EnumDef,
+    cx: &ShimCx<'_>,
+) -> Result<Vec<String>, (Span, &'static str)> {
+    let ffi = &cx.ffi;
+    let vis = pprust::vis_to_string(&item.vis);
+    let mirror = format!("__dynexport_{ident}");
+    let mut variants = String::new();
+    let mut into_arms = String::new();
+    let mut from_arms = String::new();
+    for variant in &def.variants {
+        let fields = field_reprs(&variant.data, cx)?;
+        let discr = match &variant.disr_expr {
+            Some(anon) => format!(" = {}", pprust::expr_to_string(&anon.value)),
+            None => String::new(),
+        };
+        variants.push_str(&format!(
+            "    {}{}{discr},\n",
+            variant.ident,
+            fields_def(&variant.data, &fields, "")
+        ));
+        let bindings: Vec<String> =
+            fields.iter().enumerate().map(|(i, (name, _))| format!("{name}: __f{i}")).collect();
+        let pattern = format!("{{ {} }}", bindings.join(", "));
+        let binding = |name: &str| {
+            let i = fields.iter().position(|(field, _)| field == name).unwrap_or_default();
+            format!("__f{i}")
+        };
+        let variant = variant.ident;
+        into_arms.push_str(&format!(
+            "            {ident}::{variant} {pattern} => {mirror}::{variant} {},\n",
+            convert_fields(&fields, &format!("{ffi}::FfiRepr::into_repr"), binding)
+        ));
+        from_arms.push_str(&format!(
+            "            {mirror}::{variant} {pattern} => unsafe {{ {ident}::{variant} {} }},\n",
+            convert_fields(&fields, &format!("{ffi}::FfiRepr::from_repr"), binding)
+        ));
+    }
+    let into = format!("        match self {{\n{into_arms}        }}");
+    let from = format!("        match repr {{\n{from_arms}        }}");
+    let def = format!(
+        "\n#[repr(C, u32)]\n\
+         #[doc(hidden)]\n\
+         #[allow(non_camel_case_types)]\n\
+         {vis}enum {mirror} {{\n{variants}}}\n"
+    );
+    Ok(vec![def, mirror_impl(cx, ident, &mirror, into, from)])
+}
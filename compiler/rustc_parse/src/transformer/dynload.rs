@@ -0,0 +1,246 @@
+//! `import lib "libfoo.so" { fn add(a: i32, b: i32) -> i32 }` in script mode.
+//!
+//! The import becomes a module of wrappers around the runtime loader in
+//! compiler/extensions/src/dynload.rs. Each wrapper carries the hashes the library's
+//! `dynexport_meta_<name>` record must contain. They are computed here from the declared
+//! signature with the encoding `#[dynexport]` uses (see `rustc_abi::dynexport`), so only
+//! types a declaration can spell out without seeing the library are accepted: primitives,
+//! references, pointers, slices, arrays, tuples and the standard library types in `STD_TYPES`,
+//! named by their `std` path unless the prelude brings them in.
+//! A declaration may require a minimum item version with `#[dynexport(version = N)]`, checked
+//! against the version in the record's flags.
+
+use rustc_abi::ExternAbi;
+use rustc_abi::dynexport::{dynexport_compiler_hash, dynexport_hash, encode_fn_sig};
+use rustc_ast::attr::AttributeExt;
+use rustc_ast::{self as ast, token};
+use rustc_ast_pretty::pprust;
+use rustc_span::{Ident, Span, kw, sym};
+
+/// Standard library types accepted in imported signatures, with the `std` module they are
+/// named through and their number of type parameters that have no default.
+const STD_TYPES: &[(&str, &str, usize)] = &[
+    ("String", "string", 0),
+    ("Vec", "vec", 1),
+    ("VecDeque", "collections", 1),
+    ("Option", "option", 1),
+    ("Result", "result", 2),
+    ("Box", "boxed", 1),
+    ("Rc", "rc", 1),
+    ("Arc", "sync", 1),
+    ("HashMap", "collections", 2),
+    ("HashSet", "collections", 1),
+    ("BTreeMap", "collections", 2),
+    ("BTreeSet", "collections", 1),
+];
+
+/// The `STD_TYPES` a signature may name without their path: the std prelude's, and the
+/// `HashMap` the script prelude imports.
+const PRELUDE_TYPES: &[&str] = &["String", "Vec", "Option", "Result", "Box", "HashMap"];
+
+/// Script prelude aliases (see `build_type_aliases` in script_harness.rs).
+pub(crate) const SCRIPT_ALIASES: &[(&str, &str)] = &[
+    ("int", "i64"),
+    ("float", "f64"),
+    ("boolean", "bool"),
+    ("rune", "char"),
+    ("byte", "u8"),
+    ("unicode", "u32"),
+    ("codepoint", "u32"),
+    ("string", "&str"),
+];
+
+const PRIMITIVES: &[&str] = &[
+    "bool", "char", "str", "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64",
+    "u128", "usize", "f16", "f32", "f64", "f128",
+];
+
+/// The type hash `#[dynexport]` computes for a Rust-ABI function with this signature, or the
+/// span of the first type that can't be checked.
+pub(crate) fn fn_type_hash(decl: &ast::FnDecl) -> Result<u64, Span> {
+    let unsupported =
+        |param: &&ast::Param| param.is_self() || matches!(param.ty.kind, ast::TyKind::CVarArgs);
+    if let Some(param) = decl.inputs.iter().find(unsupported) {
+        return Err(param.span);
+    }
+    let inputs: Vec<String> =
+        decl.inputs.iter().map(|param| encode_ty(&param.ty)).collect::<Result<_, _>>()?;
+    let output = match &decl.output {
+        ast::FnRetTy::Default(_) => "()".to_string(),
+        ast::FnRetTy::Ty(ty) => encode_ty(ty)?,
+    };
+    let inputs = inputs.iter().map(String::as_str);
+    let encoding = encode_fn_sig(ExternAbi::Rust, inputs, false, &output);
+    Ok(dynexport_hash(&encoding))
+}
+
+/// The `N` of `#[dynexport(version = N)]` on an imported function, or the span of whatever
+/// else was written there.
+pub(crate) fn required_version(attr: &ast::Attribute) -> Result<u16, Span> {
+    if !attr.has_name(sym::dynexport) {
+        return Err(attr.span);
+    }
+    let list = attr.meta_item_list().ok_or(attr.span)?;
+    let [item] = list.as_slice() else { return Err(attr.span) };
+    let meta = item.meta_item().filter(|meta| meta.has_name(sym::version)).ok_or(item.span())?;
+    let lit = meta.name_value_literal().ok_or(meta.span)?;
+    let ast::LitKind::Int(version, _) = lit.kind else { return Err(lit.span) };
+    u16::try_from(version.get()).map_err(|_| lit.span)
+}
+
+fn encode_ty(ty: &ast::Ty) -> Result<String, Span> {
+    let encode_list = |tys: &mut dyn Iterator<Item = &Box<ast::Ty>>| -> Result<String, Span> {
+        let mut out = String::new();
+        for ty in tys {
+            out.push_str(&encode_ty(ty)?);
+            out.push(',');
+        }
+        Ok(out)
+    };
+    Ok(match &ty.kind {
+        ast::TyKind::Paren(inner) => encode_ty(inner)?,
+        ast::TyKind::Never => "!".to_string(),
+        ast::TyKind::Tup(tys) => format!("({})", encode_list(&mut tys.iter())?),
+        ast::TyKind::Slice(elem) => format!("[{}]", encode_ty(elem)?),
+        ast::TyKind::Array(elem, len) => {
+            let ast::ExprKind::Lit(token::Lit { kind: token::Integer, symbol, .. }) = len.value.kind
+            else {
+                return Err(len.value.span);
+            };
+            let len: u64 = symbol.as_str().replace('_', "").parse().map_err(|_| len.value.span)?;
+            format!("[{};{len}]", encode_ty(elem)?)
+        }
+        ast::TyKind::Ref(_, mt) => {
+            let prefix = if mt.mutbl.is_mut() { "&mut " } else { "&" };
+            format!("{prefix}{}", encode_ty(&mt.ty)?)
+        }
+        ast::TyKind::Ptr(mt) => {
+            let prefix = if mt.mutbl.is_mut() { "*mut " } else { "*const " };
+            format!("{prefix}{}", encode_ty(&mt.ty)?)
+        }
+        ast::TyKind::Path(None, path) => {
+            let Some(last) = path.segments.last() else { return Err(ty.span) };
+            let name = last.ident.as_str();
+            let alias = SCRIPT_ALIASES.iter().find(|(alias, _)| *alias == name);
+            match &last.args {
+                None if path.segments.len() == 1 && PRIMITIVES.contains(&name) => name.to_string(),
+                None if path.segments.len() == 1 && alias.is_some() => alias.unwrap().1.to_string(),
+                args => {
+                    let Some(arity) = std_type_arity(path) else { return Err(ty.span) };
+                    let tys: Vec<&Box<ast::Ty>> = match args.as_deref() {
+                        None => Vec::new(),
+                        Some(ast::GenericArgs::AngleBracketed(args)) => args
+                            .args
+                            .iter()
+                            .filter_map(|arg| match arg {
+                                ast::AngleBracketedArg::Arg(ast::GenericArg::Type(ty)) => Some(ty),
+                                _ => None,
+                            })
+                            .collect(),
+                        Some(_) => return Err(ty.span),
+                    };
+                    if tys.len() != arity {
+                        return Err(ty.span);
+                    }
+                    if arity == 0 {
+                        name.to_string()
+                    } else {
+                        format!("{name}<{}>", encode_list(&mut tys.into_iter())?)
+                    }
+                }
+            }
+        }
+        _ => return Err(ty.span),
+    })
+}
+
+/// The number of type parameters of the `STD_TYPES` entry `path` names, either by its full
+/// `std::module::Name` path or, for `PRELUDE_TYPES`, by its bare name. Anything else may be
+/// a script's own type of the same name.
+fn std_type_arity(path: &ast::Path) -> Option<usize> {
+    let (last, modules) = path.segments.split_last()?;
+    if modules.iter().any(|segment| segment.args.is_some()) {
+        return None;
+    }
+    let modules: Vec<&str> = modules
+        .iter()
+        .filter(|segment| segment.ident.name != kw::PathRoot)
+        .map(|segment| segment.ident.as_str())
+        .collect();
+    let name = last.ident.as_str();
+    let &(_, module, arity) = STD_TYPES.iter().find(|(std_name, ..)| *std_name == name)?;
+    let named = match modules.as_slice() {
+        [] => path.segments.len() == 1 && PRELUDE_TYPES.contains(&name),
+        [krate, std_module] => *krate == "std" && *std_module == module,
+        _ => false,
+    };
+    named.then_some(arity)
+}
+
+/// Source of the module an `import lib` expands to, from each function's name, declaration and
+/// required version. Hashes must already have been checked to be computable with `fn_type_hash`.
+pub(crate) fn import_lib_source(library: &str, fns: &[(Ident, Box<ast::FnDecl>, u16)]) -> String {
+    let compiler_hash = dynexport_compiler_hash(env!("CFG_VERSION"));
+    let mut load = String::new();
+    let mut wrappers = String::new();
+    for (ident, decl, version) in fns {
+        let type_hash = fn_type_hash(decl).unwrap_or_default();
+        let names: Vec<String> = decl
+            .inputs
+            .iter()
+            .enumerate()
+            .map(|(i, param)| match param.pat.kind {
+                ast::PatKind::Ident(_, name, None) => name.to_string(),
+                _ => format!("__arg{i}"),
+            })
+            .collect();
+        let tys: Vec<String> =
+            decl.inputs.iter().map(|param| pprust::ty_to_string(&param.ty)).collect();
+        let params: Vec<String> =
+            names.iter().zip(&tys).map(|(name, ty)| format!("{name}: {ty}")).collect();
+        let (ret, ret_ty) = match &decl.output {
+            ast::FnRetTy::Default(_) => (String::new(), "()".to_string()),
+            ast::FnRetTy::Ty(ty) => {
+                let ty = pprust::ty_to_string(ty);
+                (format!(" -> {ty}"), ty)
+            }
+        };
+        let name = ident.as_str();
+        let resolve =
+            format!("{name:?}, {type_hash:#018x}u64, {compiler_hash:#010x}u32, {version}u16");
+        load.push_str(&format!("    __LIB.__resolve({resolve})?;\n"));
+        wrappers.push_str(&format!(
+            "pub fn try_{ident}({params}) -> ::std::result::Result<{ret_ty}, DynloadError> {{\n\
+             \x20   static SYM: __DynSym = __DynSym::new();\n\
+             \x20   let __f: fn({tys}){ret} =\n\
+             \x20       unsafe {{ ::std::mem::transmute(SYM.get(&__LIB, {resolve})?) }};\n\
+             \x20   Ok(__f({names}))\n\
+             }}\n\
+             pub fn {ident}({params}){ret} {{\n\
+             \x20   match try_{ident}({names}) {{\n\
+             \x20       Ok(value) => value,\n\
+             \x20       Err(err) => ::std::panic!(\"{{}}\", err),\n\
+             \x20   }}\n\
+             }}\n",
+            params = params.join(", "),
+            tys = tys.join(", "),
+            names = names.join(", "),
+        ));
+    }
+    format!(
+        "#[allow(unused_imports)]\n\
+         use crate::*;\n\
+         use crate::__script_helpers::{{__DynLib, __DynSym, DynexportInterface, DynloadError}};\n\
+         static __LIB: __DynLib = __DynLib::new({library:?});\n\
+         /// Opens the library and checks every imported function against its metadata.\n\
+         pub fn load() -> ::std::result::Result<(), DynloadError> {{\n\
+         {load}\
+         \x20   Ok(())\n\
+         }}\n\
+         /// Descriptions of every `#[dynexport]` item the library contains.\n\
+         pub fn interface() -> ::std::result::Result<DynexportInterface, DynloadError> {{\n\
+         \x20   __LIB.interface()\n\
+         }}\n\
+         {wrappers}"
+    )
+}
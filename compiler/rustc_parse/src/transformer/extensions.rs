@@ -22,6 +22,15 @@
 const VAL_SOURCE: &str = include_str!("../../../extensions/src/val.rs");
 const NUMBERS_SOURCE: &str = include_str!("../../../extensions/src/numbers.rs");
 const MACROS_SOURCE: &str = include_str!("../../../extensions/src/macros.rs");
+const CATCH_SOURCE: &str = include_str!("../../../extensions/src/catch.rs");
+const MAPS_SOURCE: &str = include_str!("../../../extensions/src/maps.rs");
+const DYNLOAD_SOURCE: &str = include_str!("../../../extensions/src/dynload.rs");
+const ELF_SOURCE: &str = include_str!("../../../extensions/src/elf.rs");
+const INTERFACE_SOURCE: &str = include_str!("../../../extensions/src/interface.rs");
+const IMPORT_FN_SOURCE: &str = include_str!("../../../extensions/src/import_fn.rs");
+const FFI_SOURCE: &str = include_str!("../../../extensions/src/ffi.rs");
+const UNITS_SOURCE: &str = include_str!("../../../extensions/src/units.rs");
+const ERRORS_SOURCE: &str = include_str!("../../../extensions/src/errors.rs");
 
 /// Parse and return the extensions items with proper span context.
 ///
@@ -40,8 +49,33 @@ pub fn parse_extensions(
         VAL_SOURCE,
         NUMBERS_SOURCE,
         MACROS_SOURCE,
+        CATCH_SOURCE,
+        MAPS_SOURCE,
+        DYNLOAD_SOURCE,
+        ELF_SOURCE,
+        INTERFACE_SOURCE,
+        IMPORT_FN_SOURCE,
     ].join("\n");
 
+    // Units are namespaced (`units::KiloMeter`) so their constants can't collide with user
+    // items at the crate root. The constants come from the suffix table in
+    // transformer/units.rs.
+    let units_module = format!(
+        "#[allow(dead_code, non_upper_case_globals)]\npub mod units {{\n{UNITS_SOURCE}\n{}}}",
+        super::units::unit_constants(),
+    );
+    // The C representation of `#[dynexport]` signatures, for decoding what shims return.
+    let dynexport_module = format!("#[allow(dead_code)]\npub mod dynexport {{\n{FFI_SOURCE}\n}}");
+    // Go's `errors.New("...")`, reached through the module like the Go package.
+    let errors_module = format!("#[allow(dead_code)]\npub mod errors {{\n{ERRORS_SOURCE}\n}}");
+    let combined_source = [
+        combined_source.as_str(),
+        units_module.as_str(),
+        dynexport_module.as_str(),
+        errors_module.as_str(),
+    ]
+    .join("\n");
+
     // Extract external crate dependencies from extensions
     let external_crates = extract_external_crates(&combined_source);
 
@@ -0,0 +1,355 @@
+//! `import fn` declarations that take strings, variadic arguments or closures.
+//!
+//! `import fn foo42() -> i32;` stays a plain foreign function that the script calls in
+//! `unsafe`. A declaration with a `string` parameter or return type, a C-variadic `...` or a
+//! callback parameter instead becomes a module `__import_<name>` holding the foreign function
+//! and a `call!` macro, and the parser turns every later `name(..)` call into `call!(..)`, unless
+//! a local `name` is in scope. (The parser tracks those locals as it goes, so arguments of another
+//! macro, parsed when it expands, don't see them.) The macro evaluates the arguments, converts
+//! them on the way in and the result on the way out:
+//!
+//!   int                  C `int`, returned as i64
+//!   rune                 uint32_t holding the scalar value
+//!   string, &str         `const char *` to a NUL-terminated copy that lives for the call;
+//!                        a returned `char *` is copied into a String, NULL giving ""
+//!   ...                  script numbers, strings and pointers as C expects them in a
+//!                        variadic call (`__CVarArg` in compiler/extensions/src/import_fn.rs)
+//!   fn(*mut c_void, ..)  a closure, called through a generated `extern "C"` trampoline
+//!
+//! Other script aliases become the type they stand for, and `c_void`, `c_char` and the other
+//! `std::ffi` C types can be named without importing them.
+//!
+//! A callback parameter is a function pointer whose first parameter is `*mut c_void`: C passes
+//! its userdata pointer back there. The declaration names that userdata parameter too, as the
+//! first `*mut c_void` parameter after the callback, or else the last one before it. Calls leave
+//! it out and the macro passes a pointer to the closure instead. The closure is only borrowed for
+//! the call, so C must not keep the callback once the function returns.
+//!
+//! The macro calls the foreign function outside any `unsafe` block of its own, so these calls too
+//! have to be written in `unsafe`: nothing in a declaration shows that the C function is sound to
+//! call with whatever the script passes.
+
+use rustc_ast as ast;
+use rustc_ast_pretty::pprust;
+use rustc_span::{Ident, Span};
+
+use super::dynload::SCRIPT_ALIASES;
+
+/// C types from `std::ffi` that a declaration may name unqualified.
+const C_TYPES: &[&str] = &[
+    "c_void",
+    "c_char",
+    "c_schar",
+    "c_uchar",
+    "c_short",
+    "c_ushort",
+    "c_int",
+    "c_uint",
+    "c_long",
+    "c_ulong",
+    "c_longlong",
+    "c_ulonglong",
+    "c_float",
+    "c_double",
+];
+
+/// How a value of a declared type crosses into C and back.
+enum Conv {
+    /// Script `int`: i64 on the script side, C `int` on the other.
+    Int,
+    /// Script `rune`: a char passed as its scalar value.
+    Rune,
+    /// Script strings: `const char *` on the C side.
+    Str,
+    /// The same Rust type on both sides.
+    Plain(String),
+}
+
+impl Conv {
+    fn of(ty: &ast::Ty) -> Conv {
+        match &ty.kind {
+            ast::TyKind::Paren(inner) => Conv::of(inner),
+            ast::TyKind::Ref(_, mt) if !mt.mutbl.is_mut() && is_named(&mt.ty, "str") => Conv::Str,
+            ast::TyKind::Path(None, path)
+                if let [segment] = &path.segments[..]
+                    && segment.args.is_none() =>
+            {
+                match segment.ident.as_str() {
+                    "int" => Conv::Int,
+                    "rune" => Conv::Rune,
+                    "string" | "String" => Conv::Str,
+                    name => match SCRIPT_ALIASES.iter().find(|(alias, _)| *alias == name) {
+                        Some((_, ty)) => Conv::Plain(ty.to_string()),
+                        None => Conv::Plain(c_ty(ty)),
+                    },
+                }
+            }
+            _ => Conv::Plain(c_ty(ty)),
+        }
+    }
+
+    fn c_ty(&self) -> String {
+        match self {
+            Conv::Int => "::std::ffi::c_int".to_string(),
+            Conv::Rune => "u32".to_string(),
+            Conv::Str => "*const ::std::ffi::c_char".to_string(),
+            Conv::Plain(ty) => ty.clone(),
+        }
+    }
+
+    /// The type a closure sees for a callback parameter of this type.
+    fn script_param_ty(&self) -> String {
+        match self {
+            Conv::Int => "i64".to_string(),
+            Conv::Rune => "char".to_string(),
+            Conv::Str => "&str".to_string(),
+            Conv::Plain(ty) => ty.clone(),
+        }
+    }
+
+    /// The type a closure returns for a callback returning this type, if it can be converted.
+    fn script_ret_ty(&self) -> Option<String> {
+        match self {
+            Conv::Str => None,
+            conv => Some(conv.script_param_ty()),
+        }
+    }
+
+    /// `expr`, a script value, converted for C. String temporaries live until the end of the
+    /// enclosing statement.
+    fn c_expr(&self, expr: &str) -> String {
+        match self {
+            Conv::Int => format!("({expr}) as ::std::ffi::c_int"),
+            Conv::Rune => format!("({expr}) as u32"),
+            Conv::Str => format!("crate::__script_helpers::__CString::__c_string(&{expr}).as_ptr()"),
+            Conv::Plain(_) => expr.to_string(),
+        }
+    }
+
+    /// `expr`, a C value, converted for the script. Strings are copied into a String.
+    fn script_expr(&self, expr: &str) -> String {
+        match self {
+            Conv::Int => format!("{expr} as i64"),
+            Conv::Rune => format!(
+                "::std::char::from_u32({expr}).unwrap_or(::std::char::REPLACEMENT_CHARACTER)"
+            ),
+            Conv::Str => format!("unsafe {{ crate::__script_helpers::__c_string_owned({expr}) }}"),
+            Conv::Plain(_) => expr.to_string(),
+        }
+    }
+}
+
+/// Whether `decl` needs the module and macro rather than a plain foreign function.
+pub(crate) fn needs_wrapper(decl: &ast::FnDecl) -> bool {
+    let is_str = |ty: &ast::Ty| matches!(Conv::of(ty), Conv::Str);
+    decl.c_variadic()
+        || decl.inputs.iter().any(|param| is_str(&param.ty) || callback(&param.ty).is_some())
+        || matches!(&decl.output, ast::FnRetTy::Ty(ty) if is_str(ty))
+}
+
+/// Source of the `__import_<name>` module for a declaration `needs_wrapper` accepted, or the
+/// span and message of a callback parameter without a userdata parameter to go with it.
+pub(crate) fn import_fn_source(ident: Ident, decl: &ast::FnDecl) -> Result<String, (Span, String)> {
+    let params: Vec<&ast::Param> = decl
+        .inputs
+        .iter()
+        .filter(|param| !matches!(param.ty.kind, ast::TyKind::CVarArgs))
+        .collect();
+    let names: Vec<String> = params
+        .iter()
+        .enumerate()
+        .map(|(i, param)| match param.pat.kind {
+            ast::PatKind::Ident(_, name, None) => name.to_string(),
+            _ => format!("arg{i}"),
+        })
+        .collect();
+
+    // The userdata parameter of each callback parameter.
+    let mut userdata: Vec<Option<usize>> = vec![None; params.len()];
+    for (i, param) in params.iter().enumerate() {
+        if callback(&param.ty).is_none() {
+            continue;
+        }
+        let free = |j: &usize| is_void_ptr(&params[*j].ty) && !userdata.contains(&Some(*j));
+        let Some(j) = (i + 1..params.len()).find(free).or_else(|| (0..i).rev().find(free)) else {
+            let msg = format!(
+                "callback parameter `{}` needs a `*mut c_void` parameter to pass its userdata",
+                names[i]
+            );
+            return Err((param.span, msg));
+        };
+        userdata[i] = Some(j);
+    }
+
+    let name = ident.as_str();
+    let module = format!("__import_{name}");
+    let mut items = String::new();
+    let mut c_params = Vec::new();
+    let mut matchers = Vec::new();
+    let mut binds = String::new();
+    let mut args = Vec::new();
+    for (i, param) in params.iter().enumerate() {
+        let param_name = &names[i];
+        if let Some(cb) = callback(&param.ty) {
+            let cb_params: Vec<Conv> =
+                cb.inputs[1..].iter().map(|param| Conv::of(&param.ty)).collect();
+            let cb_ret = match &cb.output {
+                ast::FnRetTy::Default(_) => None,
+                ast::FnRetTy::Ty(ty) => Some(Conv::of(ty)),
+            };
+            let ret_script = match &cb_ret {
+                None => String::new(),
+                Some(conv) => match conv.script_ret_ty() {
+                    Some(ty) => format!(" -> {ty}"),
+                    None => {
+                        let msg =
+                            format!("callback parameter `{param_name}` cannot return a string");
+                        return Err((param.span, msg));
+                    }
+                },
+            };
+            let ret_c =
+                cb_ret.as_ref().map(|conv| format!(" -> {}", conv.c_ty())).unwrap_or_default();
+            let script_tys: Vec<String> = cb_params.iter().map(Conv::script_param_ty).collect();
+            let bound = format!("F: FnMut({}){ret_script}", script_tys.join(", "));
+            let c_tys: Vec<String> = std::iter::once("*mut ::std::ffi::c_void".to_string())
+                .chain(cb_params.iter().map(Conv::c_ty))
+                .collect();
+            let fn_ty = format!("unsafe extern \"C\" fn({}){ret_c}", c_tys.join(", "));
+            let trampoline_params: Vec<String> =
+                c_tys[1..].iter().enumerate().map(|(k, ty)| format!("a{k}: {ty}")).collect();
+            let converted: Vec<String> = cb_params
+                .iter()
+                .enumerate()
+                .map(|(k, conv)| match conv {
+                    Conv::Str => format!("&{}", conv.script_expr(&format!("a{k}"))),
+                    conv => conv.script_expr(&format!("a{k}")),
+                })
+                .collect();
+            let call = format!("f({})", converted.join(", "));
+            let body = match &cb_ret {
+                None => call,
+                Some(conv) => conv.c_expr(&call),
+            };
+            items.push_str(&format!(
+                "pub unsafe extern \"C\" fn __trampoline_{param_name}<{bound}>(\n\
+                 \x20   data: *mut ::std::ffi::c_void,\n\
+                 \x20   {}\n\
+                 ){ret_c} {{\n\
+                 \x20   let f = unsafe {{ &mut *(data as *mut F) }};\n\
+                 \x20   {body}\n\
+                 }}\n\
+                 pub fn __bind_{param_name}<{bound}>(f: F) -> F {{\n\
+                 \x20   f\n\
+                 }}\n\
+                 pub fn __trampoline_of_{param_name}<{bound}>(_: &mut F) -> {fn_ty} {{\n\
+                 \x20   __trampoline_{param_name}::<F>\n\
+                 }}\n",
+                trampoline_params.join(", "),
+            ));
+            c_params.push(format!("{param_name}: {fn_ty}"));
+            matchers.push(format!("${param_name}:expr"));
+            binds.push_str(&format!(
+                "        let mut __cb_{param_name} = \
+                 {module}::__bind_{param_name}(${param_name});\n"
+            ));
+            args.push(format!("{module}::__trampoline_of_{param_name}(&mut __cb_{param_name})"));
+        } else if let Some(cb) = userdata.iter().position(|&j| j == Some(i)) {
+            c_params.push(format!("{param_name}: *mut ::std::ffi::c_void"));
+            let cb_name = &names[cb];
+            args.push(format!("&mut __cb_{cb_name} as *mut _ as *mut ::std::ffi::c_void"));
+        } else {
+            let conv = Conv::of(&param.ty);
+            c_params.push(format!("{param_name}: {}", conv.c_ty()));
+            matchers.push(format!("${param_name}:expr"));
+            // Strings are only borrowed, as a plain call would.
+            let borrow = if let Conv::Str = conv { "&" } else { "" };
+            binds.push_str(&format!("        let __arg_{param_name} = {borrow}${param_name};\n"));
+            args.push(conv.c_expr(&format!("__arg_{param_name}")));
+        }
+    }
+
+    let mut matcher = matchers.join(", ");
+    let mut args = args.join(", ");
+    if decl.c_variadic() {
+        c_params.push("...".to_string());
+        let rest = "crate::__script_helpers::__CPassed::__c_passed(\
+                    &crate::__script_helpers::__CVarArg::__c_hold(&$__rest))";
+        if matchers.is_empty() {
+            matcher.push_str("$($__rest:expr),*");
+            args.push_str(&format!("$({rest}),*"));
+        } else {
+            matcher.push_str(" $(, $__rest:expr)*");
+            args.push_str(&format!("$(, {rest})*"));
+        }
+    }
+    let ret = match &decl.output {
+        ast::FnRetTy::Default(_) => None,
+        ast::FnRetTy::Ty(ty) => Some(Conv::of(ty)),
+    };
+    let ret_c = ret.as_ref().map(|conv| format!(" -> {}", conv.c_ty())).unwrap_or_default();
+    let result = ret.as_ref().map(|conv| conv.script_expr("__ret")).unwrap_or("__ret".to_string());
+    Ok(format!(
+        "#![allow(dead_code, unused_imports, improper_ctypes, non_snake_case)]\n\
+         use super::*;\n\
+         unsafe extern \"C\" {{\n\
+         \x20   pub fn {name}({c_params}){ret_c};\n\
+         }}\n\
+         {items}\
+         macro_rules! __call {{\n\
+         \x20   ({matcher} $(,)?) => {{{{\n\
+         {binds}\
+         \x20       let __ret = {module}::{name}({args});\n\
+         \x20       {result}\n\
+         \x20   }}}};\n\
+         }}\n\
+         pub(crate) use __call as call;\n",
+        c_params = c_params.join(", "),
+    ))
+}
+
+/// The declaration of `ty` if it is a callback: a function pointer taking `*mut c_void` first.
+fn callback(ty: &ast::Ty) -> Option<&ast::FnDecl> {
+    match &ty.kind {
+        ast::TyKind::FnPtr(fn_ptr)
+            if fn_ptr.decl.inputs.first().is_some_and(|param| is_void_ptr(&param.ty)) =>
+        {
+            Some(&fn_ptr.decl)
+        }
+        _ => None,
+    }
+}
+
+fn is_void_ptr(ty: &ast::Ty) -> bool {
+    matches!(&ty.kind, ast::TyKind::Ptr(mt) if mt.mutbl.is_mut() && is_named(&mt.ty, "c_void"))
+}
+
+/// Whether `ty` is a path ending in `name`, such as `c_void` or `std::ffi::c_void`.
+fn is_named(ty: &ast::Ty, name: &str) -> bool {
+    match &ty.kind {
+        ast::TyKind::Path(None, path) => path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.args.is_none() && segment.ident.as_str() == name),
+        _ => false,
+    }
+}
+
+/// `ty` as written, with pointers followed and the `std::ffi` C types qualified.
+fn c_ty(ty: &ast::Ty) -> String {
+    match &ty.kind {
+        ast::TyKind::Paren(inner) => c_ty(inner),
+        ast::TyKind::Ptr(mt) => {
+            let prefix = if mt.mutbl.is_mut() { "*mut" } else { "*const" };
+            format!("{prefix} {}", c_ty(&mt.ty))
+        }
+        ast::TyKind::Path(None, path)
+            if let [segment] = &path.segments[..]
+                && segment.args.is_none()
+                && C_TYPES.contains(&segment.ident.as_str()) =>
+        {
+            format!("::std::ffi::{}", segment.ident)
+        }
+        _ => pprust::ty_to_string(ty),
+    }
+}
@@ -7,9 +7,14 @@
 use rustc_ast as ast;
 use rustc_span::{Ident, Span, sym};
 
+pub(crate) mod c_header;
+pub mod dynexport;
+pub(crate) mod dynload;
 mod extensions;
+pub(crate) mod import_fn;
 #[allow(dead_code)]
 mod macros;
+pub(crate) mod units;
 #[allow(dead_code)]
 mod val;
 
@@ -132,3 +137,35 @@ pub fn create_derive_attr(span: Span, traits: &[rustc_span::Symbol]) -> ast::Att
         span,
     }
 }
+
+/// Path to a script helper: `crate::__script_helpers::<name>`. The script harness puts every
+/// helper in that module, so what script syntax desugars to is found whatever the script
+/// itself defines at its root.
+pub fn script_helper_path(name: rustc_span::Symbol, span: Span) -> ast::Path {
+    ast::Path {
+        span,
+        segments: [rustc_span::kw::Crate, sym::__script_helpers, name]
+            .into_iter()
+            .map(|segment| ast::PathSegment::from_ident(Ident::new(segment, span)))
+            .collect(),
+        tokens: None,
+    }
+}
+
+/// The tokens of `script_helper_path`, for desugarings built as token streams.
+pub fn script_helper_path_tokens(
+    name: rustc_span::Symbol,
+    span: Span,
+) -> Vec<ast::tokenstream::TokenTree> {
+    use rustc_ast::token::{IdentIsRaw, TokenKind};
+    use rustc_ast::tokenstream::TokenTree;
+
+    let ident = |name| TokenTree::token_alone(TokenKind::Ident(name, IdentIsRaw::No), span);
+    vec![
+        ident(rustc_span::kw::Crate),
+        TokenTree::token_alone(TokenKind::PathSep, span),
+        ident(sym::__script_helpers),
+        TokenTree::token_alone(TokenKind::PathSep, span),
+        ident(name),
+    ]
+}
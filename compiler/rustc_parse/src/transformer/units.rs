@@ -0,0 +1,106 @@
+//! Unit suffixes on numeric literals in script mode.
+//!
+//! `500ms` lexes as an integer literal with suffix `ms`. The parser splits
+//! compound suffixes (`m/s`, `m²`, `N·m`) into factors and maps each unit
+//! symbol to a constant of the injected `units` module. `UNITS` is the only
+//! list of units: the constants themselves are generated from it and appended
+//! to compiler/extensions/src/units.rs, which defines the `Quantity` type and
+//! the dimensions.
+
+/// Unit symbol, constant name in `crate::__script_helpers::units`, dimension and
+/// size in SI base units (written as a float literal).
+const UNITS: &[(&str, &str, &str, &str)] = &[
+    // Length
+    ("mm", "MilliMeter", "Length", "1e-3"),
+    ("cm", "CentiMeter", "Length", "1e-2"),
+    ("m", "Meter", "Length", "1.0"),
+    ("km", "KiloMeter", "Length", "1e3"),
+    ("inch", "Inch", "Length", "0.0254"),
+    ("ft", "Foot", "Length", "0.3048"),
+    ("yd", "Yard", "Length", "0.9144"),
+    ("mi", "Mile", "Length", "1609.344"),
+    // Area and volume (m² and m³ are written as `m²`/`m³` suffixes)
+    ("ha", "Hectare", "Area", "1e4"),
+    ("mL", "MilliLiter", "Volume", "1e-6"),
+    ("L", "Liter", "Volume", "1e-3"),
+    // Time
+    ("ns", "NanoSecond", "Time", "1e-9"),
+    ("us", "MicroSecond", "Time", "1e-6"),
+    ("ms", "MilliSecond", "Time", "1e-3"),
+    ("s", "Second", "Time", "1.0"),
+    ("min", "Minute", "Time", "60.0"),
+    ("h", "Hour", "Time", "3600.0"),
+    ("d", "Day", "Time", "86400.0"),
+    // Mass
+    ("mg", "MilliGram", "Mass", "1e-6"),
+    ("g", "Gram", "Mass", "1e-3"),
+    ("kg", "KiloGram", "Mass", "1.0"),
+    ("t", "Tonne", "Mass", "1e3"),
+    ("oz", "Ounce", "Mass", "0.028349523125"),
+    ("lb", "Pound", "Mass", "0.45359237"),
+    // Acceleration: `gf` is one standard gravity
+    ("gf", "StandardGravity", "Acceleration", "9.80665"),
+    // Force
+    ("N", "Newton", "Force", "1.0"),
+    ("kN", "KiloNewton", "Force", "1e3"),
+    // Energy
+    ("J", "Joule", "Energy", "1.0"),
+    ("kJ", "KiloJoule", "Energy", "1e3"),
+    ("Wh", "WattHour", "Energy", "3600.0"),
+    ("kWh", "KiloWattHour", "Energy", "3.6e6"),
+    ("cal", "Calorie", "Energy", "4.184"),
+    ("kcal", "KiloCalorie", "Energy", "4184.0"),
+    // Power
+    ("W", "Watt", "Power", "1.0"),
+    ("kW", "KiloWatt", "Power", "1e3"),
+    ("MW", "MegaWatt", "Power", "1e6"),
+    // Pressure
+    ("Pa", "Pascal", "Pressure", "1.0"),
+    ("kPa", "KiloPascal", "Pressure", "1e3"),
+    ("bar", "Bar", "Pressure", "1e5"),
+    ("atm", "Atmosphere", "Pressure", "101325.0"),
+];
+
+/// `pub const` items for every unit in `UNITS`, appended to the injected `units` module.
+pub(crate) fn unit_constants() -> String {
+    UNITS
+        .iter()
+        .map(|(_, name, dimension, si)| {
+            format!("pub const {name}: Quantity<{dimension}> = Quantity::new({si});\n")
+        })
+        .collect()
+}
+
+/// One factor of a compound unit: `s²` in `m/s²` is
+/// `UnitFactor { name: "Second", power: 2, divide: true }`.
+pub(crate) struct UnitFactor {
+    pub name: &'static str,
+    pub power: u8,
+    pub divide: bool,
+}
+
+pub(crate) fn is_unit_suffix(suffix: &str) -> bool {
+    !rustc_lexer::is_numeric_type_suffix(suffix)
+}
+
+/// Split a unit suffix like `kg·m/s²` into its factors.
+/// Returns the offending symbol if any part is not a known unit.
+pub(crate) fn parse_unit_suffix(suffix: &str) -> Result<Vec<UnitFactor>, String> {
+    let mut factors = Vec::new();
+    for (i, group) in suffix.split('/').enumerate() {
+        for part in group.split('·') {
+            let (symbol, power) = if let Some(s) = part.strip_suffix('²') {
+                (s, 2)
+            } else if let Some(s) = part.strip_suffix('³') {
+                (s, 3)
+            } else {
+                (part, 1)
+            };
+            let Some(&(_, name, ..)) = UNITS.iter().find(|(sym, ..)| *sym == symbol) else {
+                return Err(symbol.to_string());
+            };
+            factors.push(UnitFactor { name, power, divide: i > 0 });
+        }
+    }
+    Ok(factors)
+}
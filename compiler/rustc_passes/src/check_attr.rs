@@ -333,7 +333,7 @@ fn check_attributes(
                     | AttributeKind::UnstableFeatureBound(..)
                     | AttributeKind::Used { .. }
                     | AttributeKind::WindowsSubsystem(..)
-                    | AttributeKind::DynExport(..)
+                    | AttributeKind::DynExport { .. }
                     // tidy-alphabetical-end
 
                 ) => { /* do nothing  */ }
@@ -382,6 +382,7 @@ fn check_attributes(
                             | sym::rustc_conversion_suggestion
                             | sym::rustc_deprecated_safe_2024
                             | sym::rustc_test_marker
+                            | sym::rustc_dynexport_c_shim
                             | sym::rustc_abi
                             | sym::rustc_layout
                             | sym::rustc_proc_macro_decls
@@ -566,6 +566,9 @@ fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) -> Self::Result {
             hir::ExprKind::Field(ref lhs, ..) => {
                 if self.typeck_results().opt_field_index(expr.hir_id).is_some() {
                     self.handle_field_access(lhs, expr.hir_id);
+                } else if self.typeck_results().is_method_call(expr) {
+                    // Script mode map access `m.key`.
+                    self.lookup_and_handle_method(expr.hir_id);
                 } else {
                     self.tcx.dcx().span_delayed_bug(expr.span, "couldn't resolve index for field");
                 }
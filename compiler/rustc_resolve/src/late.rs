@@ -5112,11 +5112,17 @@ fn resolve_expr(&mut self, expr: &'ast Expr, parent: Option<&'ast Expr>) {
             ExprKind::Block(ref block, label) => self.resolve_labeled_block(label, block.id, block),
 
             // Equivalent to `visit::walk_expr` + passing some context to children.
-            ExprKind::Field(ref subexpression, _) => {
-                self.resolve_expr(subexpression, Some(expr));
+            ExprKind::Field(ref subexpression, ident) => {
+                let item = Segment::from_ident(ident);
+                if !self.resolve_script_module_item(expr.id, subexpression, item, expr.span) {
+                    self.resolve_expr(subexpression, Some(expr));
+                }
             }
             ExprKind::MethodCall(box MethodCall { ref seg, ref receiver, ref args, .. }) => {
-                self.resolve_expr(receiver, Some(expr));
+                let span = receiver.span.to(seg.ident.span);
+                if !self.resolve_script_module_item(expr.id, receiver, seg.into(), span) {
+                    self.resolve_expr(receiver, Some(expr));
+                }
                 for arg in args {
                     self.resolve_expr(arg, None);
                 }
@@ -5197,6 +5203,44 @@ fn resolve_expr(&mut self, expr: &'ast Expr, parent: Option<&'ast Expr>) {
         }
     }
 
+    /// Script mode: Go-style `units.KiloMeter` or `helper.Hello()`, where the receiver names a
+    /// module and no value, resolves `id` as the path `units::KiloMeter` or `helper::Hello`. AST
+    /// lowering then turns the field access or method call into that path. Returns `false`, leaving `base` to
+    /// be resolved as usual, when `base` isn't such a module, e.g. when a local `units` shadows it.
+    fn resolve_script_module_item(
+        &mut self,
+        id: NodeId,
+        base: &'ast Expr,
+        item: Segment,
+        span: Span,
+    ) -> bool {
+        if !self.r.tcx.sess.is_script_mode() {
+            return false;
+        }
+        let ExprKind::Path(None, path) = &base.kind else { return false };
+        let [module] = &path.segments[..] else { return false };
+        if module.args.is_some()
+            || self.maybe_resolve_ident_in_lexical_scope(module.ident, ValueNS).is_some()
+        {
+            return false;
+        }
+        let is_module = self
+            .maybe_resolve_ident_in_lexical_scope(module.ident, TypeNS)
+            .is_some_and(|decl| matches!(decl.res(), Res::Def(DefKind::Mod, _)));
+        if !is_module {
+            return false;
+        }
+        self.smart_resolve_path_fragment(
+            &None,
+            &[module.into(), item],
+            PathSource::Expr(None),
+            Finalize::new(id, span),
+            RecordPartialRes::Yes,
+            None,
+        );
+        true
+    }
+
     fn record_candidate_traits_for_expr_if_necessary(&mut self, expr: &'ast Expr) {
         match expr.kind {
             ExprKind::Field(_, ident) => {
@@ -753,6 +753,22 @@ pub fn default_filename(&self) -> &'static str {
         is_text: true,
         compatible_with_cgus_and_single_output: true
     },
+    DynexportHeader => {
+        shorthand: "dynexport-header",
+        extension: "h",
+        description: "Generates a C header declaring the crate's `#[dynexport]` items",
+        default_filename: "CRATE_NAME.h",
+        is_text: true,
+        compatible_with_cgus_and_single_output: true
+    },
+    DynexportPy => {
+        shorthand: "dynexport-py",
+        extension: "py",
+        description: "Generates a Python ctypes module loading the crate's `#[dynexport]` items",
+        default_filename: "CRATE_NAME.py",
+        is_text: true,
+        compatible_with_cgus_and_single_output: true
+    },
     Exe => {
         shorthand: "link",
         extension: "",
@@ -887,7 +903,10 @@ pub fn should_codegen(&self) -> bool {
             | OutputType::Mir
             | OutputType::Object
             | OutputType::Exe => true,
-            OutputType::Metadata | OutputType::DepInfo => false,
+            OutputType::Metadata
+            | OutputType::DepInfo
+            | OutputType::DynexportHeader
+            | OutputType::DynexportPy => false,
         })
     }
 
@@ -901,7 +920,9 @@ pub fn should_link(&self) -> bool {
             | OutputType::Mir
             | OutputType::Metadata
             | OutputType::Object
-            | OutputType::DepInfo => false,
+            | OutputType::DepInfo
+            | OutputType::DynexportHeader
+            | OutputType::DynexportPy => false,
             OutputType::Exe => true,
         })
     }
@@ -1882,6 +1903,22 @@ pub fn rustc_optgroups() -> Vec<RustcOptGroup> {
             "Defines which scopes of paths should be remapped by `--remap-path-prefix`",
             "<macro,diagnostics,debuginfo,coverage,object,all>",
         ),
+        opt(
+            Unstable,
+            Multi,
+            "",
+            "inspect-dynexport",
+            "Show the #[dynexport] items of a shared library; with two libraries, compare them",
+            "<LIB>",
+        ),
+        opt(
+            Unstable,
+            Opt,
+            "",
+            "inspect-format",
+            "Output format of --inspect-dynexport",
+            "<text|json>",
+        ),
         opt(Unstable, Multi, "", "env-set", "Inject an environment variable", "<VAR>=<VALUE>"),
     ];
     options.extend(verbose_only.into_iter().map(|mut opt| {
@@ -2152,10 +2189,16 @@ fn parse_output_types(
                         display = OutputType::shorthands_display(),
                     ))
                 });
-                if output_type == OutputType::ThinLinkBitcode && !unstable_opts.unstable_options {
+                let unstable = matches!(
+                    output_type,
+                    OutputType::ThinLinkBitcode
+                        | OutputType::DynexportHeader
+                        | OutputType::DynexportPy
+                );
+                if unstable && !unstable_opts.unstable_options {
                     early_dcx.early_fatal(format!(
                         "{} requested but -Zunstable-options not specified",
-                        OutputType::ThinLinkBitcode.shorthand()
+                        output_type.shorthand()
                     ));
                 }
                 output_types.insert(output_type, path);
@@ -279,6 +279,14 @@ pub struct ParseSess {
     /// Whether script mode is enabled (shebang file or -Z script).
     /// In script mode, string literals automatically become String type.
     script_mode: std::sync::atomic::AtomicBool,
+    /// Functions declared by a script-mode `import fn` that expands to `mod __import_<name>`.
+    /// `name(..)` on one of these parses as the macro call `__import_<name>::call!(..)`,
+    /// unless a local of that name is in scope.
+    pub script_import_fns: Lock<FxIndexSet<Symbol>>,
+    /// Locals named like one of `script_import_fns` that are in scope, innermost last.
+    pub script_import_fn_locals: Lock<Vec<Symbol>>,
+    /// Files currently being imported, innermost last. Used to report import cycles.
+    pub script_import_stack: Lock<Vec<std::path::PathBuf>>,
 }
 
 impl ParseSess {
@@ -314,6 +322,9 @@ pub fn with_dcx(dcx: DiagCtxt, source_map: Arc<SourceMap>) -> Self {
             proc_macro_quoted_spans: Default::default(),
             attr_id_generator: AttrIdGenerator::new(),
             script_mode: std::sync::atomic::AtomicBool::new(false),
+            script_import_fns: Default::default(),
+            script_import_fn_locals: Default::default(),
+            script_import_stack: Default::default(),
         }
     }
 
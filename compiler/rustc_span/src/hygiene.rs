@@ -1205,6 +1205,7 @@ pub enum AstPass {
     TestHarness,
     ProcMacroHarness,
     ScriptMain,
+    DynexportShims,
 }
 
 impl AstPass {
@@ -1214,6 +1215,7 @@ pub fn descr(self) -> &'static str {
             AstPass::TestHarness => "test harness",
             AstPass::ProcMacroHarness => "proc macro harness",
             AstPass::ScriptMain => "script main wrapper",
+            AstPass::DynexportShims => "dynexport shims",
         }
     }
 }
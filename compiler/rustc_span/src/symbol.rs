@@ -285,6 +285,7 @@
         Ipv6Addr,
         IrTyKind,
         Is,
+        IsNil,
         Item,
         ItemContext,
         IterEmpty,
@@ -425,8 +426,15 @@
         __v,
         __awaitee,
         __debug_string,
+        __map_field,
+        __map_field_mut,
         __null_coalesce,
         __optional_chain,
+        __script_helpers,
+        __try_catch,
+        __try_ok,
+        __try_rethrow,
+        __try_value,
         __try_var,
         _t,
         _task_context,
@@ -1020,6 +1028,8 @@
         eqs,
         ergonomic_clones,
         ermsb_target_feature,
+        error,
+        errors,
         exact_div,
         except,
         exception_handling: "exception-handling",
@@ -1132,6 +1142,7 @@
         file_options,
         filter,
         filtered,
+        finally,
         find,
         first,
         first_cloned,
@@ -1314,6 +1325,7 @@
         import_trait_associated_functions,
         imported_main,
         in_band_lifetimes,
+        in_operator,
         include,
         include_bytes,
         include_bytes_macro,
@@ -1369,6 +1381,7 @@
         item_like_imports,
         iter,
         iter_cloned,
+        iter_contains,
         iter_copied,
         iter_filter,
         iter_mut,
@@ -1648,6 +1661,7 @@
         no_main,
         no_mangle,
         no_sanitize,
+        no_script_prelude,
         no_stack_check,
         no_std,
         nomem,
@@ -1686,6 +1700,7 @@
         on,
         on_const,
         on_unimplemented,
+        only,
         opaque,
         opaque_module_name_placeholder: "<opaque>",
         open_options_new,
@@ -2039,6 +2054,7 @@
         rustc_dump_user_args,
         rustc_dump_vtable,
         rustc_dyn_incompatible_trait,
+        rustc_dynexport_c_shim,
         rustc_effective_visibility,
         rustc_eii_foreign_item,
         rustc_evaluate_where_clauses,
@@ -2127,11 +2143,14 @@
         saturating_div,
         saturating_sub,
         script,
+        script_map_field,
+        script_prelude,
         ScriptSliceExt,
         ScriptStrExt,
         ScriptVecExt,
         Truthy,
         is_truthy,
+        is_nil,
         is_empty,
         is_some,
         float_zero: "0.0",
@@ -2500,6 +2519,7 @@
         unimplemented_macro,
         unicode,
         unit,
+        units,
         universal_impl_trait,
         unix,
         unlikely,
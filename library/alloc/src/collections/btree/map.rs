@@ -2493,6 +2493,19 @@ fn index(&self, key: &Q) -> &V {
     }
 }
 
+/// Key lookup, `key in map`.
+#[unstable(feature = "in_operator", issue = "none")]
+impl<K, Q: ?Sized, V, A: Allocator + Clone> core::ops::Contains<Q> for BTreeMap<K, V, A>
+where
+    K: Borrow<Q> + Ord,
+    Q: Ord,
+{
+    #[inline]
+    fn contains(&self, key: &Q) -> bool {
+        self.contains_key(key)
+    }
+}
+
 #[stable(feature = "std_collections_from_array", since = "1.56.0")]
 impl<K: Ord, V, const N: usize> From<[(K, V); N]> for BTreeMap<K, V> {
     /// Converts a `[(K, V); N]` into a `BTreeMap<K, V>`.
@@ -1640,6 +1640,19 @@ fn default() -> BTreeSet<T> {
     }
 }
 
+/// Membership, `value in set`.
+#[unstable(feature = "in_operator", issue = "none")]
+impl<T, Q: ?Sized, A: Allocator + Clone> core::ops::Contains<Q> for BTreeSet<T, A>
+where
+    T: Borrow<Q> + Ord,
+    Q: Ord,
+{
+    #[inline]
+    fn contains(&self, value: &Q) -> bool {
+        BTreeSet::contains(self, value)
+    }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T: Ord + Clone, A: Allocator + Clone> Sub<&BTreeSet<T, A>> for &BTreeSet<T, A> {
     type Output = BTreeSet<T, A>;
@@ -126,6 +126,7 @@
 #![feature(freeze)]
 #![feature(generic_atomic)]
 #![feature(hasher_prefixfree_extras)]
+#![feature(in_operator)]
 #![feature(inplace_iteration)]
 #![feature(iter_advance_by)]
 #![feature(iter_next_chunk)]
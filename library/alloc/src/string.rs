@@ -2818,6 +2818,8 @@ fn add(mut self, other: $t) -> String {
 // Only implement for default numeric types (i32, f64) to avoid type inference ambiguity
 // with unsuffixed literals. For other numeric types, users can cast explicitly.
 impl_string_add_for_type!(i32, f64, char);
+// Borrowed forms, used by script-mode juxtaposition `"n:" count "items"` -> `... + &count`.
+impl_string_add_for_type!(&i32, &f64, &char);
 
 /// Special implementation for bool that uses emoji representation.
 #[cfg(not(no_global_oom_handling))]
@@ -2836,6 +2838,18 @@ fn add(mut self, other: bool) -> String {
     }
 }
 
+/// Implements `String + &bool` with the same emoji representation as `String + bool`.
+#[cfg(not(no_global_oom_handling))]
+#[stable(feature = "string_concat_bool", since = "1.0.0")]
+impl Add<&bool> for String {
+    type Output = String;
+
+    #[inline]
+    fn add(self, other: &bool) -> String {
+        self + *other
+    }
+}
+
 /// Implements `String + String` concatenation.
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "string_concat_string", since = "1.0.0")]
@@ -2946,6 +2960,39 @@ fn index_mut(&mut self, index: I) -> &mut I::Output {
     }
 }
 
+/// Substring search, `needle in string`.
+#[unstable(feature = "in_operator", issue = "none")]
+impl ops::Contains<str> for String {
+    #[inline]
+    fn contains(&self, needle: &str) -> bool {
+        self.as_str().contains(needle)
+    }
+}
+
+#[unstable(feature = "in_operator", issue = "none")]
+impl ops::Contains<String> for String {
+    #[inline]
+    fn contains(&self, needle: &String) -> bool {
+        self.as_str().contains(needle.as_str())
+    }
+}
+
+#[unstable(feature = "in_operator", issue = "none")]
+impl ops::Contains<char> for String {
+    #[inline]
+    fn contains(&self, needle: &char) -> bool {
+        self.as_str().contains(*needle)
+    }
+}
+
+#[unstable(feature = "in_operator", issue = "none")]
+impl ops::Contains<String> for str {
+    #[inline]
+    fn contains(&self, needle: &String) -> bool {
+        self.contains(needle.as_str())
+    }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl ops::Deref for String {
     type Target = str;
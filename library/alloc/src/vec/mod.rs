@@ -3749,6 +3749,14 @@ fn index_mut(&mut self, index: I) -> &mut Self::Output {
     }
 }
 
+#[unstable(feature = "in_operator", issue = "none")]
+impl<T: PartialEq<N>, N: ?Sized, A: Allocator> ops::Contains<N> for Vec<T, A> {
+    #[inline]
+    fn contains(&self, needle: &N) -> bool {
+        ops::Contains::contains(&**self, needle)
+    }
+}
+
 /// Collects an iterator into a Vec, commonly called via [`Iterator::collect()`]
 ///
 /// # Allocation behavior
@@ -0,0 +1,128 @@
+use crate::borrow::Borrow;
+use crate::ops::{Range, RangeFrom, RangeInclusive, RangeTo, RangeToInclusive};
+
+/// The `in` operator, `needle in haystack`.
+///
+/// `needle in haystack` is `Contains::contains(&haystack, &needle)`; the needle
+/// is borrowed unless it already is a reference, so both `2 in [1, 2, 3]` and
+/// `&2 in [1, 2, 3]` look for `2`. A reference needle is looked for as-is when
+/// the haystack has no impl for its referent, as in `"a" in ["a", "b"]`.
+/// `needle not in haystack` negates the result.
+///
+/// Sequences compare their elements with the needle through [`PartialEq`], so
+/// `"word" in vec_of_strings` works; maps and sets look the needle up as a key
+/// through [`Borrow`], so `"key" in map` works for a `HashMap<String, V>`.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(in_operator)]
+/// use std::ops::Contains;
+///
+/// struct Bag(Vec<u32>);
+///
+/// impl Contains<u32> for Bag {
+///     fn contains(&self, needle: &u32) -> bool {
+///         self.0.contains(needle)
+///     }
+/// }
+///
+/// assert!(3 in Bag(vec![1, 2, 3]));
+/// assert!(4 not in Bag(vec![1, 2, 3]));
+/// ```
+#[lang = "contains"]
+#[diagnostic::on_unimplemented(
+    message = "cannot check whether `{Self}` contains `{Needle}`",
+    label = "no implementation for `{Needle} in {Self}`"
+)]
+#[unstable(feature = "in_operator", issue = "none")]
+pub trait Contains<Needle: ?Sized> {
+    /// Returns `true` if `needle` is in `self`.
+    #[unstable(feature = "in_operator", issue = "none")]
+    fn contains(&self, needle: &Needle) -> bool;
+}
+
+/// `needle in iterator`: iterators are consumed by the search, so they can't
+/// implement [`Contains`] and the operator calls this instead. Items are
+/// borrowed as the needle, which lets `2 in v.iter()` find `&2`.
+#[lang = "iter_contains"]
+#[inline]
+pub(crate) fn iter_contains<I, N>(mut iter: I, needle: &N) -> bool
+where
+    I: Iterator<Item: Borrow<N>>,
+    N: ?Sized + PartialEq,
+{
+    iter.any(|item| item.borrow() == needle)
+}
+
+#[unstable(feature = "in_operator", issue = "none")]
+impl<C: ?Sized + Contains<N>, N: ?Sized> Contains<N> for &C {
+    #[inline]
+    fn contains(&self, needle: &N) -> bool {
+        C::contains(&**self, needle)
+    }
+}
+
+#[unstable(feature = "in_operator", issue = "none")]
+impl<C: ?Sized + Contains<N>, N: ?Sized> Contains<N> for &mut C {
+    #[inline]
+    fn contains(&self, needle: &N) -> bool {
+        C::contains(&**self, needle)
+    }
+}
+
+#[unstable(feature = "in_operator", issue = "none")]
+impl<T: PartialEq<N>, N: ?Sized> Contains<N> for [T] {
+    #[inline]
+    fn contains(&self, needle: &N) -> bool {
+        self.iter().any(|item| *item == *needle)
+    }
+}
+
+#[unstable(feature = "in_operator", issue = "none")]
+impl<T: PartialEq<N>, N: ?Sized, const LEN: usize> Contains<N> for [T; LEN] {
+    #[inline]
+    fn contains(&self, needle: &N) -> bool {
+        <[T] as Contains<N>>::contains(self, needle)
+    }
+}
+
+/// Substring search, `"ell" in "hello"`.
+#[unstable(feature = "in_operator", issue = "none")]
+impl Contains<str> for str {
+    #[inline]
+    fn contains(&self, needle: &str) -> bool {
+        str::contains(self, needle)
+    }
+}
+
+#[unstable(feature = "in_operator", issue = "none")]
+impl Contains<char> for str {
+    #[inline]
+    fn contains(&self, needle: &char) -> bool {
+        str::contains(self, *needle)
+    }
+}
+
+/// `x in Some(x)`; nothing is in `None`.
+#[unstable(feature = "in_operator", issue = "none")]
+impl<T: PartialEq<N>, N: ?Sized> Contains<N> for Option<T> {
+    #[inline]
+    fn contains(&self, needle: &N) -> bool {
+        self.as_ref().is_some_and(|item| *item == *needle)
+    }
+}
+
+macro_rules! range_contains_impl {
+    ($($range:ident)*) => ($(
+        #[unstable(feature = "in_operator", issue = "none")]
+        impl<Idx: PartialOrd> Contains<Idx> for $range<Idx> {
+            #[inline]
+            fn contains(&self, needle: &Idx) -> bool {
+                $range::contains(self, needle)
+            }
+        }
+    )*)
+}
+
+range_contains_impl! { Range RangeFrom RangeInclusive RangeTo RangeToInclusive }
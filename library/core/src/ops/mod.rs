@@ -141,6 +141,7 @@
 mod arith;
 mod async_function;
 mod bit;
+mod contains;
 mod control_flow;
 mod coroutine;
 mod deref;
@@ -163,6 +164,8 @@
 pub use self::bit::{BitAnd, BitOr, BitXor, Not, Shl, Shr};
 #[stable(feature = "op_assign_traits", since = "1.8.0")]
 pub use self::bit::{BitAndAssign, BitOrAssign, BitXorAssign, ShlAssign, ShrAssign};
+#[unstable(feature = "in_operator", issue = "none")]
+pub use self::contains::Contains;
 #[stable(feature = "control_flow_enum_type", since = "1.55.0")]
 pub use self::control_flow::ControlFlow;
 #[unstable(feature = "coroutine_trait", issue = "43122")]
@@ -1477,6 +1477,21 @@ fn index(&self, key: &Q) -> &V {
     }
 }
 
+/// Key lookup, `key in map`.
+#[unstable(feature = "in_operator", issue = "none")]
+impl<K, Q: ?Sized, V, S, A> crate::ops::Contains<Q> for HashMap<K, V, S, A>
+where
+    K: Eq + Hash + Borrow<Q>,
+    Q: Eq + Hash,
+    S: BuildHasher,
+    A: Allocator,
+{
+    #[inline]
+    fn contains(&self, key: &Q) -> bool {
+        self.contains_key(key)
+    }
+}
+
 #[stable(feature = "std_collections_from_array", since = "1.56.0")]
 // Note: as what is currently the most convenient built-in way to construct
 // a HashMap, a simple usage of this function must not *require* the user
@@ -1248,6 +1248,21 @@ fn default() -> HashSet<T, S> {
     }
 }
 
+/// Membership, `value in set`.
+#[unstable(feature = "in_operator", issue = "none")]
+impl<T, Q: ?Sized, S, A> crate::ops::Contains<Q> for HashSet<T, S, A>
+where
+    T: Eq + Hash + Borrow<Q>,
+    Q: Eq + Hash,
+    S: BuildHasher,
+    A: Allocator,
+{
+    #[inline]
+    fn contains(&self, value: &Q) -> bool {
+        HashSet::contains(self, value)
+    }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T, S> BitOr<&HashSet<T, S>> for &HashSet<T, S>
 where
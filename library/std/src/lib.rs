@@ -343,6 +343,7 @@
 #![feature(hasher_prefixfree_extras)]
 #![feature(hashmap_internals)]
 #![feature(hint_must_use)]
+#![feature(in_operator)]
 #![feature(int_from_ascii)]
 #![feature(ip)]
 #![feature(maybe_uninit_array_assume_init)]
@@ -0,0 +1,23 @@
+/* A few libc declarations for test_include_header.rust. */
+#ifndef LIBC_SUBSET_H
+#define LIBC_SUBSET_H
+
+#include <stddef.h>
+
+#define BUFFER_SIZE (1 << 8)
+#define EXIT_OK 0
+
+typedef struct {
+    int quot;
+    int rem;
+} div_t;
+
+enum seek_whence { FROM_START, FROM_CURRENT, FROM_END };
+
+int abs(int n);
+long labs(long n);
+div_t div(int numerator, int denominator);
+size_t strlen(const char *s);
+int snprintf(char *buf, size_t len, const char *format, ...);
+
+#endif
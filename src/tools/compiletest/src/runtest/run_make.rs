@@ -188,7 +188,9 @@ pub(super) fn run_rmake_test(&self) {
             .env("RUSTC", &self.config.rustc_path)
             // Provide which LLVM components are available (e.g. which LLVM components are provided
             // through a specific CI runner).
-            .env("LLVM_COMPONENTS", &self.config.llvm_components);
+            .env("LLVM_COMPONENTS", &self.config.llvm_components)
+            // Provide the codegen backend the suite is tested with (`llvm`, `cranelift` or `gcc`).
+            .env("CODEGEN_BACKEND", self.config.default_codegen_backend.as_str());
 
         // The `run-make-cargo` and `build-std` suites need an in-tree `cargo`, `run-make` does not.
         if matches!(self.config.suite, TestSuite::RunMakeCargo | TestSuite::BuildStd) {
@@ -0,0 +1,20 @@
+#!/usr/bin/env rust
+
+import lib "libplugin_llvm.so" as llvm {
+    fn add(a: i32, b: i32) -> i32
+    fn greet(name: &str) -> String
+}
+
+import lib "libplugin_other.so" as other {
+    fn add(a: i32, b: i32) -> i32
+    fn greet(name: &str) -> String
+}
+
+assert!(llvm.load().is_ok());
+assert!(other.load().is_ok());
+assert_eq!(llvm.add(2, 3), other.add(2, 3));
+assert_eq!(other.greet("other"), "Hello, other!");
+
+let interface = llvm.interface().unwrap();
+assert_eq!(interface.items.len(), 4);
+assert_eq!(other.interface().unwrap(), interface);
@@ -0,0 +1,24 @@
+#![crate_type = "cdylib"]
+
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+#[dynexport]
+pub fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[dynexport]
+pub fn greet(name: &str) -> String {
+    format!("Hello, {name}!")
+}
+
+#[dynexport(version = 2)]
+pub fn norm(p: &Point) -> f64 {
+    (p.x * p.x + p.y * p.y).sqrt()
+}
+
+#[dynexport]
+pub static VERSION: u32 = 3;
@@ -0,0 +1,47 @@
+// `#[dynexport]` metadata doesn't depend on the codegen backend: the same plugin built with
+// LLVM and with the backend under test (Cranelift or GCC) has identical `dynexport_meta_<name>`
+// records and interface descriptions, and one host loads both.
+
+//@ needs-backends: cranelift gcc
+// Reason: the other backend has to be in the sysroot; LLVM always is
+//@ only-linux
+// Reason: the host names the libraries `lib*.so` and loads them with dlopen
+//@ ignore-cross-compile
+// Reason: the compiled host is executed
+
+use run_make_support::{env_var, run, rustc};
+
+fn main() {
+    // `libplugin_other.so` comes from the backend under test.
+    for (backend, name) in [("llvm".to_string(), "llvm"), (env_var("CODEGEN_BACKEND"), "other")] {
+        rustc()
+            .input("plugin.rs")
+            .arg(format!("-Zcodegen-backend={backend}"))
+            .output(format!("libplugin_{name}.so"))
+            .run();
+    }
+
+    // Same records and descriptions, down to the compiler hash and the flags.
+    rustc()
+        .arg("-Zunstable-options")
+        .arg("--inspect-dynexport")
+        .arg("libplugin_llvm.so")
+        .arg("--inspect-dynexport")
+        .arg("libplugin_other.so")
+        .run()
+        .assert_stdout_contains("no changes");
+    let listing = |lib: &str| {
+        let listing = rustc()
+            .arg("-Zunstable-options")
+            .arg("--inspect-dynexport")
+            .arg(lib)
+            .run()
+            .stdout_utf8();
+        listing.split_once('\n').unwrap().1.to_string()
+    };
+    assert_eq!(listing("libplugin_llvm.so"), listing("libplugin_other.so"));
+
+    // An explicit output keeps the script from being cached and run by the compiler.
+    rustc().input("host.rs").output("host").run();
+    run("host");
+}
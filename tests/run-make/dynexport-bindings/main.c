@@ -0,0 +1,40 @@
+// Uses plugin.h, generated by `--emit=dynexport-header`, instead of declarations written by
+// hand as in dynexport-c-shim.
+
+#include <assert.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "plugin.h"
+// Headers of several libraries can be included together.
+#include "plugin.h"
+
+int main(void) {
+#ifdef EXPECT_MISMATCH
+    const char *changed = plugin_dynexport_check();
+    assert(changed != NULL && strcmp(changed, "greet") == 0);
+#else
+    assert(plugin_dynexport_check() == NULL);
+    assert(dynexport_meta_area.type_hash == PLUGIN_AREA_TYPE_HASH);
+    assert(PLUGIN_AREA_VERSION == 2);
+
+    FfiStr name = { (const uint8_t *)"C", 1 };
+    FfiString greeting = dynexport_c_greet(name);
+    assert(strcmp((const char *)greeting.ptr, "Hello, C!") == 0);
+    free(greeting.ptr);
+
+    FfiOptionUsize found = dynexport_c_find(name, 'C');
+    assert(found.is_some && found.value == 0);
+
+    Shape circle = { .tag = SHAPE_CIRCLE, .circle = { { 1.0, 1.0 }, 2.0 } };
+    assert(dynexport_c_area(circle) == 12.0);
+    FfiOptionPoint center = dynexport_c_center(circle);
+    assert(center.is_some && center.value.y == 1.0);
+
+    uint8_t buf[4];
+    fill(buf, sizeof buf, 'x');
+    assert(buf[3] == 'x');
+    assert(add(2, 3) == 5 && LIMIT == 64);
+#endif
+    return 0;
+}
@@ -0,0 +1,35 @@
+# Uses plugin.py, generated by `--emit=dynexport-py`.
+
+import ctypes
+
+import plugin
+
+lib = plugin.Library()
+assert lib.greet("Python").take() == "Hello, Python!"
+assert lib.lengths(["one", "three"]).take() == [3, 5]
+assert lib.find("hello", ord("l")).get() == 2
+assert lib.find("hello", ord("z")).get() is None
+
+mid = lib.midpoint(plugin.Point(0.0, 2.0), plugin.Point(4.0, 4.0))
+assert (mid.x, mid.y) == (2.0, 3.0)
+circle = plugin.Shape(
+    tag=plugin.SHAPE_CIRCLE, circle=plugin.Shape.Circle(plugin.Point(1.0, 1.0), 2.0)
+)
+assert lib.area(circle) == 12.0
+assert lib.center(circle).get().x == 1.0
+assert lib.center(plugin.Shape(tag=plugin.SHAPE_EMPTY)).get() is None
+
+buf = (ctypes.c_uint8 * 4)()
+lib.fill(buf, len(buf), ord("x"))
+assert bytes(buf) == b"xxxx"
+assert lib.add(2, 3) == 5
+assert lib.LIMIT.value == 64
+assert not hasattr(lib, "checksum")
+
+# The same items, built from other signatures.
+try:
+    plugin.Library("./libchanged.so")
+except plugin.DynexportError as error:
+    assert "`greet`" in str(error), error
+else:
+    raise AssertionError("libchanged.so was accepted")
@@ -0,0 +1,76 @@
+#![crate_type = "cdylib"]
+
+#[dynexport]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+#[dynexport]
+pub enum Shape {
+    Dot(Point),
+    Circle { center: Point, radius: f64 },
+    Empty,
+}
+
+#[cfg(not(changed))]
+#[dynexport]
+pub fn greet(name: &str) -> String {
+    format!("Hello, {name}!")
+}
+
+#[cfg(changed)]
+#[dynexport]
+pub fn greet(name: &str, excited: bool) -> String {
+    format!("Hello, {name}{}", if excited { "!" } else { "." })
+}
+
+#[dynexport]
+pub fn lengths(words: Vec<String>) -> Vec<usize> {
+    words.iter().map(|word| word.len()).collect()
+}
+
+#[dynexport]
+pub fn find(haystack: &str, needle: char) -> Option<usize> {
+    haystack.find(needle)
+}
+
+#[dynexport]
+pub fn midpoint(a: Point, b: Point) -> Point {
+    Point { x: (a.x + b.x) / 2.0, y: (a.y + b.y) / 2.0 }
+}
+
+#[dynexport(version = 2)]
+pub fn area(shape: Shape) -> f64 {
+    match shape {
+        Shape::Circle { radius, .. } => 3.0 * radius * radius,
+        Shape::Dot(_) | Shape::Empty => 0.0,
+    }
+}
+
+#[dynexport]
+pub fn center(shape: Shape) -> Option<Point> {
+    match shape {
+        Shape::Dot(point) | Shape::Circle { center: point, .. } => Some(point),
+        Shape::Empty => None,
+    }
+}
+
+#[dynexport]
+pub extern "C" fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[dynexport]
+pub unsafe extern "C" fn fill(buf: *mut u8, len: usize, byte: u8) {
+    unsafe { std::ptr::write_bytes(buf, byte, len) }
+}
+
+#[dynexport]
+pub static LIMIT: u64 = 64;
+
+// A slice has no C representation: only its type hash is declared.
+#[dynexport]
+pub fn checksum(bytes: &[u8]) -> u32 {
+    bytes.iter().map(|&byte| u32::from(byte)).sum()
+}
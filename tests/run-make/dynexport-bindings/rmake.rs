@@ -0,0 +1,44 @@
+// `--emit=dynexport-header,dynexport-py` writes a C header and a Python ctypes module for the
+// `#[dynexport]` items of a cdylib. Both compare the type hashes they were generated with
+// against the library's `dynexport_meta_<name>` records, and reject a library built from other
+// signatures. Both output types are unstable.
+
+//@ only-linux
+// Reason: the representation's buffers come from the C allocator of the same libc
+//@ ignore-cross-compile
+// Reason: the C program and the Python module load the library
+
+use run_make_support::{cc, cwd, python_command, run, rustc};
+
+fn main() {
+    rustc()
+        .input("plugin.rs")
+        .emit("link,dynexport-header")
+        .run_fail()
+        .assert_stderr_contains("dynexport-header requested but -Zunstable-options not specified");
+    rustc()
+        .input("plugin.rs")
+        .arg("-Zunstable-options")
+        .emit("link,dynexport-header,dynexport-py")
+        .run();
+    rustc().input("plugin.rs").cfg("changed").output("libchanged.so").run();
+
+    cc().input("main.c")
+        .arg("-Wall")
+        .arg("-Werror")
+        .arg("-lplugin")
+        .library_search_path(cwd())
+        .output("main")
+        .run();
+    run("main");
+
+    cc().input("main.c")
+        .arg("-DEXPECT_MISMATCH")
+        .arg("-lchanged")
+        .library_search_path(cwd())
+        .output("mismatch")
+        .run();
+    run("mismatch");
+
+    python_command().arg("main.py").run();
+}
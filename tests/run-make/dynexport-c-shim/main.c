@@ -0,0 +1,79 @@
+// Calls the `dynexport_c_*` shims of shapes.rs through the representation documented in
+// compiler/extensions/src/ffi.rs, written out by hand.
+
+#include <assert.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+#include <string.h>
+
+typedef struct { const uint8_t *ptr; size_t len; } FfiStr;
+typedef struct { uint8_t *ptr; size_t len; } FfiString;
+typedef struct { FfiString *ptr; size_t len; } FfiVecString;
+typedef struct { size_t *ptr; size_t len; } FfiVecUsize;
+typedef struct { bool is_some; size_t value; } FfiOptionUsize;
+
+typedef struct { double x; double y; } Point;
+typedef struct { bool is_some; Point value; } FfiOptionPoint;
+
+enum { SHAPE_DOT, SHAPE_CIRCLE, SHAPE_EMPTY };
+typedef struct {
+    uint32_t tag;
+    union {
+        struct { Point _0; } dot;
+        struct { Point center; double radius; } circle;
+    };
+} Shape;
+
+FfiString dynexport_c_greet(FfiStr name);
+FfiVecUsize dynexport_c_lengths(FfiVecString words);
+FfiOptionUsize dynexport_c_find(FfiStr haystack, uint32_t needle);
+Point dynexport_c_midpoint(Point a, Point b);
+double dynexport_c_area(Shape shape);
+FfiOptionPoint dynexport_c_center(Shape shape);
+
+static FfiStr borrow(const char *s) {
+    FfiStr str = { (const uint8_t *)s, strlen(s) };
+    return str;
+}
+
+static FfiString own(const char *s) {
+    FfiString string = { malloc(strlen(s) + 1), strlen(s) };
+    memcpy(string.ptr, s, string.len + 1);
+    return string;
+}
+
+int main(void) {
+    FfiString greeting = dynexport_c_greet(borrow("C"));
+    assert(greeting.len == 9);
+    assert(strcmp((const char *)greeting.ptr, "Hello, C!") == 0);
+    free(greeting.ptr);
+
+    FfiVecString words = { malloc(2 * sizeof(FfiString)), 2 };
+    words.ptr[0] = own("one");
+    words.ptr[1] = own("three");
+    FfiVecUsize lengths = dynexport_c_lengths(words);
+    assert(lengths.len == 2 && lengths.ptr[0] == 3 && lengths.ptr[1] == 5);
+    free(lengths.ptr);
+
+    FfiOptionUsize found = dynexport_c_find(borrow("hello"), 'l');
+    assert(found.is_some && found.value == 2);
+    assert(!dynexport_c_find(borrow("hello"), 'z').is_some);
+
+    Point a = { 0.0, 2.0 }, b = { 4.0, 4.0 };
+    Point mid = dynexport_c_midpoint(a, b);
+    assert(mid.x == 2.0 && mid.y == 3.0);
+
+    Shape circle = { .tag = SHAPE_CIRCLE, .circle = { { 1.0, 1.0 }, 2.0 } };
+    assert(dynexport_c_area(circle) == 12.0);
+    FfiOptionPoint center = dynexport_c_center(circle);
+    assert(center.is_some && center.value.x == 1.0);
+
+    Shape dot = { .tag = SHAPE_DOT, .dot = { { 5.0, 6.0 } } };
+    assert(dynexport_c_area(dot) == 0.0);
+    assert(dynexport_c_center(dot).value.y == 6.0);
+
+    Shape empty = { .tag = SHAPE_EMPTY };
+    assert(!dynexport_c_center(empty).is_some);
+    return 0;
+}
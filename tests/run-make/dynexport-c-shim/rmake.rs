@@ -0,0 +1,16 @@
+// Every `#[dynexport]` Rust-ABI fn is also exported as an `extern "C"` shim that passes
+// `String`, `Vec`, `Option` and `#[dynexport]` structs and enums in a documented C layout.
+// A C program declares that layout by hand and calls the shims of a cdylib.
+
+//@ only-linux
+// Reason: the representation's buffers come from the C allocator of the same libc
+//@ ignore-cross-compile
+// Reason: the C program is executed
+
+use run_make_support::{cc, cwd, run, rustc};
+
+fn main() {
+    rustc().input("shapes.rs").run();
+    cc().input("main.c").arg("-lshapes").library_search_path(cwd()).output("main").run();
+    run("main");
+}
@@ -0,0 +1,50 @@
+#![crate_type = "cdylib"]
+
+#[dynexport]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+#[dynexport]
+pub enum Shape {
+    Dot(Point),
+    Circle { center: Point, radius: f64 },
+    Empty,
+}
+
+#[dynexport]
+pub fn greet(name: &str) -> String {
+    format!("Hello, {name}!")
+}
+
+#[dynexport]
+pub fn lengths(words: Vec<String>) -> Vec<usize> {
+    words.iter().map(|word| word.len()).collect()
+}
+
+#[dynexport]
+pub fn find(haystack: &str, needle: char) -> Option<usize> {
+    haystack.find(needle)
+}
+
+#[dynexport]
+pub fn midpoint(a: Point, b: Point) -> Point {
+    Point { x: (a.x + b.x) / 2.0, y: (a.y + b.y) / 2.0 }
+}
+
+#[dynexport]
+pub fn area(shape: Shape) -> f64 {
+    match shape {
+        Shape::Circle { radius, .. } => 3.0 * radius * radius,
+        Shape::Dot(_) | Shape::Empty => 0.0,
+    }
+}
+
+#[dynexport]
+pub fn center(shape: Shape) -> Option<Point> {
+    match shape {
+        Shape::Dot(point) | Shape::Circle { center: point, .. } => Some(point),
+        Shape::Empty => None,
+    }
+}
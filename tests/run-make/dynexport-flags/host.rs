@@ -0,0 +1,28 @@
+#!/usr/bin/env rust
+
+import lib "libplugin.so" {
+    #[dynexport(version = 1)]
+    fn add(a: i32, b: i32) -> i32
+}
+
+import lib "libplugin.so" as exact {
+    #[dynexport(version = 2)]
+    fn add(a: i32, b: i32) -> i32
+}
+
+import lib "libplugin.so" as newer {
+    #[dynexport(version = 3)]
+    fn add(a: i32, b: i32) -> i32
+}
+
+assert!(plugin.load().is_ok());
+assert_eq!(plugin.add(2, 3), 5);
+assert!(exact.load().is_ok());
+
+match newer.load() {
+    Err(DynloadError::VersionTooOld { symbol, required, found, .. }) => {
+        assert_eq!(symbol, "add");
+        assert_eq!((required, found), (3, 2));
+    }
+    other => panic!("expected a version error, got {other:?}"),
+}
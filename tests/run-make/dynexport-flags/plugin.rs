@@ -0,0 +1,22 @@
+#![crate_type = "cdylib"]
+
+// Rust ABI, may unwind, has a C shim.
+#[dynexport(version = 2)]
+pub fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+// No C shim: raw pointers have no FFI representation of their own.
+#[dynexport]
+pub fn first(bytes: *const u8) -> u8 {
+    unsafe { *bytes }
+}
+
+// A panic can't leave an `extern "C"` fn.
+#[dynexport]
+pub extern "C" fn halve(x: u32) -> u32 {
+    x / 2
+}
+
+#[dynexport(version = 7)]
+pub static LIMIT: u64 = 64;
@@ -0,0 +1,51 @@
+// The `flags` word of each `dynexport_meta_<name>` record says whether the item is a static,
+// whether it can unwind into its caller and whether it has a `dynexport_c_<name>` shim, and
+// carries the `N` of `#[dynexport(version = N)]` in its upper half. `import lib` declarations
+// with `#[dynexport(version = N)]` reject items with a lower version.
+
+//@ only-linux
+// Reason: the host names the library `libplugin.so` and loads it with dlopen
+//@ ignore-cross-compile
+// Reason: the compiled host is executed
+
+use run_make_support::{run, rustc};
+
+fn main() {
+    rustc().input("plugin.rs").run();
+
+    let listing = rustc()
+        .arg("-Zunstable-options")
+        .arg("--inspect-dynexport")
+        .arg("libplugin.so")
+        .run()
+        .stdout_utf8();
+    // Items are listed as blocks separated by blank lines, the description first.
+    let block = |name: &str| {
+        let prefix = format!("{name}: ");
+        listing.split("\n\n").find(|block| block.contains(&prefix)).expect(name).to_string()
+    };
+    // bit 0 static, bit 1 nounwind, bit 2 C shim, version << 16
+    for (name, flags, description) in [
+        ("add", 0x0002_0004, "fn, c-shim, version 2"),
+        ("first", 0x0000_0000, "fn, version 0"),
+        ("halve", 0x0000_0002, "fn, nounwind, version 0"),
+        ("LIMIT", 0x0007_0001, "static, version 7"),
+    ] {
+        let block = block(name);
+        let expected = format!("flags {flags:#010x} ({description})");
+        assert!(block.contains(&expected), "expected `{expected}` for {name}:\n{block}");
+    }
+
+    rustc()
+        .arg("-Zunstable-options")
+        .arg("--inspect-dynexport")
+        .arg("libplugin.so")
+        .arg("--inspect-format")
+        .arg("json")
+        .run()
+        .assert_stdout_contains(r#""version":7"#);
+
+    // An explicit output keeps the script from being cached and run by the compiler.
+    rustc().input("host.rs").output("host").run();
+    run("host");
+}
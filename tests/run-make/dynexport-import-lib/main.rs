@@ -0,0 +1,29 @@
+#!/usr/bin/env rust
+
+import lib "libmathlib.so" {
+    fn add(a: i32, b: i32) -> i32
+    fn greet(name: &str) -> String
+}
+
+// Same library, but `add` declared with the wrong types.
+import lib "libmathlib.so" as wrong {
+    fn add(a: i64, b: i64) -> i64
+}
+
+import lib "libmissing.so" {
+    fn add(a: i32, b: i32) -> i32
+}
+
+assert!(mathlib.load().is_ok());
+assert_eq!(mathlib.add(2, 3), 5);
+assert_eq!(mathlib.greet("dynexport"), "Hello, dynexport!");
+
+match wrong.load() {
+    Err(DynloadError::TypeMismatch { symbol, .. }) => assert_eq!(symbol, "add"),
+    other => panic!("expected a type mismatch, got {other:?}"),
+}
+
+match missing.load() {
+    Err(DynloadError::LibraryNotFound { .. }) => {}
+    other => panic!("expected a missing library, got {other:?}"),
+}
@@ -0,0 +1,11 @@
+#![crate_type = "cdylib"]
+
+#[dynexport]
+pub fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[dynexport]
+pub fn greet(name: &str) -> String {
+    format!("Hello, {name}!")
+}
@@ -0,0 +1,17 @@
+// `import lib` in script mode loads `#[dynexport]` functions from a shared library and
+// checks each one against its `dynexport_meta_<name>` record: matching signatures can be
+// called, a mismatched declaration and a missing library surface as `DynloadError`s.
+
+//@ only-linux
+// Reason: the script names the library `libmathlib.so` and loads it with dlopen
+//@ ignore-cross-compile
+// Reason: the compiled script is executed
+
+use run_make_support::{run, rustc};
+
+fn main() {
+    rustc().input("mathlib.rs").run();
+    // An explicit output keeps the script from being cached and run by the compiler.
+    rustc().input("main.rs").output("main").run();
+    run("main");
+}
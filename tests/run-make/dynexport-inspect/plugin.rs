@@ -0,0 +1,39 @@
+#![crate_type = "cdylib"]
+
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+#[dynexport]
+pub fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[cfg(not(v2))]
+#[dynexport]
+pub fn scale(p: &Point, by: f64) -> Point {
+    Point { x: p.x * by, y: p.y * by }
+}
+
+// v2 widens the factor, which breaks callers built against v1.
+#[cfg(v2)]
+#[dynexport]
+pub fn scale(p: &Point, by: f32) -> Point {
+    Point { x: p.x * by as f64, y: p.y * by as f64 }
+}
+
+#[cfg(not(v2))]
+#[dynexport]
+pub fn legacy() -> u32 {
+    1
+}
+
+#[cfg(v2)]
+#[dynexport]
+pub fn version() -> u32 {
+    2
+}
+
+#[dynexport]
+pub static LIMIT: u64 = 64;
@@ -0,0 +1,92 @@
+// `rustc --inspect-dynexport` lists the `#[dynexport]` items of a shared library from its
+// `dynexport_meta_<name>` and `dynexport_wit_<name>` symbols, and with two libraries reports
+// added, removed and changed items, failing when the second one breaks users of the first.
+// Both options are unstable.
+
+//@ only-elf
+//@ ignore-cross-compile
+
+use run_make_support::object::{Object, ObjectSection, ObjectSymbol};
+use run_make_support::{object, rfs, rustc};
+
+fn main() {
+    rustc().input("plugin.rs").output("libv1.so").run();
+    rustc().input("plugin.rs").cfg("v2").output("libv2.so").run();
+    // A rebuild from the same source is compatible with itself.
+    rustc().input("plugin.rs").output("libv1-again.so").run();
+
+    let listing = rustc()
+        .arg("-Zunstable-options")
+        .arg("--inspect-dynexport")
+        .arg("libv1.so")
+        .run()
+        .stdout_utf8();
+    assert!(listing.starts_with("libv1.so: 4 #[dynexport] item(s)"), "{listing}");
+    for line in [
+        "add: func(a: s32, b: s32) -> s32;",
+        "record point { x: f64, y: f64 }",
+        "scale: func(p: point, by: f64) -> point;",
+        "legacy: func() -> u32;",
+        "LIMIT: static u64;",
+        "(this compiler)",
+    ] {
+        assert!(listing.contains(line), "missing `{line}` in:\n{listing}");
+    }
+
+    let json = rustc()
+        .arg("-Zunstable-options")
+        .arg("--inspect-dynexport")
+        .arg("libv1.so")
+        .arg("--inspect-format")
+        .arg("json")
+        .run()
+        .stdout_utf8();
+    assert!(json.contains(r#""library":"libv1.so""#), "{json}");
+    assert!(json.contains(r#""signature":"add: func(a: s32, b: s32) -> s32;""#), "{json}");
+    assert!(json.contains(r#""same_compiler":true"#), "{json}");
+
+    rustc()
+        .arg("-Zunstable-options")
+        .arg("--inspect-dynexport")
+        .arg("libv1.so")
+        .arg("--inspect-dynexport")
+        .arg("libv1-again.so")
+        .run()
+        .assert_stdout_contains("no changes");
+
+    let drift = rustc()
+        .arg("-Zunstable-options")
+        .arg("--inspect-dynexport")
+        .arg("libv1.so")
+        .arg("--inspect-dynexport")
+        .arg("libv2.so")
+        .run_fail();
+    drift
+        .assert_stdout_contains("+ version: func() -> u32;")
+        .assert_stdout_contains("- legacy: func() -> u32;")
+        .assert_stdout_contains("~ scale")
+        .assert_stdout_contains("    now scale: func(p: point, by: f32) -> point;")
+        .assert_stderr_contains("`libv2.so` is not ABI compatible with `libv1.so`");
+
+    // The same items from another compiler: the loader rejects them, so it is drift too.
+    let mut data = rfs::read("libv1.so");
+    let record = {
+        let file = object::File::parse(&*data).unwrap();
+        let symbol = file.dynamic_symbols().find(|s| s.name() == Ok("dynexport_meta_add")).unwrap();
+        let section = file.section_by_index(symbol.section_index().unwrap()).unwrap();
+        let (offset, _) = section.file_range().unwrap();
+        (offset + symbol.address() - section.address()) as usize
+    };
+    // Bytes 8..12 of the record hold the compiler hash.
+    data[record + 8] ^= 0xff;
+    rfs::write("libv1-other-compiler.so", data);
+    rustc()
+        .arg("-Zunstable-options")
+        .arg("--inspect-dynexport")
+        .arg("libv1.so")
+        .arg("--inspect-dynexport")
+        .arg("libv1-other-compiler.so")
+        .run_fail()
+        .assert_stdout_contains("~ add")
+        .assert_stderr_contains("`libv1-other-compiler.so` is not ABI compatible with `libv1.so`");
+}
@@ -0,0 +1,25 @@
+#!/usr/bin/env rust
+
+import lib "libplugin.so" {
+    fn add(a: i32, b: i32) -> i32
+}
+
+// Discover what the plugin exports without a header.
+let interface = plugin.interface().unwrap();
+assert_eq!(interface, DynexportInterface::read("libplugin.so").unwrap());
+assert_eq!(interface.functions().len(), 3);
+
+let add = interface.item("add").unwrap();
+assert_eq!(add.signature, "func(a: s32, b: s32) -> s32");
+assert_eq!(add.params, vec![("a".to_string(), "s32".to_string()), ("b".to_string(), "s32".to_string())]);
+assert_eq!(add.result.as_deref(), Some("s32"));
+assert_eq!(plugin.add(2, 3), 5);
+
+let version = interface.item("VERSION").unwrap();
+assert!(version.is_static);
+assert_eq!(version.result.as_deref(), Some("u32"));
+
+assert!(interface.types.contains(&"record point { x: f64, y: f64 }".to_string()));
+let wit = interface.to_wit("plugin");
+assert!(wit.starts_with("interface plugin {\n"));
+assert!(wit.contains("    names: func(filter: option<color>) -> list<string>;\n"));
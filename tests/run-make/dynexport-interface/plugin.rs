@@ -0,0 +1,45 @@
+#![crate_type = "cdylib"]
+
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+pub enum Shape {
+    Dot(Point),
+    Circle { center: Point, radius: f64 },
+}
+
+pub enum Color {
+    Red,
+    Green,
+}
+
+#[dynexport]
+pub fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[dynexport]
+pub fn area(shape: &Shape) -> f64 {
+    match shape {
+        Shape::Dot(_) => 0.0,
+        Shape::Circle { radius, .. } => 3.0 * radius * radius,
+    }
+}
+
+#[dynexport]
+pub fn names(filter: Option<Color>) -> Vec<String> {
+    let all = [("red", Color::Red), ("green", Color::Green)];
+    all.into_iter()
+        .filter(|(_, color)| match (&filter, color) {
+            (None, _) => true,
+            (Some(Color::Red), Color::Red) | (Some(Color::Green), Color::Green) => true,
+            _ => false,
+        })
+        .map(|(name, _)| name.to_string())
+        .collect()
+}
+
+#[dynexport]
+pub static VERSION: u32 = 3;
@@ -0,0 +1,34 @@
+// `#[dynexport]` items carry a WIT-style description of their signature and the types it
+// uses in the `.rustc_dynexport_wit` section. Check the descriptions the compiler writes, then
+// read them back at runtime from a script that loads the library.
+
+//@ only-linux
+// Reason: the host names the library `libplugin.so` and reads it as ELF
+//@ ignore-cross-compile
+// Reason: the compiled host is executed
+
+use run_make_support::{llvm_objcopy, rfs, run, rustc};
+
+fn main() {
+    rustc().input("plugin.rs").run();
+
+    llvm_objcopy().dump_section(".rustc_dynexport_wit", "plugin.wit").arg("libplugin.so").run();
+    let section = String::from_utf8(rfs::read("plugin.wit")).unwrap();
+    let mut descriptions: Vec<&str> = section.split('\0').filter(|d| !d.is_empty()).collect();
+    descriptions.sort();
+    assert_eq!(
+        descriptions,
+        [
+            "VERSION: static u32;",
+            "add: func(a: s32, b: s32) -> s32;",
+            "enum color { red, green }\nnames: func(filter: option<color>) -> list<string>;",
+            "record point { x: f64, y: f64 }\n\
+             variant shape { dot(point), circle(tuple<point, f64>) }\n\
+             area: func(shape: shape) -> f64;",
+        ]
+    );
+
+    // An explicit output keeps the script from being cached and run by the compiler.
+    rustc().input("host.rs").output("host").run();
+    run("host");
+}
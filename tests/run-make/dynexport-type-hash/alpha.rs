@@ -0,0 +1,16 @@
+#![crate_name = "alpha"]
+
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+pub enum Shape {
+    Dot(Point),
+    Segment { from: Point, to: Point },
+}
+
+#[dynexport]
+pub fn describe(name: &str, shapes: Vec<Shape>, scale: Option<f64>) -> String {
+    format!("{name}: {} shapes, scale {scale:?}", shapes.len())
+}
@@ -0,0 +1,23 @@
+#![crate_name = "beta"]
+
+// Same signature as in `alpha.rs`, but the types live in a nested module of a differently
+// named crate and the function body differs.
+pub mod geometry {
+    pub struct Point {
+        pub x: f64,
+        pub y: f64,
+    }
+
+    pub enum Shape {
+        Dot(Point),
+        Segment { from: Point, to: Point },
+    }
+}
+
+use geometry::Shape;
+
+#[dynexport]
+pub fn describe(label: &str, items: Vec<Shape>, factor: Option<f64>) -> String {
+    let _ = (label, items, factor);
+    String::new()
+}
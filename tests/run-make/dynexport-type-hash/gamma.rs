@@ -0,0 +1,17 @@
+#![crate_name = "gamma"]
+
+// Like `alpha.rs`, except that `Point`'s fields are named differently.
+pub struct Point {
+    pub lat: f64,
+    pub lon: f64,
+}
+
+pub enum Shape {
+    Dot(Point),
+    Segment { from: Point, to: Point },
+}
+
+#[dynexport]
+pub fn describe(name: &str, shapes: Vec<Shape>, scale: Option<f64>) -> String {
+    format!("{name}: {} shapes, scale {scale:?}", shapes.len())
+}
@@ -0,0 +1,28 @@
+// `#[dynexport]` items carry a structural hash of their type in the `.rustc_dynexport` section.
+// Crates compiled separately must agree on the hash when the signatures match structurally,
+// regardless of crate name, module paths or parameter names, and disagree as soon as a
+// layout-relevant detail such as a field name changes.
+
+//@ only-elf
+//@ ignore-cross-compile
+
+use run_make_support::{llvm_objcopy, rfs, rustc};
+
+fn type_hash(krate: &str) -> Vec<u8> {
+    rustc().input(format!("{krate}.rs")).crate_type("rlib").emit("obj").run();
+    let dump = format!("{krate}.dynexport");
+    llvm_objcopy().dump_section(".rustc_dynexport", &dump).arg(format!("{krate}.o")).run();
+    // { u64 type_hash, u32 compiler_version, u32 flags }
+    let section = rfs::read(&dump);
+    assert_eq!(section.len(), 16, "expected exactly one dynexport record in {krate}");
+    section[..8].to_vec()
+}
+
+fn main() {
+    let alpha = type_hash("alpha");
+    let beta = type_hash("beta");
+    let gamma = type_hash("gamma");
+
+    assert_eq!(alpha, beta, "structurally identical signatures must hash the same");
+    assert_ne!(alpha, gamma, "renaming a field must change the hash");
+}
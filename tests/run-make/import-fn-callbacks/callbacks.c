@@ -0,0 +1,56 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdio.h>
+#include <string.h>
+
+// Calls `visit` with each number below `n`.
+void for_each_number(int n, void (*visit)(void *data, int value), void *data) {
+    for (int i = 0; i < n; i++) {
+        visit(data, i);
+    }
+}
+
+// Sums what `weigh` returns for each space-separated word of `text`.
+int weigh_words(void *data, const char *text, int (*weigh)(void *data, const char *word)) {
+    char word[64];
+    int total = 0;
+    while (*text) {
+        size_t len = strcspn(text, " ");
+        if (len >= sizeof word) {
+            len = sizeof word - 1;
+        }
+        memcpy(word, text, len);
+        word[len] = '\0';
+        total += weigh(data, word);
+        text += strcspn(text, " ");
+        text += *text == ' ';
+    }
+    return total;
+}
+
+// Counts the ASCII characters of `text` that `keep` accepts.
+int count_kept(const char *text, bool (*keep)(void *data, unsigned int c), void *data) {
+    int count = 0;
+    for (; *text; text++) {
+        count += keep(data, (unsigned char)*text);
+    }
+    return count;
+}
+
+const char *greeting(const char *name) {
+    static char buffer[64];
+    snprintf(buffer, sizeof buffer, "hello, %s", name);
+    return buffer;
+}
+
+// Sums `count` long long arguments.
+long long sum_numbers(int count, ...) {
+    va_list args;
+    va_start(args, count);
+    long long sum = 0;
+    for (int i = 0; i < count; i++) {
+        sum += va_arg(args, long long);
+    }
+    va_end(args);
+    return sum;
+}
@@ -0,0 +1,35 @@
+#!/usr/bin/env rust
+
+#[link(name = "callbacks")]
+import fn for_each_number(n: int, visit: fn(*mut c_void, int), data: *mut c_void);
+#[link(name = "callbacks")]
+import fn weigh_words(data: *mut c_void, text: string, weigh: fn(*mut c_void, string) -> int) int;
+#[link(name = "callbacks")]
+import fn count_kept(text: string, keep: fn(*mut c_void, rune) -> boolean, data: *mut c_void) int;
+#[link(name = "callbacks")]
+import fn greeting(name: string) string;
+#[link(name = "callbacks")]
+import fn sum_numbers(count: int, ...) -> i64;
+
+// The closure is passed through the userdata pointer, so it may borrow local state.
+let mut seen = Vec::new();
+unsafe { for_each_number(4, x => seen.push(x * 10)) };
+eq!(seen, vec![0, 10, 20, 30]);
+
+let mut words = Vec::new();
+total := unsafe {
+    weigh_words("alpha be c", |word| {
+        words.push(word.to_uppercase());
+        word.len() as int
+    })
+};
+eq!(total, 8);
+eq!(words, vec!["ALPHA", "BE", "C"]);
+
+vowels := unsafe { count_kept("userdata pointer", c => "aeiou".contains(c)) };
+eq!(vowels, 7);
+
+eq!(unsafe { greeting("script") }, "hello, script");
+eq!(unsafe { sum_numbers(3, 1, 20, 300) }, 321);
+
+put!("ok");
@@ -0,0 +1,15 @@
+// `import fn` declarations with `string` parameters, a C-variadic `...` or callbacks in script
+// mode. Closures passed for `fn(*mut c_void, ..)` parameters are called back from C through
+// the userdata pointer, and strings cross in both directions.
+
+//@ ignore-cross-compile
+// Reason: the compiled script is executed
+
+use run_make_support::{build_native_static_lib, run, rustc};
+
+fn main() {
+    build_native_static_lib("callbacks");
+    // An explicit output keeps the script from being cached and run by the compiler.
+    rustc().input("main.rs").output("main").run();
+    run("main").assert_stdout_contains("ok");
+}
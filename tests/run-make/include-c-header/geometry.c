@@ -0,0 +1,58 @@
+#include <math.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "geometry.h"
+
+struct cache {
+    int hits;
+};
+
+double geometry_distance(point a, point b) {
+    return sqrt((a.x - b.x) * (a.x - b.x) + (a.y - b.y) * (a.y - b.y));
+}
+
+int geometry_clamp(int value, int lo, int hi) {
+    return value < lo ? lo : value > hi ? hi : value;
+}
+
+double geometry_area(shape_kind kind, double size) {
+    switch (kind) {
+    case SHAPE_CIRCLE: return 3.0 * size * size;
+    case SHAPE_SQUARE: return size * size;
+    case SHAPE_TRIANGLE: return size * size / 2.0;
+    }
+    return -1.0;
+}
+
+void geometry_polygon_push(polygon *poly, point p) {
+    if (poly->len < GEOMETRY_MAX_POINTS) {
+        poly->points[poly->len++] = p;
+    }
+}
+
+double geometry_polygon_sum(const polygon *poly, point_fn f, void *data) {
+    double sum = 0.0;
+    for (size_t i = 0; i < poly->len; i++) {
+        sum += f(poly->points[i], data);
+    }
+    return sum;
+}
+
+size_t geometry_name_len(const char *name) {
+    return strlen(name);
+}
+
+cache *geometry_cache_new(void) {
+    cache *c = malloc(sizeof(cache));
+    c->hits = 7;
+    return c;
+}
+
+int geometry_cache_hits(const cache *c) {
+    return c->hits;
+}
+
+void geometry_cache_free(cache *c) {
+    free(c);
+}
@@ -0,0 +1,50 @@
+#ifndef GEOMETRY_H
+#define GEOMETRY_H
+
+#include <stddef.h>
+#include "geometry_types.h"
+
+#ifdef _MSC_VER
+#  define GEOMETRY_API __declspec(dllexport)
+#else
+#  define GEOMETRY_API extern
+#endif
+
+#define GEOMETRY_VERSION 3
+#define GEOMETRY_MAX_POINTS (1 << 4)
+#define GEOMETRY_FLAG(n) (1u << (n))
+#define GEOMETRY_CLOSED GEOMETRY_FLAG(2)
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Called for each point of a polygon with the caller's data. */
+typedef double (*point_fn)(point p, void *data);
+
+typedef struct polygon {
+    point points[GEOMETRY_MAX_POINTS];
+    size_t len;
+    unsigned flags;
+} polygon;
+
+/* Only declared, so only usable behind a pointer. */
+typedef struct cache cache;
+
+GEOMETRY_API double geometry_distance(point a, point b);
+GEOMETRY_API int geometry_clamp(int value, int lo, int hi);
+GEOMETRY_API double geometry_area(shape_kind kind, double size);
+GEOMETRY_API void geometry_polygon_push(polygon *poly, point p);
+GEOMETRY_API double geometry_polygon_sum(const polygon *poly, point_fn f, void *data);
+GEOMETRY_API size_t geometry_name_len(const char *name);
+GEOMETRY_API cache *geometry_cache_new(void);
+GEOMETRY_API int geometry_cache_hits(const cache *c);
+GEOMETRY_API void geometry_cache_free(cache *c);
+
+static inline int geometry_unused(void) { return 0; }
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,10 @@
+#ifndef GEOMETRY_TYPES_H
+#define GEOMETRY_TYPES_H
+
+typedef struct point {
+    double x, y;
+} point;
+
+typedef enum { SHAPE_CIRCLE, SHAPE_SQUARE = 4, SHAPE_TRIANGLE } shape_kind;
+
+#endif
@@ -0,0 +1,54 @@
+#!/usr/bin/env rust
+
+include "geometry.h"
+include shapes from "geometry_types.h"
+
+eq!(geometry.GEOMETRY_VERSION, 3)
+eq!(geometry.GEOMETRY_MAX_POINTS, 16)
+eq!(geometry.GEOMETRY_CLOSED, 4)
+
+// Every function is called in `unsafe`, even one taking and returning only numbers.
+eq!(unsafe { geometry.geometry_clamp(42, 0, 10) }, 10)
+eq!(unsafe { geometry.geometry_clamp(-3, 0, 10) }, 0)
+
+// Structs are #[repr(C)] and enums integer newtypes, passed by value.
+origin := geometry::point { x: 0.0, y: 0.0 }
+corner := geometry::point { x: 3.0, y: 4.0 }
+eq!(unsafe { geometry.geometry_distance(origin, corner) }, 5.0)
+eq!(unsafe { geometry.geometry_area(geometry.SHAPE_SQUARE, 3.0) }, 9.0)
+eq!(geometry.SHAPE_TRIANGLE.0, 5)
+
+let mut poly = geometry::polygon {
+    points: [origin; 16],
+    len: 0,
+    flags: geometry.GEOMETRY_CLOSED as u32,
+};
+unsafe {
+    geometry.geometry_polygon_push(&mut poly, corner);
+    geometry.geometry_polygon_push(&mut poly, geometry::point { x: 1.0, y: 1.0 });
+}
+eq!(poly.len, 2)
+eq!(poly.points[0].y, 4.0)
+
+// Function pointer typedefs are `Option<unsafe extern "C" fn(..)>`.
+extern "C" fn weighted(p: geometry::point, data: *mut std::ffi::c_void) -> f64 {
+    let weight = unsafe { *(data as *const f64) };
+    (p.x + p.y) * weight
+}
+let mut weight = 10.0f64;
+let data = &mut weight as *mut f64 as *mut std::ffi::c_void;
+eq!(unsafe { geometry.geometry_polygon_sum(&poly, Some(weighted), data) }, 90.0)
+
+eq!(unsafe { geometry.geometry_name_len(c"polygon".as_ptr()) }, 7)
+
+// A struct the header only declares is opaque.
+unsafe {
+    cache := geometry.geometry_cache_new()
+    eq!(geometry.geometry_cache_hits(cache), 7)
+    geometry.geometry_cache_free(cache)
+}
+
+// The module's name doesn't have to match the header's.
+eq!(shapes.SHAPE_SQUARE.0, 4)
+
+put!("All include tests passed!")
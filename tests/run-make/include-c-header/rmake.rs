@@ -0,0 +1,15 @@
+// `include "geometry.h"` in script mode reads a C header and declares its functions, structs,
+// enums and integer #defines in a module `geometry` linking `libgeometry`. The script calls
+// into a static library built from geometry.c.
+
+//@ ignore-cross-compile
+// Reason: the compiled script is executed
+
+use run_make_support::{build_native_static_lib, run, rustc};
+
+fn main() {
+    build_native_static_lib("geometry");
+    // An explicit output keeps the script from being cached and run by the compiler.
+    rustc().input("main.rs").output("main").run();
+    run("main");
+}
@@ -0,0 +1,5 @@
+#!/usr/bin/env rust
+
+import "./helper.rust"
+
+put!(helper.Message())
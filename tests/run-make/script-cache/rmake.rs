@@ -0,0 +1,39 @@
+// Shebang scripts are built into $XDG_CACHE_HOME/rust-scripts and run from there. A rerun
+// executes the cached binary, editing an imported file rebuilds it, and building a script
+// deletes entries that haven't been run for 30 days.
+
+//@ ignore-cross-compile
+// Reason: the compiled script is executed
+
+use std::time::{Duration, SystemTime};
+
+use run_make_support::{path, rfs, rustc};
+
+fn main() {
+    let cache = path("cache");
+    let scripts = cache.join("rust-scripts");
+    let run_script = || rustc().input("main.rs").env("XDG_CACHE_HOME", &cache).run();
+
+    // An entry of some other script, last run 60 days ago.
+    let stale = scripts.join("stale");
+    rfs::create_dir_all(&stale);
+    rfs::write(stale.join("deps"), "");
+    let long_ago = SystemTime::now() - Duration::from_secs(60 * 24 * 60 * 60);
+    let manifest = std::fs::File::options().append(true).open(stale.join("deps")).unwrap();
+    manifest.set_modified(long_ago).unwrap();
+
+    run_script().assert_stdout_contains("first");
+    assert!(!stale.exists(), "unused entry was not evicted");
+    let entries = rfs::shallow_find_dir_entries(&scripts);
+    let [entry] = entries.as_slice() else { panic!("expected one cache entry: {entries:?}") };
+    let exe = entry.join("main");
+    let built = rfs::metadata(&exe).modified().unwrap();
+
+    // Nothing changed: the cached binary runs as is.
+    run_script().assert_stdout_contains("first");
+    assert_eq!(rfs::metadata(&exe).modified().unwrap(), built, "script was rebuilt");
+
+    // Only the imported file changes, which must still invalidate the entry.
+    rfs::write("helper.rust", "fn Message() string {\n    return \"second\"\n}\n");
+    run_script().assert_stdout_contains("second").assert_stdout_not_contains("first");
+}
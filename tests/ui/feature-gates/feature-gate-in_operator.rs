@@ -0,0 +1,4 @@
+fn main() {
+    let v = vec![1, 2, 3];
+    let _ = 2 in v; //~ ERROR the `in` operator is experimental
+}
@@ -0,0 +1,8 @@
+//@ compile-flags: -Zscript
+//@ dont-check-compiler-stderr
+// `import_both` names both a folder of scripts and a script file; the import
+// has to say which one it means.
+
+import "./import_both"; //~ ERROR names both a folder and a script file
+
+fn main() {}
@@ -0,0 +1,29 @@
+//@ compile-flags: -Zscript
+// A script `try` body runs as a closure, so `return`, `break` and `continue` can't leave it.
+
+fn find(xs: &[i64]) -> i64 {
+    for x in xs {
+        try {
+            if *x > 2 {
+                break;
+            }
+            if *x == 0 {
+                continue;
+            }
+            // Loops inside the body are fine.
+            for y in xs {
+                if y == x {
+                    break;
+                }
+            }
+        } catch _ {}
+    }
+    try {
+        return 1;
+    } catch _ {}
+    0
+}
+
+fn main() {
+    find(&[1, 2, 3]);
+}
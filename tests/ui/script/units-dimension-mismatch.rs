@@ -0,0 +1,12 @@
+//@ compile-flags: -Zscript
+//@ dont-check-compiler-stderr
+// Unit-suffixed literals carry their dimension in the type, so quantities of
+// different dimensions can't be added, subtracted or compared.
+
+fn main() {
+    let _ = 1m + 1s; //~ ERROR mismatched types
+    let _ = 5km - 2h; //~ ERROR mismatched types
+    let _ = 2km < 3h; //~ ERROR mismatched types
+    let _ = 10m + 20m;
+    let _ = 10m / 2s;
+}